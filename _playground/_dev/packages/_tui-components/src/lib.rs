@@ -8,6 +8,8 @@ pub mod list_panel;
 pub mod form_panel;
 pub mod toast;
 pub mod file_browser;
+#[cfg(feature = "testing")]
+pub mod testing;
 
 // Re-export commonly used types and functions
 pub use helpers::{hex_color, get_selection_style, accent_color, centered_rect};
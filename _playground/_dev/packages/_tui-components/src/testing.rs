@@ -0,0 +1,85 @@
+// Headless UI test harness - screen text, snapshot files and scratch dirs
+//
+// Enabled by the `testing` feature, for dev-dependencies only. Crates on a
+// different ratatui/crossterm than this one get `render`/`keys` through
+// `ui_test_harness!`, which expands against the caller's own versions.
+
+use std::path::{Path, PathBuf};
+
+/// Cell symbols of a `width`-wide buffer as lines, trailing spaces trimmed and passed through `line`
+pub fn screen_text<'a>(width: u16, symbols: impl IntoIterator<Item = &'a str>, line: impl Fn(&str) -> String) -> String {
+    let symbols: Vec<&str> = symbols.into_iter().collect();
+    symbols
+        .chunks(width.max(1) as usize)
+        .map(|row| line(row.concat().trim_end()))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Compare `actual` with `dir/<name>.txt`; with UPDATE_SNAPSHOTS set, write it instead
+pub fn assert_snapshot(dir: impl AsRef<Path>, name: &str, actual: &str) {
+    let path = dir.as_ref().join(format!("{}.txt", name));
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        std::fs::write(&path, format!("{}\n", actual)).unwrap();
+        return;
+    }
+    let expected = std::fs::read_to_string(&path)
+        .unwrap_or_else(|_| panic!("No snapshot {} - run with UPDATE_SNAPSHOTS=1 to create it", path.display()));
+    assert!(expected.trim_end() == actual.trim_end(),
+        "Snapshot {} differs (UPDATE_SNAPSHOTS=1 to approve)\n--- expected\n{}\n--- actual\n{}", name, expected, actual);
+}
+
+/// Temp directory private to one test, removed when dropped
+pub struct ScratchDir(PathBuf);
+
+impl ScratchDir {
+    pub fn new(prefix: &str, name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("{}-{}-{}", prefix, std::process::id(), name));
+        // Left over from a run that panicked before the drop
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).unwrap();
+        Self(path)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for ScratchDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Defines `render`, `keys` and `assert_snapshot` for a ui_snapshots test file.
+///
+/// `handle` is the crate's `events::handle_event`, `draw` its `ui::ui`; snapshots
+/// live in `tests/snapshots`. An optional `line` function rewrites every rendered
+/// line, e.g. to mask clock-dependent text.
+#[macro_export]
+macro_rules! ui_test_harness {
+    ($app:ty, handle: $handle:path, draw: $draw:path, size: ($width:expr, $height:expr)) => {
+        $crate::ui_test_harness!($app, handle: $handle, draw: $draw, size: ($width, $height), line: ToString::to_string);
+    };
+    ($app:ty, handle: $handle:path, draw: $draw:path, size: ($width:expr, $height:expr), line: $line:path) => {
+        /// Rendered screen as text, trailing spaces trimmed
+        fn render(app: &mut $app) -> String {
+            let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new($width, $height)).unwrap();
+            terminal.draw(|f| $draw(f, app)).unwrap();
+            let buffer = terminal.backend().buffer();
+            $crate::testing::screen_text(buffer.area.width, buffer.content.iter().map(|cell| cell.symbol()), $line)
+        }
+
+        fn keys(app: &mut $app, codes: &[crossterm::event::KeyCode]) {
+            use crossterm::event::{Event, KeyEvent, KeyModifiers};
+            for code in codes {
+                $handle(Event::Key(KeyEvent::new(*code, KeyModifiers::NONE)), app);
+            }
+        }
+
+        fn assert_snapshot(name: &str, actual: &str) {
+            $crate::testing::assert_snapshot(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/snapshots"), name, actual);
+        }
+    };
+}
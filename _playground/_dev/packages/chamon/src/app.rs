@@ -153,6 +153,11 @@ pub struct App { //>
 
 impl App {
     pub fn new() -> App { //>
+        Self::with_data_dir(PathBuf::from("/home/pi/_playground/_dev/packages/chamon/data"))
+    } //<
+    
+    /// App reading baselines from `data_dir` instead of the installed location
+    pub fn with_data_dir(data_dir: PathBuf) -> App { //>
         let config_content = include_str!("../config.yaml");
         let config: Config = serde_yaml::from_str(config_content)
             .expect("Failed to parse config.yaml - check file format and location");
@@ -192,9 +197,6 @@ impl App {
             }
         }).collect();
         
-        // Determine data directory
-        let data_dir = PathBuf::from("/home/pi/_playground/_dev/packages/chamon/data");
-        
         // Load baseline versions if they exist
         let baseline_versions = Baseline::list_versions(&data_dir).unwrap_or_default();
         
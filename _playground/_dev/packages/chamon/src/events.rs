@@ -6,20 +6,27 @@ use std::time::Duration;
 
 pub fn handle_events(app: &mut App) -> io::Result<bool> { //>
     if crossterm::event::poll(Duration::from_millis(100))? {
-        match crossterm::event::read()? {
-            Event::Key(key) => {
-                if key.kind == KeyEventKind::Press {
-                    handle_keyboard_input(app, key);
-                }
-            }
-            Event::Mouse(mouse) => {
-                handle_mouse_input(app, mouse);
+        handle_event(crossterm::event::read()?, app);
+    }
+    
+    Ok(app.should_quit)
+} //<
+
+/// Dispatch one terminal event - returns true when the app should quit
+pub fn handle_event(event: Event, app: &mut App) -> bool { //>
+    match event {
+        Event::Key(key) => {
+            if key.kind == KeyEventKind::Press {
+                handle_keyboard_input(app, key);
             }
-            _ => {}
         }
+        Event::Mouse(mouse) => {
+            handle_mouse_input(app, mouse);
+        }
+        _ => {}
     }
     
-    Ok(app.should_quit)
+    app.should_quit
 } //<
 
 fn handle_keyboard_input(app: &mut App, key: KeyEvent) {
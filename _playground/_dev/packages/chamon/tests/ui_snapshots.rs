@@ -0,0 +1,45 @@
+// Headless UI snapshots - scripted keys through events::handle_event, rendered to
+// TestBackend and compared with tests/snapshots/*.txt
+//
+// Approve changes with: UPDATE_SNAPSHOTS=1 cargo test --test ui_snapshots
+
+use chamon_tui::{events, ui, App};
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use tui_common::testing::ScratchDir;
+
+const WIDTH: u16 = 120;
+const HEIGHT: u16 = 32;
+
+tui_common::ui_test_harness!(App, handle: events::handle_event, draw: ui::ui, size: (WIDTH, HEIGHT));
+
+/// App over an empty data dir - no baselines, no system-monitor output
+fn fixture_app(name: &str) -> (App, ScratchDir) {
+    let dir = ScratchDir::new("chamon-ui", name);
+    (App::with_data_dir(dir.path().to_path_buf()), dir)
+}
+
+#[test]
+fn baseline_view() {
+    let (mut app, _dir) = fixture_app("baseline_view");
+    assert_snapshot("baseline_view", &render(&mut app));
+}
+
+#[test]
+fn commands_column_focused() {
+    let (mut app, _dir) = fixture_app("commands_column_focused");
+    keys(&mut app, &[KeyCode::Right]);
+    assert_snapshot("commands_column_focused", &render(&mut app));
+}
+
+#[test]
+fn changes_view() {
+    let (mut app, _dir) = fixture_app("changes_view");
+    keys(&mut app, &[KeyCode::Down]);
+    assert_snapshot("changes_view", &render(&mut app));
+}
+
+#[test]
+fn quit_key_stops_the_loop() {
+    let (mut app, _dir) = fixture_app("quit_key_stops_the_loop");
+    assert!(events::handle_event(Event::Key(KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE)), &mut app));
+}
@@ -1,5 +1,6 @@
 // Application state management
 
+use ratatui::layout::Rect;
 use ratatui::widgets::ListState;
 use crate::manager::DetourManager;
 use crate::injection::InjectionManager;
@@ -7,11 +8,26 @@ use crate::mirror::MirrorManager;
 use crate::popup::Popup;
 use crate::diff::DiffViewer;
 use std::time::{SystemTime, UNIX_EPOCH};
+use std::collections::HashSet;
+use std::path::Path;
+use crate::search::{ListFilter, Searchable};
 
+/// Check results shown as a navigable overlay
 #[derive(Debug, Clone)]
 pub struct ValidationReport {
-    pub content: String,
-    pub has_issues: bool,
+    pub report: crate::checks::CheckReport,
+    pub scope: crate::checks::Scope,
+    pub selected: usize,
+}
+
+impl ValidationReport {
+    pub fn has_issues(&self) -> bool {
+        self.report.findings.iter().any(|f| f.severity != crate::checks::Severity::Info)
+    }
+    
+    pub fn selected_finding(&self) -> Option<&crate::checks::Finding> {
+        self.report.findings.get(self.selected)
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -55,11 +71,21 @@ pub enum FormAction {
 
 #[derive(Debug, Clone)]
 pub struct Detour {
+    pub id: String,
     pub original: String,
     pub custom: String,
     pub active: bool,
     pub size: u64,
     pub modified: String,
+    pub modified_secs: u64,
+    pub description: Option<String>,
+    pub profile: Option<String>,
+    /// Visible only to this unit/command instead of globally
+    pub scope: Option<crate::config::DetourScope>,
+    /// A referenced file is missing
+    pub broken: bool,
+    /// Why `when:` excludes this entry on this host
+    pub skipped: Option<String>,
 }
 
 impl Detour {
@@ -88,24 +114,70 @@ impl Detour {
 
 #[derive(Debug, Clone)]
 pub struct Injection {
+    pub id: String,
     pub target: String,
     pub include_file: String,
     pub active: bool,
     pub size: u64,
     pub modified: String,
+    pub modified_secs: u64,
+    pub description: Option<String>,
+    pub profile: Option<String>,
+    /// Include is rendered with secrets before injecting
+    pub template: bool,
+    /// A referenced file is missing
+    pub broken: bool,
+    /// Why `when:` excludes this entry on this host
+    pub skipped: Option<String>,
 }
 
 #[derive(Debug, Clone)]
 pub struct Mirror {
+    pub id: String,
     pub source: String,
     pub target: String,
     pub active: bool,
     pub size: u64,
     pub modified: String,
+    pub modified_secs: u64,
+    pub description: Option<String>,
+    pub profile: Option<String>,
+    /// A referenced file is missing
+    pub broken: bool,
+    /// Why `when:` excludes this entry on this host
+    pub skipped: Option<String>,
+}
+
+impl Searchable for Detour {
+    fn search_text(&self) -> String { format!("{} ← {}", self.original, self.custom) }
+    fn description(&self) -> Option<&str> { self.description.as_deref() }
+    fn is_active(&self) -> bool { self.active }
+    fn is_broken(&self) -> bool { self.broken }
+    fn modified_secs(&self) -> u64 { self.modified_secs }
+    fn size(&self) -> u64 { self.size }
+}
+
+impl Searchable for Injection {
+    fn search_text(&self) -> String { format!("{} ← {}", self.target, self.include_file) }
+    fn description(&self) -> Option<&str> { self.description.as_deref() }
+    fn is_active(&self) -> bool { self.active }
+    fn is_broken(&self) -> bool { self.broken }
+    fn modified_secs(&self) -> u64 { self.modified_secs }
+    fn size(&self) -> u64 { self.size }
+}
+
+impl Searchable for Mirror {
+    fn search_text(&self) -> String { format!("{} → {}", self.source, self.target) }
+    fn description(&self) -> Option<&str> { self.description.as_deref() }
+    fn is_active(&self) -> bool { self.active }
+    fn is_broken(&self) -> bool { self.broken }
+    fn modified_secs(&self) -> u64 { self.modified_secs }
+    fn size(&self) -> u64 { self.size }
 }
 
 #[derive(Debug, Clone)]
 pub struct Service {
+    pub id: String,
     pub name: String,
     pub action: String,
     pub status: String,
@@ -132,6 +204,20 @@ pub struct Toast {
     pub shown_at: SystemTime,
 }
 
+/// Screen regions from the last draw, for mouse hit-testing
+#[derive(Debug, Clone, Default)]
+pub struct HitAreas {
+    pub views: Rect,
+    pub actions: Rect,
+    pub content: Rect,
+    /// Visible rows as (position in the list, rect)
+    pub view_rows: Vec<(usize, Rect)>,
+    pub action_rows: Vec<(usize, Rect)>,
+    pub content_rows: Vec<(usize, Rect)>,
+    /// Popup targets as (choice, rect) - Yes/No, select options, or a whole message box
+    pub popup: Vec<(usize, Rect)>,
+}
+
 #[derive(Debug, Clone)]
 pub struct AddDetourForm {
     pub original_path: String,
@@ -139,7 +225,7 @@ pub struct AddDetourForm {
     pub description: String,
     pub active_field: usize,
     pub cursor_pos: usize,
-    pub editing_index: Option<usize>,
+    pub editing_id: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -149,7 +235,7 @@ pub struct AddInjectionForm {
     pub description: String,
     pub active_field: usize,
     pub cursor_pos: usize,
-    pub editing_index: Option<usize>,
+    pub editing_id: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -159,18 +245,30 @@ pub struct AddMirrorForm {
     pub description: String,
     pub active_field: usize,
     pub cursor_pos: usize,
-    pub editing_index: Option<usize>,
+    pub editing_id: Option<String>,
 }
 
 #[derive(Debug, Clone)]
 pub enum PendingAction {
-    CreateFileAndSaveDetour,
-    DeleteDetour(usize),
-    DeleteDetourAndFile(usize, String),
-    DeleteInjection(usize),
-    DeleteInjectionAndFile(usize, String),
+    /// Source picker is open for the missing custom file
+    CreateFileAndSaveDetour(Vec<crate::template::FileSource>),
+    DeleteDetour(String),
+    DeleteDetourAndFile(String, String),
+    DeleteInjection(String),
+    DeleteInjectionAndFile(String, String),
     CreateInjectionFileAndSave,
-    DeleteMirror(usize),
+    DeleteMirror(String),
+    /// Bulk menu is open for these entries
+    BulkChoose(crate::config::EntryKind, Vec<String>),
+    /// Profile name input is open for these entries
+    BulkProfile(crate::config::EntryKind, Vec<String>),
+    Bulk(crate::operations::bulk::BulkPlan),
+    /// Revision picker is open for this file (commit hashes, newest first)
+    HistoryPick(String, Vec<String>),
+    /// Restore file to revision
+    RestoreRevision(String, String),
+    /// Persist ids generated for entries that had none
+    WriteMissingIds,
 }
 
 
@@ -182,7 +280,7 @@ impl Default for AddDetourForm {
             description: String::new(),
             active_field: 0,
             cursor_pos: 0,
-            editing_index: None,
+            editing_id: None,
         }
     }
 }
@@ -195,7 +293,7 @@ impl Default for AddInjectionForm {
             description: String::new(),
             active_field: 0,
             cursor_pos: 0,
-            editing_index: None,
+            editing_id: None,
         }
     }
 }
@@ -208,7 +306,7 @@ impl Default for AddMirrorForm {
             description: String::new(),
             active_field: 0,
             cursor_pos: 0,
-            editing_index: None,
+            editing_id: None,
         }
     }
 }
@@ -232,6 +330,15 @@ pub struct App {
     pub mirror_state: ListState,
     pub service_state: ListState,
     
+    // Search/filter/sort per list view
+    pub detour_filter: ListFilter,
+    pub injection_filter: ListFilter,
+    pub mirror_filter: ListFilter,
+    
+    // Multi-select: marked entry ids and the anchor for range marking
+    pub marked: HashSet<String>,
+    pub mark_anchor: Option<usize>,
+    
     pub views: Vec<String>,
     pub detours: Vec<Detour>,
     pub injections: Vec<Injection>,
@@ -242,12 +349,22 @@ pub struct App {
     pub toasts: Vec<Toast>,
     pub popup: Option<Popup>,
     pub diff_viewer: Option<DiffViewer>,
+    pub hit_areas: HitAreas,
+    /// Set by `open_in_editor`; the main loop suspends the TUI and runs it
+    pub pending_editor: Option<crate::editor::PendingEdit>,
     pub validation_report: Option<ValidationReport>,
+    pub config_editor: Option<crate::config_editor::ConfigEditor>,
+    pub status_report: Option<crate::status::StatusReport>,
+    pub selected_tile: usize,
     
     pub detour_manager: DetourManager,
+    /// Facts `when:` predicates are evaluated against
+    pub host: crate::conditions::Host,
     pub injection_manager: InjectionManager,
     pub mirror_manager: MirrorManager,
     pub config_path: String,
+    /// File browser recent paths, kept out of the config (see `filebrowser::recent_paths_file`)
+    pub recent_paths_file: std::path::PathBuf,
     
     pub add_form: AddDetourForm,
     pub injection_form: AddInjectionForm,
@@ -270,14 +387,20 @@ enum ActionSelectionBehavior {
 
 impl App {
     pub fn new() -> Self {
-        let config_path = "/home/pi/.detour.yaml".to_string();
+        Self::with_config_path("/home/pi/.detour.yaml")
+    }
+    
+    /// App over the config at `config_path` (fixtures in the UI snapshot tests)
+    pub fn with_config_path(config_path: &str) -> Self {
+        let config_path = config_path.to_string();
         let detour_manager = DetourManager::new();
         let injection_manager = InjectionManager::new();
         let mirror_manager = MirrorManager::new();
+        let host = crate::conditions::Host::detect();
         
-        let (detours, injections, mirrors, services) = Self::load_initial_config(&config_path, &detour_manager, &mirror_manager);
+        let (detours, injections, mirrors, services) = Self::load_initial_config(&config_path, &detour_manager, &mirror_manager, &host);
         
-        Self {
+        let mut app = Self {
             should_quit: false,
             active_column: ActiveColumn::Views,
             view_mode: ViewMode::DetoursList,
@@ -319,6 +442,11 @@ impl App {
                 state.select(Some(0));
                 state
             },
+            detour_filter: ListFilter::default(),
+            injection_filter: ListFilter::default(),
+            mirror_filter: ListFilter::default(),
+            marked: HashSet::new(),
+            mark_anchor: None,
             
             views: vec![
                 "Detours".to_string(),
@@ -327,6 +455,7 @@ impl App {
                 "Services".to_string(),
                 "Status".to_string(),
                 "Logs".to_string(),
+                "Config".to_string(),
             ],
             detours,
             injections,
@@ -337,12 +466,19 @@ impl App {
             toasts: vec![],
             popup: None,
             diff_viewer: None,
+            hit_areas: HitAreas::default(),
+            pending_editor: None,
             validation_report: None,
+            config_editor: None,
+            status_report: None,
+            selected_tile: 0,
             
             detour_manager,
+            host,
             injection_manager,
             mirror_manager,
             config_path,
+            recent_paths_file: crate::filebrowser::recent_paths_file(),
             
             add_form: AddDetourForm::default(),
             injection_form: AddInjectionForm::default(),
@@ -354,34 +490,92 @@ impl App {
 
             // Default: Always revert to List in column 2
             action_selection_behavior: ActionSelectionBehavior::AlwaysList,
+        };
+        
+        let config = crate::operations::config_ops::load_config(&app.config_path);
+        app.warn_duplicate_ids(&config);
+        app.offer_missing_ids();
+        app
+    }
+    
+    /// Ask before writing generated ids into the user's config file
+    fn offer_missing_ids(&mut self) {
+        if crate::operations::config_ops::has_missing_ids(&self.config_path) {
+            self.pending_action = Some(PendingAction::WriteMissingIds);
+            self.popup = Some(crate::popup::Popup::Confirm {
+                title: "Write Entry Ids".to_string(),
+                message: format!(
+                    "Some entries in {} have no id.\n\nWrite the generated ids to the file?\nComments and formatting will not be kept.",
+                    self.config_path
+                ),
+                selected: 1,
+            });
         }
     }
     
-    fn load_initial_config(config_path: &str, detour_manager: &DetourManager, mirror_manager: &MirrorManager) -> (Vec<Detour>, Vec<Injection>, Vec<Mirror>, Vec<Service>) {
+    pub fn write_missing_ids(&mut self) {
+        match crate::operations::config_ops::write_missing_ids(&self.config_path) {
+            Ok(true) => {
+                self.add_log("INFO", "Wrote generated ids to config");
+                self.add_toast("Entry ids written to config".to_string(), ToastType::Success);
+            }
+            Ok(false) => {}
+            Err(e) => {
+                self.add_log("ERROR", &format!("Failed to write ids: {}", e));
+                self.show_error("Write Ids Failed".to_string(), e);
+            }
+        }
+    }
+    
+    /// Ids must be unique for id-addressed actions to be safe
+    fn warn_duplicate_ids(&mut self, config: &crate::config::DetourConfig) {
+        let duplicates = config.duplicate_ids();
+        if !duplicates.is_empty() {
+            let message = format!("Duplicate ids in config: {}", duplicates.join(", "));
+            self.add_log("ERROR", &message);
+            self.add_toast(message, ToastType::Error);
+        }
+    }
+    
+    fn load_initial_config(config_path: &str, detour_manager: &DetourManager, mirror_manager: &MirrorManager, host: &crate::conditions::Host) -> (Vec<Detour>, Vec<Injection>, Vec<Mirror>, Vec<Service>) {
         use crate::operations::config_ops;
         let config = config_ops::load_config(config_path);
         
         let detours = config.detours.iter().map(|entry| {
             let file_info = detour_manager.get_file_info(&entry.custom);
-            let is_active = detour_manager.is_active(&entry.original);
+            let is_active = detour_manager.is_entry_active(&entry.id, &entry.original, entry.scope.as_ref());
             
             Detour {
+                id: entry.id.clone(),
                 original: entry.original.clone(),
                 custom: entry.custom.clone(),
                 active: is_active,
                 size: file_info.as_ref().map(|f| f.size).unwrap_or(0),
                 modified: Self::time_ago(file_info.as_ref().map(|f| f.modified_secs).unwrap_or(0)),
+                modified_secs: file_info.as_ref().map(|f| f.modified_secs).unwrap_or(0),
+                description: entry.description.clone(),
+                profile: entry.profile.clone(),
+                scope: entry.scope.clone(),
+                broken: !Path::new(&entry.custom).exists() || !Path::new(&entry.original).exists(),
+                skipped: crate::conditions::skip_reason(entry.when.as_ref(), host),
             }
         }).collect();
         
         let injections = config.injections.iter().map(|entry| {
             let file_info = detour_manager.get_file_info(&entry.include_file);
             Injection {
+                id: entry.id.clone(),
                 target: entry.target.clone(),
                 include_file: entry.include_file.clone(),
                 active: entry.enabled,
                 size: file_info.as_ref().map(|f| f.size).unwrap_or(0),
                 modified: Self::time_ago(file_info.as_ref().map(|f| f.modified_secs).unwrap_or(0)),
+                modified_secs: file_info.as_ref().map(|f| f.modified_secs).unwrap_or(0),
+                description: entry.description.clone(),
+                profile: entry.profile.clone(),
+                template: entry.template,
+                broken: !Path::new(&entry.target).exists() || !Path::new(&entry.include_file).exists(),
+                skipped: crate::conditions::skip_reason(entry.when.as_ref(), host),
             }
         }).collect();
         
@@ -390,15 +584,22 @@ impl App {
             let is_active = mirror_manager.is_active(&entry.source, &entry.target);
             
             Mirror {
+                id: entry.id.clone(),
                 source: entry.source.clone(),
                 target: entry.target.clone(),
                 active: is_active,
                 size: file_info.as_ref().map(|f| f.size).unwrap_or(0),
                 modified: Self::time_ago(file_info.as_ref().map(|f| f.modified_secs).unwrap_or(0)),
+                modified_secs: file_info.as_ref().map(|f| f.modified_secs).unwrap_or(0),
+                description: entry.description.clone(),
+                profile: entry.profile.clone(),
+                broken: !Path::new(&entry.source).exists(),
+                skipped: crate::conditions::skip_reason(entry.when.as_ref(), host),
             }
         }).collect();
         
         let services = config.services.iter().map(|entry| Service {
+            id: entry.id.clone(),
             name: entry.name.clone(),
             action: entry.action.clone(),
             status: "Unknown".to_string(),
@@ -429,14 +630,21 @@ impl App {
             // Reload detours
             self.detours = config.detours.iter().map(|entry| {
                 let file_info = self.detour_manager.get_file_info(&entry.custom);
-                let is_active = self.detour_manager.is_active(&entry.original);
+                let is_active = self.detour_manager.is_entry_active(&entry.id, &entry.original, entry.scope.as_ref());
                 
                 Detour {
+                    id: entry.id.clone(),
                     original: entry.original.clone(),
                     custom: entry.custom.clone(),
                     active: is_active,
                     size: file_info.as_ref().map(|f| f.size).unwrap_or(0),
                     modified: Self::time_ago(file_info.as_ref().map(|f| f.modified_secs).unwrap_or(0)),
+                    modified_secs: file_info.as_ref().map(|f| f.modified_secs).unwrap_or(0),
+                    description: entry.description.clone(),
+                    profile: entry.profile.clone(),
+                    scope: entry.scope.clone(),
+                    broken: !Path::new(&entry.custom).exists() || !Path::new(&entry.original).exists(),
+                    skipped: crate::conditions::skip_reason(entry.when.as_ref(), &self.host),
                 }
             }).collect();
             
@@ -444,11 +652,18 @@ impl App {
         self.injections = config.injections.iter().map(|entry| {
             let file_info = self.detour_manager.get_file_info(&entry.include_file);
             Injection {
+                id: entry.id.clone(),
                 target: entry.target.clone(),
                 include_file: entry.include_file.clone(),
                 active: entry.enabled,
                 size: file_info.as_ref().map(|f| f.size).unwrap_or(0),
                 modified: Self::time_ago(file_info.as_ref().map(|f| f.modified_secs).unwrap_or(0)),
+                modified_secs: file_info.as_ref().map(|f| f.modified_secs).unwrap_or(0),
+                description: entry.description.clone(),
+                profile: entry.profile.clone(),
+                template: entry.template,
+                broken: !Path::new(&entry.target).exists() || !Path::new(&entry.include_file).exists(),
+                skipped: crate::conditions::skip_reason(entry.when.as_ref(), &self.host),
             }
             }).collect();
             
@@ -458,16 +673,23 @@ impl App {
                 let is_active = self.mirror_manager.is_active(&entry.source, &entry.target);
                 
                 Mirror {
+                    id: entry.id.clone(),
                     source: entry.source.clone(),
                     target: entry.target.clone(),
                     active: is_active,
                 size: file_info.as_ref().map(|f| f.size).unwrap_or(0),
                 modified: Self::time_ago(file_info.as_ref().map(|f| f.modified_secs).unwrap_or(0)),
+                modified_secs: file_info.as_ref().map(|f| f.modified_secs).unwrap_or(0),
+                description: entry.description.clone(),
+                profile: entry.profile.clone(),
+                broken: !Path::new(&entry.source).exists(),
+                skipped: crate::conditions::skip_reason(entry.when.as_ref(), &self.host),
             }
             }).collect();
             
             // Reload services
             self.services = config.services.iter().map(|entry| Service {
+                id: entry.id.clone(),
                 name: entry.name.clone(),
                 action: entry.action.clone(),
                 status: "Unknown".to_string(),
@@ -476,12 +698,13 @@ impl App {
             // Validate and sync all selections
             self.validate_all_selections();
             
-            // Sync mirror list state
-            if !self.mirrors.is_empty() {
-                self.mirror_state.select(Some(self.selected_mirror));
-        } else {
-                self.mirror_state.select(None);
-            }
+            // Status is recollected on next view
+            self.status_report = None;
+            
+            // Drop marks for entries that no longer exist
+            self.marked.retain(|id| config.find(id).is_some());
+            
+            self.warn_duplicate_ids(&config);
             
             self.add_toast("Config reloaded".to_string(), ToastType::Success);
     }
@@ -489,15 +712,19 @@ impl App {
     
     pub fn activate_all_detours(&mut self) {
         // Activate all inactive detours individually
+        let config = crate::operations::config_ops::load_config(&self.config_path);
         let mut activated_count = 0;
+        let mut messages = Vec::new();
         let mut errors = Vec::new();
         
         for (idx, detour) in self.detours.iter_mut().enumerate() {
-            if !detour.active {
-                match self.detour_manager.apply_detour(&detour.original, &detour.custom) {
-                    Ok(_) => {
+            // `when:` excludes this host
+            if !detour.active && detour.skipped.is_none() {
+                match crate::operations::activation::activate(&config, &detour.id) {
+                    Ok(msg) => {
                         detour.active = true;
                         activated_count += 1;
+                        messages.push(msg);
                         
                         // Update config to reflect enabled state
                         use crate::operations::config_ops;
@@ -515,6 +742,11 @@ impl App {
             }
         }
         
+        self.log_lines("INFO", &messages);
+        for line in Self::skipped_lines(self.detours.iter().map(|d| (&d.original, &d.skipped)).collect()) {
+            self.add_log("INFO", &line);
+        }
+        
         if activated_count > 0 {
             self.add_log("INFO", &format!("Activated {} detour(s)", activated_count));
             self.add_toast(format!("Activated {} detour(s)", activated_count), ToastType::Success);
@@ -531,16 +763,17 @@ impl App {
 
     pub fn deactivate_all_detours(&mut self) {
         // Deactivate all active detours
+        let config = crate::operations::config_ops::load_config(&self.config_path);
         let mut deactivated_count = 0;
+        let mut messages = Vec::new();
         let mut errors = Vec::new();
         for (idx, detour) in self.detours.iter_mut().enumerate() {
             if detour.active {
-                use crate::manager::DetourManager;
-                let m = DetourManager::new();
-                match m.remove_detour(&detour.original) {
-                    Ok(_) => {
+                match crate::operations::activation::deactivate(&config, &detour.id) {
+                    Ok(msg) => {
                         detour.active = false;
                         deactivated_count += 1;
+                        messages.push(msg);
                         // Update config
                         use crate::operations::config_ops;
                         let _ = config_ops::with_config_mut(&self.config_path, |config| {
@@ -554,84 +787,107 @@ impl App {
                 }
             }
         }
+        self.log_lines("INFO", &messages);
+        self.log_lines("ERROR", &errors);
         if deactivated_count > 0 { self.add_toast(format!("Deactivated {} detours", deactivated_count), ToastType::Success); }
         if !errors.is_empty() { self.add_toast(format!("{} errors during deactivation", errors.len()), ToastType::Error); }
     }
 
     pub fn validate_injections_all(&mut self) {
-        for i in 0..self.injections.len() {
-            self.validate_single_injection(i);
-        }
-        self.add_toast("Validated injections".to_string(), ToastType::Info);
+        self.run_checks(crate::checks::Scope::kind(crate::status::EntryKind::Injection));
     }
 
     pub fn activate_all_injections(&mut self) {
-        use crate::injection::InjectionManager;
-        let manager = InjectionManager::new();
-        let mut count = 0;
+        let config = crate::operations::config_ops::load_config(&self.config_path);
+        let mut messages = Vec::new();
+        let mut errors = Vec::new();
         for inj in self.injections.iter_mut() {
-            if !inj.active {
-                if manager.apply(std::path::Path::new(&inj.target), std::path::Path::new(&inj.include_file)).is_ok() {
-                    inj.active = true;
-                    count += 1;
+            if !inj.active && inj.skipped.is_none() {
+                match crate::operations::activation::activate(&config, &inj.id) {
+                    Ok(msg) => {
+                        inj.active = true;
+                        messages.push(msg);
+                    }
+                    Err(e) => errors.push(format!("{}: {}", inj.target, e)),
                 }
             }
         }
+        let count = messages.len();
+        self.log_lines("INFO", &messages);
+        self.log_lines("ERROR", &errors);
+        for line in Self::skipped_lines(self.injections.iter().map(|i| (&i.target, &i.skipped)).collect()) {
+            self.add_log("INFO", &line);
+        }
         if count > 0 { self.add_toast(format!("Activated {} injections", count), ToastType::Success); }
     }
 
     pub fn deactivate_all_injections(&mut self) {
-        use crate::injection::InjectionManager;
-        let manager = InjectionManager::new();
-        let mut count = 0;
+        let config = crate::operations::config_ops::load_config(&self.config_path);
+        let mut messages = Vec::new();
+        let mut errors = Vec::new();
         for inj in self.injections.iter_mut() {
             if inj.active {
-                if manager.remove(std::path::Path::new(&inj.target), std::path::Path::new(&inj.include_file)).is_ok() {
-                    inj.active = false;
-                    count += 1;
+                match crate::operations::activation::deactivate(&config, &inj.id) {
+                    Ok(msg) => {
+                        inj.active = false;
+                        messages.push(msg);
+                    }
+                    Err(e) => errors.push(format!("{}: {}", inj.target, e)),
                 }
             }
         }
+        let count = messages.len();
+        self.log_lines("INFO", &messages);
+        self.log_lines("ERROR", &errors);
         if count > 0 { self.add_toast(format!("Deactivated {} injections", count), ToastType::Success); }
     }
 
     pub fn validate_mirrors_all(&mut self) {
-        // Basic validation: presence of source/target strings
-        let mut missing = 0;
-        for m in &self.mirrors {
-            if m.source.trim().is_empty() || m.target.trim().is_empty() { missing += 1; }
-        }
-        if missing == 0 { self.add_toast("Mirrors OK".to_string(), ToastType::Info); }
-        else { self.add_toast(format!("{} mirrors incomplete", missing), ToastType::Info); }
+        self.run_checks(crate::checks::Scope::kind(crate::status::EntryKind::Mirror));
     }
 
     pub fn activate_all_mirrors(&mut self) {
-        use crate::mirror::MirrorManager;
-        let m = MirrorManager::new();
-        let mut count = 0;
+        let config = crate::operations::config_ops::load_config(&self.config_path);
+        let mut messages = Vec::new();
+        let mut errors = Vec::new();
         for mir in self.mirrors.iter_mut() {
-            if !mir.active {
-                if m.apply_mirror(&mir.source, &mir.target).is_ok() {
-                    mir.active = true;
-                    count += 1;
+            if !mir.active && mir.skipped.is_none() {
+                match crate::operations::activation::activate(&config, &mir.id) {
+                    Ok(msg) => {
+                        mir.active = true;
+                        messages.push(msg);
+                    }
+                    Err(e) => errors.push(format!("{}: {}", mir.target, e)),
                 }
             }
         }
+        let count = messages.len();
+        self.log_lines("INFO", &messages);
+        self.log_lines("ERROR", &errors);
+        for line in Self::skipped_lines(self.mirrors.iter().map(|m| (&m.target, &m.skipped)).collect()) {
+            self.add_log("INFO", &line);
+        }
         if count > 0 { self.add_toast(format!("Activated {} mirrors", count), ToastType::Success); }
     }
 
     pub fn deactivate_all_mirrors(&mut self) {
-        use crate::mirror::MirrorManager;
-        let m = MirrorManager::new();
-        let mut count = 0;
+        let config = crate::operations::config_ops::load_config(&self.config_path);
+        let mut messages = Vec::new();
+        let mut errors = Vec::new();
         for mir in self.mirrors.iter_mut() {
             if mir.active {
-                if m.remove_mirror(&mir.target).is_ok() {
-                    mir.active = false;
-                    count += 1;
+                match crate::operations::activation::deactivate(&config, &mir.id) {
+                    Ok(msg) => {
+                        mir.active = false;
+                        messages.push(msg);
+                    }
+                    Err(e) => errors.push(format!("{}: {}", mir.target, e)),
                 }
             }
         }
+        let count = messages.len();
+        self.log_lines("INFO", &messages);
+        self.log_lines("ERROR", &errors);
         if count > 0 { self.add_toast(format!("Deactivated {} mirrors", count), ToastType::Success); }
     }
     
@@ -655,6 +911,13 @@ impl App {
         }
     }
     
+    /// Log multi-line messages (e.g. with hook output) one line per entry
+    pub fn log_lines(&mut self, level: &str, messages: &[String]) {
+        for line in messages.iter().flat_map(|m| m.lines()) {
+            self.add_log(level, line);
+        }
+    }
+    
     pub fn add_toast(&mut self, message: String, toast_type: ToastType) {
         self.toasts.push(Toast {
             message,
@@ -687,6 +950,244 @@ impl App {
         self.diff_viewer = None;
     }
     
+    /// List history revisions of the selected entry's managed file
+    pub fn open_history(&mut self) {
+        let config = crate::operations::config_ops::load_config(&self.config_path);
+        let Some(history) = crate::history::History::open(&config) else {
+            self.show_error("History Disabled".to_string(),
+                "Set `history: {enabled: true}` and `templates.managed_dir` in the config to version custom files.".to_string());
+            return;
+        };
+        let Some((id, _)) = self.selected_entry_state() else { return };
+        let Some((_, path)) = crate::history::entry_path(&config, &id) else { return };
+        if !history.tracks(&path) || std::path::Path::new(&path).is_dir() {
+            self.show_error("No History".to_string(),
+                format!("{} is not a file inside {}", path, history.root().display()));
+            return;
+        }
+        
+        match history.log(&path) {
+            Ok(revisions) if revisions.is_empty() => {
+                self.popup = Some(Popup::info("No History", format!("{} has no commits yet", path)));
+            }
+            Ok(revisions) => {
+                let options = revisions.iter().map(|r| r.label()).collect();
+                let hashes = revisions.into_iter().map(|r| r.hash).collect();
+                self.popup = Some(Popup::select(format!("History: {}", path), options));
+                self.pending_action = Some(PendingAction::HistoryPick(path, hashes));
+            }
+            Err(e) => self.show_error("History Error".to_string(), e),
+        }
+    }
+    
+    /// Diff a template injection's include (left) against its rendered output with secrets redacted
+    pub fn show_rendered_diff(&mut self) {
+        let Some(injection) = self.injections.get(self.selected_injection) else { return };
+        let id = injection.id.clone();
+        let config = crate::operations::config_ops::load_config(&self.config_path);
+        let Some(entry) = config.injections.iter().find(|e| e.id == id) else { return };
+        let output = entry.injected_file();
+        
+        // Mask by template structure - nothing is resolved or run just to show a diff
+        let result = std::fs::read_to_string(&entry.include_file)
+            .map_err(|e| format!("Failed to read template {}: {}", entry.include_file, e))
+            .and_then(|template| {
+                let mut viewer = DiffViewer::new(entry.include_file.clone(), output.clone())?;
+                let rendered = std::fs::read_to_string(&output)
+                    .map_err(|e| format!("Failed to read {}: {}", output, e))?;
+                let redacted = crate::secrets::redact_rendered(&template, &rendered);
+                viewer.right_content = redacted.lines().map(String::from).collect();
+                viewer.right_path = format!("{} (secrets redacted)", output);
+                Ok(viewer)
+            });
+        match result {
+            Ok(viewer) => self.diff_viewer = Some(viewer),
+            Err(e) => self.show_error("Diff Error".to_string(), e),
+        }
+    }
+    
+    /// Queue the selected entry's custom/include/source file for $EDITOR
+    pub fn open_in_editor(&mut self) {
+        let config = crate::operations::config_ops::load_config(&self.config_path);
+        let Some((id, _)) = self.selected_entry_state() else { return };
+        let Some((_, path)) = crate::history::entry_path(&config, &id) else { return };
+        if std::path::Path::new(&path).is_dir() {
+            self.show_error("Edit Error".to_string(), format!("{} is a directory", path));
+            return;
+        }
+        self.pending_editor = Some(crate::editor::PendingEdit::new(id, path));
+    }
+    
+    /// Back from the editor: refresh sizes and mtimes, then re-check the entry if it's applied
+    pub fn finish_edit(&mut self, edit: crate::editor::PendingEdit, result: Result<(), String>) {
+        use crate::config::EntryKind;
+        use crate::operations::{activation, config_ops};
+        
+        self.reload_config();
+        if let Err(e) = result {
+            self.show_error("Edit Error".to_string(), e);
+            return;
+        }
+        
+        let config = config_ops::load_config(&self.config_path);
+        if let Err(e) = crate::history::record_entry(&config, &edit.id, "Edit") {
+            self.add_log("WARN", &format!("History commit failed: {}", e));
+        }
+        if !activation::is_active(&config, &edit.id) {
+            self.add_log("INFO", &format!("Edited {}", edit.path));
+            return;
+        }
+        
+        // A bind mount pins the inode, so a file saved by rename needs re-binding
+        let mut message = format!("Edited {}", edit.path);
+        if edit.replaced() {
+            if let Some((EntryKind::Detour, i)) = config.find(&edit.id) {
+                let entry = &config.detours[i];
+                let manager = DetourManager::new();
+                match manager.remove_entry(&entry.id, &entry.original, entry.scope.as_ref())
+                    .and_then(|_| manager.apply_entry(entry))
+                {
+                    Ok(_) => message.push_str(" (editor replaced the file, re-bound)"),
+                    Err(e) => {
+                        self.show_error("Edit Error".to_string(), format!("Re-binding {} failed: {}", entry.original, e));
+                        return;
+                    }
+                }
+            }
+        }
+
+        // The target includes the rendered copy, not the template that was edited
+        if let Some((EntryKind::Injection, i)) = config.find(&edit.id) {
+            let entry = &config.injections[i];
+            if entry.template && edit.path == entry.include_file {
+                match activation::render_injection(&config, entry) {
+                    Ok(_) => message.push_str(&format!(" (re-rendered {})", entry.injected_file())),
+                    Err(e) => {
+                        self.show_error("Edit Error".to_string(), format!("Rendering {} failed: {}", entry.include_file, e));
+                        return;
+                    }
+                }
+            }
+        }
+
+        match crate::hooks::changed(activation::entry_hooks(&config, &edit.id), message) {
+            Ok(msg) => {
+                let lines: Vec<String> = msg.lines().map(String::from).collect();
+                self.log_lines("SUCCESS", &lines);
+            }
+            Err(e) => {
+                let lines: Vec<String> = e.lines().map(String::from).collect();
+                self.log_lines("ERROR", &lines);
+                self.show_error("Hook Failed".to_string(), e);
+                return;
+            }
+        }
+        
+        let kind = match config.find(&edit.id) {
+            Some((kind, _)) => kind,
+            None => return,
+        };
+        let report = crate::checks::CheckReport::run(&config, &crate::checks::Scope::entry(kind, &edit.id));
+        if report.findings.is_empty() {
+            self.add_toast(format!("Saved {}", edit.path), ToastType::Success);
+        } else {
+            self.run_checks(crate::checks::Scope::entry(kind, &edit.id));
+        }
+        self.reload_config();
+    }
+    
+    /// Diff a past revision (left) against the current file (right)
+    pub fn show_revision_diff(&mut self, path: &str, rev: &str) {
+        let config = crate::operations::config_ops::load_config(&self.config_path);
+        let Some(history) = crate::history::History::open(&config) else { return };
+        let short = &rev[..rev.len().min(7)];
+        let name = std::path::Path::new(path).file_name().unwrap_or_default().to_string_lossy().to_string();
+        
+        let result = history.show(path, rev)
+            .and_then(|contents| crate::operations::file_ops::write_private_temp(&format!("{}-{}", short, name), &contents))
+            .and_then(|snapshot| {
+                let viewer = DiffViewer::new(snapshot.to_string_lossy().to_string(), path.to_string());
+                let _ = std::fs::remove_file(&snapshot);
+                viewer
+            });
+        match result {
+            Ok(mut viewer) => {
+                viewer.left_path = format!("{} @ {}", path, short);
+                viewer.revision = Some(rev.to_string());
+                self.diff_viewer = Some(viewer);
+            }
+            Err(e) => self.show_error("History Error".to_string(), e),
+        }
+    }
+    
+    /// Diff the packaged version of the selected detour's original (left) against its custom file
+    pub fn show_pristine_diff(&mut self) {
+        let Some(detour) = self.detours.get(self.selected_detour) else { return };
+        let (id, original, custom, active) = (detour.id.clone(), detour.original.clone(), detour.custom.clone(), detour.active);
+        let config = crate::operations::config_ops::load_config(&self.config_path);
+        let local_deb = config.detours.iter()
+            .find(|d| d.id == id)
+            .and_then(|d| d.deb.clone());
+        
+        let pristine = match crate::package::pristine(&original, local_deb.as_deref()) {
+            Ok(pristine) => pristine,
+            Err(e) => {
+                self.show_error("Pristine Error".to_string(), e);
+                return;
+            }
+        };
+        let name = std::path::Path::new(&original).file_name().unwrap_or_default().to_string_lossy().to_string();
+        
+        let result = crate::operations::file_ops::write_private_temp(&format!("pristine-{}", name), &pristine.contents)
+            .and_then(|snapshot| {
+                let viewer = DiffViewer::new(snapshot.to_string_lossy().to_string(), custom.clone());
+                let _ = std::fs::remove_file(&snapshot);
+                viewer
+            });
+        match result {
+            Ok(mut viewer) => {
+                viewer.left_path = format!("{} @ {} (pristine)", original, pristine.package);
+                // An active detour hides the file on disk, so only inactive ones can be checked
+                if !active {
+                    if let Some(conffile) = crate::package::modified_conffile(&original) {
+                        viewer.left_path.push_str(&format!(" ⚠ on-disk conffile differs from {}'s md5", conffile.package));
+                    }
+                }
+                self.diff_viewer = Some(viewer);
+            }
+            Err(e) => self.show_error("Pristine Error".to_string(), e),
+        }
+    }
+    
+    /// Ask before restoring the revision shown in the diff viewer
+    pub fn confirm_restore_revision(&mut self) {
+        let Some(diff) = &self.diff_viewer else { return };
+        let Some(rev) = diff.revision.clone() else { return };
+        let path = diff.right_path.clone();
+        self.popup = Some(Popup::confirm("Restore Revision",
+            format!("Overwrite {} with revision {}?\nThe current contents stay in history.", path, &rev[..rev.len().min(7)])));
+        self.pending_action = Some(PendingAction::RestoreRevision(path, rev));
+    }
+    
+    pub fn restore_revision(&mut self, path: &str, rev: &str) {
+        let config = crate::operations::config_ops::load_config(&self.config_path);
+        let Some(history) = crate::history::History::open(&config) else { return };
+        // Keep uncommitted edits reachable before overwriting them
+        if let Err(e) = history.record(path, &format!("Save {} before restore", path)) {
+            self.show_error("History Error".to_string(), e);
+            return;
+        }
+        match history.restore(path, rev) {
+            Ok(()) => {
+                self.close_diff();
+                self.add_log("SUCCESS", &format!("Restored {} to {}", path, &rev[..rev.len().min(7)]));
+                self.add_toast("Revision restored".to_string(), ToastType::Success);
+                self.reload_config();
+            }
+            Err(e) => self.show_error("Restore Error".to_string(), e),
+        }
+    }
+    
     pub fn scroll_diff_up(&mut self) {
         if let Some(ref mut diff) = self.diff_viewer {
             if diff.scroll_offset > 0 {
@@ -713,6 +1214,169 @@ impl App {
         }
     }
     
+    /// Load ~/.detour.yaml into the embedded editor (keeps an existing unsaved buffer)
+    pub fn open_config_editor(&mut self) {
+        let keep_buffer = self.config_editor.as_ref()
+            .map(|e| e.dirty && e.path == self.config_path)
+            .unwrap_or(false);
+        if !keep_buffer {
+            self.config_editor = Some(crate::config_editor::ConfigEditor::open(&self.config_path));
+        }
+    }
+
+    /// Save the editor buffer - blocked while the YAML doesn't parse
+    pub fn save_config_editor(&mut self) {
+        let result = match self.config_editor.as_mut() {
+            Some(editor) => editor.save(),
+            None => return,
+        };
+
+        match result {
+            Ok(_) => {
+                self.add_log("SUCCESS", &format!("Saved config: {}", self.config_path));
+                self.reload_config();
+            }
+            Err(e) => {
+                if let Some(editor) = self.config_editor.as_mut() {
+                    editor.goto_first_diagnostic();
+                }
+                self.show_error("Save Blocked".to_string(), e);
+            }
+        }
+    }
+
+    /// Re-collect the status overview from the current config
+    pub fn refresh_status(&mut self) {
+        self.status_report = Some(crate::status::StatusReport::collect(&self.config_path));
+    }
+    
+    /// Jump from the selected status tile to the first entry needing attention
+    pub fn open_status_tile(&mut self) {
+        use crate::status::{EntryKind, StatusTile};
+        
+        let report = match &self.status_report {
+            Some(report) => report.clone(),
+            None => return,
+        };
+        let tile = StatusTile::ALL[self.selected_tile.min(StatusTile::ALL.len() - 1)];
+        
+        let first_of = |kind: EntryKind| {
+            let category = match kind {
+                EntryKind::Detour => &report.detours,
+                EntryKind::Injection => &report.injections,
+                _ => &report.mirrors,
+            };
+            category.failing.first()
+                .or_else(|| report.drift.iter().find(|e| e.kind == kind))
+                .map(|e| e.id.clone())
+        };
+        
+        let target = match tile {
+            StatusTile::Config => {
+                if report.config.parsed {
+                    self.add_toast("Config OK".to_string(), ToastType::Info);
+                } else {
+                    self.selected_view = 6;
+                    self.view_state.select(Some(6));
+                    self.view_mode = ViewMode::ConfigEdit;
+                    self.selected_action = 0;
+                    self.action_state.select(Some(0));
+                    self.open_config_editor();
+                    if let Some(editor) = self.config_editor.as_mut() {
+                        editor.goto_first_diagnostic();
+                    }
+                    self.active_column = ActiveColumn::Content;
+                }
+                return;
+            }
+            StatusTile::Privilege => {
+                self.popup = Some(Popup::info("Privilege", report.privilege.detail.clone()));
+                return;
+            }
+            StatusTile::Detours => first_of(EntryKind::Detour).map(|i| (EntryKind::Detour, i)),
+            StatusTile::Injections => first_of(EntryKind::Injection).map(|i| (EntryKind::Injection, i)),
+            StatusTile::Mirrors => first_of(EntryKind::Mirror).map(|i| (EntryKind::Mirror, i)),
+            StatusTile::Services => report.services.iter()
+                .find(|s| s.state != "active")
+                .map(|s| (EntryKind::Service, s.id.clone())),
+            StatusTile::Drift => report.drift.first().map(|e| (e.kind, e.id.clone())),
+        };
+        
+        match target {
+            Some((kind, id)) => self.focus_entry(kind, &id),
+            None => self.add_toast(format!("{}: nothing to fix", tile.title()), ToastType::Info),
+        }
+    }
+    
+    /// Switch to the list view for `kind` and select the entry with `id`
+    pub fn focus_entry(&mut self, kind: crate::status::EntryKind, id: &str) {
+        use crate::status::EntryKind;
+        
+        let view = match kind {
+            EntryKind::Detour => 0,
+            EntryKind::Injection => 1,
+            EntryKind::Mirror => 2,
+            EntryKind::Service => 3,
+        };
+        self.selected_view = view;
+        self.view_state.select(Some(view));
+        self.view_mode = Self::view_mode_from_index(view);
+        self.selected_action = 0;
+        self.action_state.select(Some(0));
+        self.active_column = ActiveColumn::Content;
+        
+        let position = match kind {
+            EntryKind::Detour => self.detours.iter().position(|e| e.id == id),
+            EntryKind::Injection => self.injections.iter().position(|e| e.id == id),
+            EntryKind::Mirror => self.mirrors.iter().position(|e| e.id == id),
+            EntryKind::Service => self.services.iter().position(|e| e.id == id),
+        };
+        let index = match position {
+            Some(index) => index,
+            None => {
+                self.add_toast(format!("{:?} {} not found", kind, id), ToastType::Error);
+                return;
+            }
+        };
+        
+        match kind {
+            EntryKind::Detour => {
+                // Drop a filter that would hide the target
+                if !self.visible_detours().contains(&index) {
+                    self.detour_filter.clear();
+                }
+                self.selected_detour = index;
+                self.sync_detour_selection();
+            }
+            EntryKind::Injection => {
+                // Drop a filter that would hide the target
+                if !self.visible_injections().contains(&index) {
+                    self.injection_filter.clear();
+                }
+                self.selected_injection = index;
+                self.sync_injection_selection();
+            }
+            EntryKind::Mirror => {
+                // Drop a filter that would hide the target
+                if !self.visible_mirrors().contains(&index) {
+                    self.mirror_filter.clear();
+                }
+                self.selected_mirror = index;
+                self.sync_mirror_selection();
+            }
+            EntryKind::Service => {
+                self.selected_service = index;
+                self.sync_service_selection();
+            }
+        }
+    }
+    
+    /// Discard unsaved edits and re-read the config from disk
+    pub fn revert_config_editor(&mut self) {
+        self.config_editor = Some(crate::config_editor::ConfigEditor::open(&self.config_path));
+        self.add_toast("Reverted to saved config".to_string(), ToastType::Info);
+    }
+
     pub fn handle_popup_left(&mut self) {
         if let Some(Popup::Confirm { selected, .. }) = &mut self.popup {
             if *selected > 0 {
@@ -721,6 +1385,18 @@ impl App {
         }
     }
     
+    pub fn handle_popup_up(&mut self) {
+        if let Some(popup) = &mut self.popup {
+            popup.handle_up();
+        }
+    }
+    
+    pub fn handle_popup_down(&mut self) {
+        if let Some(popup) = &mut self.popup {
+            popup.handle_down();
+        }
+    }
+    
     pub fn handle_popup_right(&mut self) {
         if let Some(Popup::Confirm { selected, .. }) = &mut self.popup {
             if *selected < 1 {
@@ -749,27 +1425,12 @@ impl App {
         match self.active_column {
             ActiveColumn::Views => {
                 if self.selected_view > 0 {
-                    self.selected_view -= 1;
-                    self.view_state.select(Some(self.selected_view));
-                    // Sync view_mode when selection changes in Views column
-                    self.sync_view_mode();
-                    // Update Actions preview selection according to settings
-                    let actions = self.get_current_actions();
-                    let preview_idx = match self.action_selection_behavior {
-                        ActionSelectionBehavior::PerViewPersist => *self.action_selection_by_view.get(&self.selected_view).unwrap_or(&0),
-                        ActionSelectionBehavior::AlwaysList => 0,
-                    };
-                    let clamped = preview_idx.min(actions.len().saturating_sub(1));
-                    self.selected_action = clamped;
-                    self.action_state.select(Some(clamped));
+                    self.set_selected_view(self.selected_view - 1);
                 }
             }
             ActiveColumn::Actions => {
                 if self.selected_action > 0 {
-                    self.selected_action -= 1;
-                    self.action_state.select(Some(self.selected_action));
-                    // persist per-view selection
-                    self.action_selection_by_view.insert(self.selected_view, self.selected_action);
+                    self.set_selected_action(self.selected_action - 1);
                 }
             }
             ActiveColumn::Content => {
@@ -782,28 +1443,13 @@ impl App {
         match self.active_column {
             ActiveColumn::Views => {
                 if self.selected_view < self.views.len().saturating_sub(1) {
-                    self.selected_view += 1;
-                    self.view_state.select(Some(self.selected_view));
-                    // Sync view_mode when selection changes in Views column
-                    self.sync_view_mode();
-                    // Update Actions preview selection according to settings
-                    let actions = self.get_current_actions();
-                    let preview_idx = match self.action_selection_behavior {
-                        ActionSelectionBehavior::PerViewPersist => *self.action_selection_by_view.get(&self.selected_view).unwrap_or(&0),
-                        ActionSelectionBehavior::AlwaysList => 0,
-                    };
-                    let clamped = preview_idx.min(actions.len().saturating_sub(1));
-                    self.selected_action = clamped;
-                    self.action_state.select(Some(clamped));
+                    self.set_selected_view(self.selected_view + 1);
                 }
             }
             ActiveColumn::Actions => {
                 let actions = self.get_current_actions();
                 if self.selected_action < actions.len().saturating_sub(1) {
-                    self.selected_action += 1;
-                    self.action_state.select(Some(self.selected_action));
-                    // persist per-view selection
-                    self.action_selection_by_view.insert(self.selected_view, self.selected_action);
+                    self.set_selected_action(self.selected_action + 1);
                 }
             }
             ActiveColumn::Content => {
@@ -812,6 +1458,45 @@ impl App {
         }
     }
     
+    /// Select a view in Column 1 and update the Actions preview to match
+    pub fn set_selected_view(&mut self, index: usize) {
+        self.selected_view = index;
+        self.view_state.select(Some(self.selected_view));
+        // Sync view_mode when selection changes in Views column
+        self.sync_view_mode();
+        // Update Actions preview selection according to settings
+        let actions = self.get_current_actions();
+        let preview_idx = match self.action_selection_behavior {
+            ActionSelectionBehavior::PerViewPersist => *self.action_selection_by_view.get(&self.selected_view).unwrap_or(&0),
+            ActionSelectionBehavior::AlwaysList => 0,
+        };
+        let clamped = preview_idx.min(actions.len().saturating_sub(1));
+        self.selected_action = clamped;
+        self.action_state.select(Some(clamped));
+    }
+    
+    pub fn set_selected_action(&mut self, index: usize) {
+        self.selected_action = index;
+        self.action_state.select(Some(self.selected_action));
+        // persist per-view selection
+        self.action_selection_by_view.insert(self.selected_view, self.selected_action);
+    }
+    
+    /// Move focus to `column` through the intermediate columns, so each step's syncing runs
+    pub fn focus_column(&mut self, column: ActiveColumn) {
+        let rank = |c: ActiveColumn| match c {
+            ActiveColumn::Views => 0,
+            ActiveColumn::Actions => 1,
+            ActiveColumn::Content => 2,
+        };
+        while rank(self.active_column) < rank(column) {
+            self.navigate_next_column();
+        }
+        while rank(self.active_column) > rank(column) {
+            self.navigate_prev_column();
+        }
+    }
+    
     pub fn navigate_prev_column(&mut self) {
         match self.active_column {
             ActiveColumn::Views => {}
@@ -843,6 +1528,9 @@ impl App {
             ActiveColumn::Actions => {
                 self.active_column = ActiveColumn::Content;
                 // view_mode already synced when we entered Actions
+                if self.view_mode == ViewMode::ConfigEdit {
+                    self.open_config_editor();
+                }
             }
             ActiveColumn::Content => {}
         }
@@ -857,7 +1545,9 @@ impl App {
                 self.handle_action_select();
             }
             ActiveColumn::Content => {
-                // Content-specific enter handling
+                if self.view_mode == ViewMode::StatusOverview {
+                    self.open_status_tile();
+                }
             }
         }
     }
@@ -871,6 +1561,7 @@ impl App {
             3 => ViewMode::ServicesList,
             4 => ViewMode::StatusOverview,
             5 => ViewMode::LogsLive,
+            6 => ViewMode::ConfigEdit,
             _ => ViewMode::DetoursList, // Default fallback
         }
     }
@@ -896,19 +1587,241 @@ impl App {
         }
     }
     
+    /// Sync a filtered list: keep the selection on a visible entry, or park it
+    /// past the end (so lookups return None) when nothing is visible
+    fn sync_filtered_selection(
+        selected_idx: &mut usize,
+        visible: &[usize],
+        list_len: usize,
+        state: &mut ratatui::widgets::ListState,
+    ) {
+        if visible.is_empty() {
+            *selected_idx = list_len;
+            state.select(None);
+            return;
+        }
+        let position = visible.iter().position(|&i| i == *selected_idx)
+            .unwrap_or_else(|| visible.iter().position(|&i| i > *selected_idx).unwrap_or(visible.len() - 1));
+        *selected_idx = visible[position];
+        state.select(Some(position));
+    }
+    
+    /// Indices of detours shown with the current filter, in display order
+    pub fn visible_detours(&self) -> Vec<usize> {
+        self.detour_filter.apply(&self.detours).into_iter().map(|m| m.index).collect()
+    }
+    
+    pub fn visible_injections(&self) -> Vec<usize> {
+        self.injection_filter.apply(&self.injections).into_iter().map(|m| m.index).collect()
+    }
+    
+    pub fn visible_mirrors(&self) -> Vec<usize> {
+        self.mirror_filter.apply(&self.mirrors).into_iter().map(|m| m.index).collect()
+    }
+    
     /// Sync selection state for detours
     fn sync_detour_selection(&mut self) {
-        Self::sync_selection_generic(&mut self.selected_detour, self.detours.len(), &mut self.detour_state);
+        let visible = self.visible_detours();
+        Self::sync_filtered_selection(&mut self.selected_detour, &visible, self.detours.len(), &mut self.detour_state);
     }
     
     /// Sync selection state for injections
     fn sync_injection_selection(&mut self) {
-        Self::sync_selection_generic(&mut self.selected_injection, self.injections.len(), &mut self.injection_state);
+        let visible = self.visible_injections();
+        Self::sync_filtered_selection(&mut self.selected_injection, &visible, self.injections.len(), &mut self.injection_state);
     }
     
     /// Sync selection state for mirrors
     fn sync_mirror_selection(&mut self) {
-        Self::sync_selection_generic(&mut self.selected_mirror, self.mirrors.len(), &mut self.mirror_state);
+        let visible = self.visible_mirrors();
+        Self::sync_filtered_selection(&mut self.selected_mirror, &visible, self.mirrors.len(), &mut self.mirror_state);
+    }
+    
+    /// Filter for the list shown in the current view, if it has one
+    pub fn current_filter(&self) -> Option<&ListFilter> {
+        match self.view_mode {
+            ViewMode::DetoursList => Some(&self.detour_filter),
+            ViewMode::InjectionsList => Some(&self.injection_filter),
+            ViewMode::MirrorsList => Some(&self.mirror_filter),
+            _ => None,
+        }
+    }
+    
+    /// Apply `update` to the current view's filter and re-sync its selection
+    pub fn update_filter<F: FnOnce(&mut ListFilter)>(&mut self, update: F) {
+        match self.view_mode {
+            ViewMode::DetoursList => {
+                update(&mut self.detour_filter);
+                self.sync_detour_selection();
+            }
+            ViewMode::InjectionsList => {
+                update(&mut self.injection_filter);
+                self.sync_injection_selection();
+            }
+            ViewMode::MirrorsList => {
+                update(&mut self.mirror_filter);
+                self.sync_mirror_selection();
+            }
+            _ => {}
+        }
+    }
+    
+    pub fn is_searching(&self) -> bool {
+        self.current_filter().is_some_and(|f| f.editing)
+    }
+    
+    pub fn cycle_status_filter(&mut self) {
+        self.update_filter(|f| f.status = f.status.next());
+        if let Some(filter) = self.current_filter() {
+            self.add_toast(format!("Filter: {}", filter.status.label()), ToastType::Info);
+        }
+    }
+    
+    pub fn cycle_sort(&mut self) {
+        self.update_filter(|f| f.sort = f.sort.next());
+        if let Some(filter) = self.current_filter() {
+            self.add_toast(format!("Sort: {}", filter.sort.label()), ToastType::Info);
+        }
+    }
+    
+    /// Ids of the current list in display order, with the selected position
+    fn current_list_ids(&self) -> Option<(crate::config::EntryKind, Vec<String>, usize)> {
+        use crate::config::EntryKind;
+        let (kind, ids, selected): (EntryKind, Vec<String>, usize) = match self.view_mode {
+            ViewMode::DetoursList => (EntryKind::Detour, self.visible_detours().into_iter().map(|i| self.detours[i].id.clone()).collect(), self.selected_detour),
+            ViewMode::InjectionsList => (EntryKind::Injection, self.visible_injections().into_iter().map(|i| self.injections[i].id.clone()).collect(), self.selected_injection),
+            ViewMode::MirrorsList => (EntryKind::Mirror, self.visible_mirrors().into_iter().map(|i| self.mirrors[i].id.clone()).collect(), self.selected_mirror),
+            _ => return None,
+        };
+        let selected_id = match kind {
+            EntryKind::Detour => self.detours.get(selected).map(|e| &e.id),
+            EntryKind::Injection => self.injections.get(selected).map(|e| &e.id),
+            _ => self.mirrors.get(selected).map(|e| &e.id),
+        }?;
+        let position = ids.iter().position(|id| id == selected_id)?;
+        Some((kind, ids, position))
+    }
+    
+    /// Id and active state of the selected row in the current list
+    fn selected_entry_state(&self) -> Option<(String, bool)> {
+        match self.view_mode {
+            ViewMode::DetoursList => self.detours.get(self.selected_detour).map(|e| (e.id.clone(), e.active)),
+            ViewMode::InjectionsList => self.injections.get(self.selected_injection).map(|e| (e.id.clone(), e.active)),
+            ViewMode::MirrorsList => self.mirrors.get(self.selected_mirror).map(|e| (e.id.clone(), e.active)),
+            _ => None,
+        }
+    }
+    
+    /// Log lines for entries activate-all left alone because of `when:`
+    fn skipped_lines(entries: Vec<(&String, &Option<String>)>) -> Vec<String> {
+        entries.into_iter()
+            .filter_map(|(label, reason)| reason.as_ref().map(|r| format!("Skipped {} ({})", label, r)))
+            .collect()
+    }
+    
+    /// Commit the entry's managed file to history (no-op when history is off)
+    fn record_history(&mut self, id: &str, action: &str) {
+        let config = crate::operations::config_ops::load_config(&self.config_path);
+        match crate::history::record_entry(&config, id, action) {
+            Ok(true) => self.add_log("INFO", &format!("History: {} {}", action.to_lowercase(), id)),
+            Ok(false) => {}
+            Err(e) => self.add_log("WARN", &format!("History commit failed for {}: {}", id, e)),
+        }
+    }
+    
+    /// Toggle the mark on the selected entry and make it the range anchor
+    pub fn toggle_mark(&mut self) {
+        if let Some((_, ids, position)) = self.current_list_ids() {
+            let id = &ids[position];
+            if !self.marked.remove(id) {
+                self.marked.insert(id.clone());
+            }
+            self.mark_anchor = Some(position);
+        }
+    }
+    
+    /// Mark every visible entry between the anchor and the selection
+    pub fn mark_range(&mut self) {
+        if let Some((_, ids, position)) = self.current_list_ids() {
+            let anchor = self.mark_anchor.unwrap_or(position).min(ids.len() - 1);
+            let (from, to) = if anchor <= position { (anchor, position) } else { (position, anchor) };
+            self.marked.extend(ids[from..=to].iter().cloned());
+            self.add_toast(format!("{} marked", self.marked_in_view().len()), ToastType::Info);
+        }
+    }
+    
+    pub fn clear_marks(&mut self) {
+        self.marked.clear();
+        self.mark_anchor = None;
+    }
+    
+    /// Marked ids belonging to the current list, in display order
+    pub fn marked_in_view(&self) -> Vec<String> {
+        self.current_list_ids()
+            .map(|(_, ids, _)| ids.into_iter().filter(|id| self.marked.contains(id)).collect())
+            .unwrap_or_default()
+    }
+    
+    pub fn open_bulk_menu(&mut self) {
+        let Some((kind, _, _)) = self.current_list_ids() else { return };
+        let ids = self.marked_in_view();
+        if ids.is_empty() {
+            self.add_toast("No entries marked - [m] mark, [M] mark range".to_string(), ToastType::Info);
+            return;
+        }
+        let options = crate::operations::bulk::BulkOp::MENU.iter().map(|o| o.to_string()).collect();
+        self.popup = Some(Popup::select(format!("{} marked", ids.len()), options));
+        self.pending_action = Some(PendingAction::BulkChoose(kind, ids));
+    }
+    
+    /// Dispatch a choice from the bulk menu
+    pub fn handle_bulk_choice(&mut self, kind: crate::config::EntryKind, ids: Vec<String>, choice: usize) {
+        use crate::operations::bulk::{BulkOp, BulkPlan};
+        let op = match choice {
+            0 => BulkOp::Apply,
+            1 => BulkOp::Remove,
+            2 => BulkOp::Delete,
+            3 => {
+                self.run_checks(crate::checks::Scope::entries(kind, ids));
+                return;
+            }
+            _ => {
+                self.popup = Some(Popup::input("Move to Profile", "Profile name (empty clears it):"));
+                self.pending_action = Some(PendingAction::BulkProfile(kind, ids));
+                return;
+            }
+        };
+        self.confirm_bulk(BulkPlan { op, kind, ids });
+    }
+    
+    pub fn confirm_bulk(&mut self, plan: crate::operations::bulk::BulkPlan) {
+        let config = crate::operations::config_ops::load_config(&self.config_path);
+        self.popup = Some(Popup::confirm(format!("Bulk {}", plan.op.verb()), plan.describe(&config)));
+        self.pending_action = Some(PendingAction::Bulk(plan));
+    }
+    
+    pub fn execute_bulk(&mut self, plan: crate::operations::bulk::BulkPlan) {
+        let report = plan.execute(&self.config_path);
+        for outcome in &report.outcomes {
+            match &outcome.error {
+                None => self.add_log("INFO", &format!("{}: {}", plan.op.verb(), outcome.label)),
+                Some(e) => self.add_log("ERROR", &format!("{} failed for {}: {}", plan.op.verb(), outcome.label, e)),
+            }
+        }
+        
+        // Keep only failures marked so they can be retried
+        for outcome in report.outcomes.iter().filter(|o| o.error.is_none()) {
+            self.marked.remove(&outcome.id);
+        }
+        self.reload_config();
+        
+        let title = format!("Bulk {}", plan.op.verb());
+        if report.failed().next().is_none() {
+            self.mark_anchor = None;
+            self.popup = Some(Popup::info(title, report.to_text()));
+        } else {
+            self.popup = Some(Popup::error(title, report.to_text()));
+        }
     }
     
     /// Sync selection state for services
@@ -968,30 +1881,38 @@ impl App {
     
     // PHASE 5 REFACTOR: Generic list navigation helper
     /// Generic helper for navigating lists based on view mode
-    fn navigate_list<F>(&mut self, direction: fn(usize, usize) -> Option<usize>, update_selection: F)
+    fn navigate_list<D, F>(&mut self, direction: D, update_selection: F)
     where
+        D: Fn(usize, usize) -> Option<usize>,
         F: Fn(&mut Self, usize),
     {
         match self.view_mode {
+            // Filtered lists step through visible positions, not raw indices
             ViewMode::DetoursList => {
-                if let Some(new_idx) = direction(self.selected_detour, self.detours.len()) {
-                    self.selected_detour = new_idx;
-                    self.detour_state.select(Some(new_idx));
-                    update_selection(self, new_idx);
+                let visible = self.visible_detours();
+                let current = visible.iter().position(|&i| i == self.selected_detour).unwrap_or(0);
+                if let Some(pos) = direction(current, visible.len()) {
+                    self.selected_detour = visible[pos];
+                    self.detour_state.select(Some(pos));
+                    update_selection(self, visible[pos]);
                 }
             }
             ViewMode::InjectionsList => {
-                if let Some(new_idx) = direction(self.selected_injection, self.injections.len()) {
-                    self.selected_injection = new_idx;
-                    self.injection_state.select(Some(new_idx));
-                    update_selection(self, new_idx);
+                let visible = self.visible_injections();
+                let current = visible.iter().position(|&i| i == self.selected_injection).unwrap_or(0);
+                if let Some(pos) = direction(current, visible.len()) {
+                    self.selected_injection = visible[pos];
+                    self.injection_state.select(Some(pos));
+                    update_selection(self, visible[pos]);
                 }
             }
             ViewMode::MirrorsList => {
-                if let Some(new_idx) = direction(self.selected_mirror, self.mirrors.len()) {
-                    self.selected_mirror = new_idx;
-                    self.mirror_state.select(Some(new_idx));
-                    update_selection(self, new_idx);
+                let visible = self.visible_mirrors();
+                let current = visible.iter().position(|&i| i == self.selected_mirror).unwrap_or(0);
+                if let Some(pos) = direction(current, visible.len()) {
+                    self.selected_mirror = visible[pos];
+                    self.mirror_state.select(Some(pos));
+                    update_selection(self, visible[pos]);
                 }
             }
             ViewMode::ServicesList => {
@@ -1001,6 +1922,12 @@ impl App {
                     update_selection(self, new_idx);
                 }
             }
+            ViewMode::StatusOverview => {
+                if let Some(new_idx) = direction(self.selected_tile, crate::status::StatusTile::ALL.len()) {
+                    self.selected_tile = new_idx;
+                    update_selection(self, new_idx);
+                }
+            }
             _ => {}
         }
     }
@@ -1021,6 +1948,14 @@ impl App {
         );
     }
     
+    /// Select the row at `position` among the visible entries of the Content list
+    pub fn select_content_row(&mut self, position: usize) {
+        self.navigate_list(
+            move |_, len| (position < len).then_some(position),
+            |_, _| {},
+        );
+    }
+    
     /* PHASE 5 REFACTOR: Old navigate_content_up/down (commented out for review)
     fn navigate_content_up_OLD(&mut self) {
         match self.view_mode {
@@ -1111,7 +2046,7 @@ impl App {
             ViewMode::InjectionsList => vec!["List".to_string(), "New".to_string(), "Verify All".to_string(), "Activate All".to_string(), "Deactivate All".to_string()],
             ViewMode::MirrorsList => vec!["List".to_string(), "New".to_string(), "Verify All".to_string(), "Activate All".to_string(), "Deactivate All".to_string()],
             ViewMode::ServicesList => vec!["List".to_string()],
-            ViewMode::StatusOverview => vec!["Overview".to_string()],
+            ViewMode::StatusOverview => vec!["Overview".to_string(), "Refresh".to_string()],
             ViewMode::LogsLive => vec!["Logs".to_string()],
             ViewMode::ConfigEdit => vec!["Edit".to_string()],
             _ => vec![],
@@ -1164,6 +2099,17 @@ impl App {
                     ViewMode::MirrorsList => self.deactivate_all_mirrors(),
                     _ => {}
                 },
+                "Overview" => {
+                    self.active_column = ActiveColumn::Content;
+                }
+                "Refresh" if self.view_mode == ViewMode::StatusOverview => {
+                    self.refresh_status();
+                    self.add_toast("Status refreshed".to_string(), ToastType::Success);
+                }
+                "Edit" if self.view_mode == ViewMode::ConfigEdit => {
+                    self.open_config_editor();
+                    self.active_column = ActiveColumn::Content;
+                }
                 "New" => match self.view_mode {
                     ViewMode::DetoursList => {
                         self.view_mode = ViewMode::DetoursAdd;
@@ -1224,7 +2170,7 @@ impl App {
                 } else {
                     msg.clone()
                 };
-                self.add_log("INFO", &log_msg);
+                self.log_lines("INFO", &[log_msg]);
                 self.add_toast(format!("{} {}", action, item_name), ToastType::Success);
                 Ok(msg)
                             }
@@ -1273,7 +2219,7 @@ impl App {
                 } else {
                     msg.clone()
                 };
-                self.add_log("INFO", &log_msg);
+                self.log_lines("INFO", &[log_msg]);
                 self.add_toast(format!("{} {}", action, item_name), ToastType::Success);
                 Ok(msg)
                             }
@@ -1287,33 +2233,26 @@ impl App {
     
     pub fn handle_space(&mut self) {
         if self.active_column == ActiveColumn::Content {
+            // Goes through activation so entry hooks and history apply
+            let config = crate::operations::config_ops::load_config(&self.config_path);
+            let apply_fn = |id: &str| crate::operations::activation::activate(&config, id);
+            let remove_fn = |id: &str| crate::operations::activation::deactivate(&config, id);
             match self.view_mode {
                 ViewMode::DetoursList => {
                     // Extract values before mutable borrow
-                    let (current_active, original, custom) = if let Some(detour) = self.detours.get(self.selected_detour) {
-                        (detour.active, detour.original.clone(), detour.custom.clone())
+                    let (current_active, id, original) = if let Some(detour) = self.detours.get(self.selected_detour) {
+                        (detour.active, detour.id.clone(), detour.original.clone())
                     } else {
                         return;
                     };
 
-                    // Use generic toggle
-                    let apply_fn = || {
-                        use crate::manager::DetourManager;
-                        let m = DetourManager::new();
-                        m.apply_detour(&original, &custom)
-                    };
-                    let remove_fn = || {
-                        use crate::manager::DetourManager;
-                        let m = DetourManager::new();
-                        m.remove_detour(&original)
-                    };
                     let update_cfg = |config: &mut crate::config::DetourConfig, enabled: bool| {
                         if let Some(entry) = config.detours.iter_mut().find(|e| e.original == original) {
                             entry.enabled = enabled;
                         }
                     };
 
-                    if self.toggle_item_generic(current_active, "detour", "Mount Error", apply_fn, remove_fn, update_cfg).is_ok() {
+                    if self.toggle_item_generic(current_active, "detour", "Mount Error", || apply_fn(&id), || remove_fn(&id), update_cfg).is_ok() {
                         if let Some(detour) = self.detours.get_mut(self.selected_detour) {
                             detour.active = !current_active;
                         }
@@ -1321,29 +2260,19 @@ impl App {
                 }
                 ViewMode::InjectionsList => {
                     // Extract values before mutable borrow
-                    let (current_active, target, include_file) = if let Some(injection) = self.injections.get(self.selected_injection) {
-                        (injection.active, injection.target.clone(), injection.include_file.clone())
+                    let (current_active, id, target) = if let Some(injection) = self.injections.get(self.selected_injection) {
+                        (injection.active, injection.id.clone(), injection.target.clone())
                     } else {
                         return;
                     };
 
-                    let apply_fn = || {
-                        use crate::injection::InjectionManager;
-                        let m = InjectionManager::new();
-                        m.apply(std::path::Path::new(&target), std::path::Path::new(&include_file)).map(|_| format!("Applied include: {}", target))
-                    };
-                    let remove_fn = || {
-                        use crate::injection::InjectionManager;
-                        let m = InjectionManager::new();
-                        m.remove(std::path::Path::new(&target), std::path::Path::new(&include_file)).map(|_| format!("Removed include: {}", target))
-                    };
                     let update_cfg = |config: &mut crate::config::DetourConfig, enabled: bool| {
                         if let Some(entry) = config.injections.iter_mut().find(|e| e.target == target) {
                             entry.enabled = enabled;
                         }
                     };
 
-                    if self.toggle_item_generic(current_active, "include", "Include Error", apply_fn, remove_fn, update_cfg).is_ok() {
+                    if self.toggle_item_generic(current_active, "include", "Include Error", || apply_fn(&id), || remove_fn(&id), update_cfg).is_ok() {
                         if let Some(injection) = self.injections.get_mut(self.selected_injection) {
                             injection.active = !current_active;
                         }
@@ -1351,29 +2280,19 @@ impl App {
                 }
                 ViewMode::MirrorsList => {
                     // Extract values before mutable borrow
-                    let (current_active, source, target) = if let Some(mirror) = self.mirrors.get(self.selected_mirror) {
-                        (mirror.active, mirror.source.clone(), mirror.target.clone())
+                    let (current_active, id, source, target) = if let Some(mirror) = self.mirrors.get(self.selected_mirror) {
+                        (mirror.active, mirror.id.clone(), mirror.source.clone(), mirror.target.clone())
                     } else {
                         return;
                     };
 
-                    let apply_fn = || {
-                        use crate::mirror::MirrorManager;
-                        let m = MirrorManager::new();
-                        m.apply_mirror(&source, &target)
-                    };
-                    let remove_fn = || {
-                        use crate::mirror::MirrorManager;
-                        let m = MirrorManager::new();
-                        m.remove_mirror(&target)
-                    };
                     let update_cfg = |config: &mut crate::config::DetourConfig, enabled: bool| {
                         if let Some(entry) = config.mirrors.iter_mut().find(|e| e.source == source && e.target == target) {
                             entry.enabled = enabled;
                         }
                     };
 
-                    if self.toggle_item_generic(current_active, "mirror", "Mirror Error", apply_fn, remove_fn, update_cfg).is_ok() {
+                    if self.toggle_item_generic(current_active, "mirror", "Mirror Error", || apply_fn(&id), || remove_fn(&id), update_cfg).is_ok() {
                         if let Some(mirror) = self.mirrors.get_mut(self.selected_mirror) {
                             mirror.active = !current_active;
                         }
@@ -1381,6 +2300,8 @@ impl App {
                 }
                 _ => {}
             }
+            // A status filter may now hide the toggled entry
+            self.validate_all_selections();
         }
     }
 
@@ -1526,11 +2447,11 @@ impl App {
         let target_for_validate = target.clone();
         let include_for_validate = include.clone();
         let include_for_file_check = include.clone();
-        let editing_idx = self.injection_form.editing_index;
+        let editing_id = self.injection_form.editing_id.clone();
         let target_for_update = target.clone();
         let include_for_update = include.clone();
         let description_for_update = description.clone();
-        let was_new_injection = self.injection_form.editing_index.is_none();
+        let was_new_injection = self.injection_form.editing_id.is_none();
         let tgt_for_activation = target.clone();
         let inc_for_activation = include.clone();
         
@@ -1551,22 +2472,29 @@ impl App {
         };
         
         let update_fn = move |config: &mut crate::config::DetourConfig| -> Result<bool, String> {
-            if let Some(edit_idx) = editing_idx {
+            if let Some(edit_id) = &editing_id {
                 // Edit existing include
-                if let Some(entry) = config.injections.get_mut(edit_idx) {
+                if let Some(entry) = config.injections.iter_mut().find(|e| &e.id == edit_id) {
                     entry.target = target_for_update.clone();
                     entry.include_file = include_for_update.clone();
                     entry.description = Self::description_from_str(&description_for_update);
                     Ok(true) // Is edit
                 } else {
-                    Err("Edit index out of bounds".to_string())
+                    Err(format!("Include {} no longer exists in config", edit_id))
                 }
             } else {
                 // Add new include (active by default)
                 config.injections.push(crate::config::InjectionEntry { 
+                    id: String::new(),
                     target: target_for_update.clone(), 
                     include_file: include_for_update.clone(), 
                     description: Self::description_from_str(&description_for_update), 
+                    profile: None,
+                    when: None,
+                    hooks: None,
+                    template: false,
+                    output: None,
+                    service: None,
                     enabled: true 
                 });
                 Ok(false) // Is add
@@ -1737,7 +2665,6 @@ impl App {
     }
 
     pub fn injection_form_open_file_browser(&mut self) {
-        use crate::filebrowser::FileBrowser;
         let start_path = match self.injection_form.active_field {
             0 => {
                 if !self.injection_form.target_path.is_empty() {
@@ -1761,7 +2688,8 @@ impl App {
             }
             _ => "/home/pi",
         };
-        self.file_browser = Some(FileBrowser::new(start_path));
+        let start_path = start_path.to_string();
+        self.open_file_browser(&start_path);
     }
     pub fn injection_form_close_file_browser(&mut self, selected_path: Option<String>) {
         if let Some(path) = selected_path {
@@ -1835,7 +2763,7 @@ impl App {
     /// sync_selection_fn: Function to sync selection after deletion
     fn delete_item_generic<F, G>(
         &mut self,
-        _index: usize,
+        _id: &str,
         was_active: bool,
         item_name: &str,
         disable_fn: F,
@@ -1893,7 +2821,7 @@ impl App {
 
     pub fn delete_selected_injection(&mut self) {
         if let Some(_include) = self.injections.get(self.selected_injection) {
-            self.pending_action = Some(PendingAction::DeleteInjection(self.selected_injection));
+            self.pending_action = Some(PendingAction::DeleteInjection(self.injections[self.selected_injection].id.clone()));
             self.popup = Some(crate::popup::Popup::Confirm {
                 title: "Confirm Delete".to_string(),
                 message: format!("Delete this include?\n\n{} ← {}", self.injections[self.selected_injection].target, self.injections[self.selected_injection].include_file),
@@ -1902,11 +2830,11 @@ impl App {
         }
     }
 
-    pub fn confirm_delete_injection(&mut self, index: usize) {
+    pub fn confirm_delete_injection(&mut self, id: &str) {
         use std::path::Path;
         
         // Extract values before operations
-        let (was_active, target_path_str, include_file_path_str) = if let Some(injection) = self.injections.get(index) {
+        let (was_active, target_path_str, include_file_path_str) = if let Some(injection) = self.injections.iter().find(|i| i.id == id) {
             (injection.active, injection.target.clone(), injection.include_file.clone())
         } else {
             return;
@@ -1923,19 +2851,19 @@ impl App {
             manager.remove(&target_path, &include_path)
         };
         
-        let idx = index; // Copy index for closure
+        let entry_id = id.to_string(); // Owned id for closure
         let remove_fn = move |config: &mut crate::config::DetourConfig| -> Result<Option<String>, String> {
-            if idx < config.injections.len() {
+            if let Some(idx) = config.injections.iter().position(|e| e.id == entry_id) {
                 let removed = config.injections.remove(idx);
                 // Return include file path for file check
                 Ok(Some(removed.include_file.clone()))
                 } else {
-                Err("Index out of bounds".to_string())
+                Err(format!("Include {} no longer exists in config", entry_id))
             }
         };
         
         match self.delete_item_generic(
-            index,
+            id,
             was_active,
             "include",
             disable_fn,
@@ -1946,7 +2874,7 @@ impl App {
             Ok(_) => {}
             Err(e) if e.starts_with("FILE_EXISTS:") => {
                 let file_path = e.trim_start_matches("FILE_EXISTS:");
-                self.pending_action = Some(PendingAction::DeleteInjectionAndFile(index, file_path.to_string()));
+                self.pending_action = Some(PendingAction::DeleteInjectionAndFile(id.to_string(), file_path.to_string()));
                     self.popup = Some(crate::popup::Popup::Confirm {
                         title: "Delete Include File?".to_string(),
                     message: format!("The include file still exists:\n\n{}\n\nDelete it as well?", file_path),
@@ -1960,7 +2888,7 @@ impl App {
     }
     
     // Delete include and optionally the include file
-    pub fn delete_injection_and_file(&mut self, _index: usize, include_file_path: String, delete_file: bool) {
+    pub fn delete_injection_and_file(&mut self, _id: &str, include_file_path: String, delete_file: bool) {
         use std::path::Path;
         use crate::operations::file_ops;
         
@@ -1990,7 +2918,7 @@ impl App {
             // Load config to get description
             use crate::operations::config_ops;
             let config = config_ops::load_config(&self.config_path);
-            let description = config.injections.get(self.selected_injection)
+            let description = config.injections.iter().find(|e| e.id == injection.id)
                 .and_then(|e| e.description.clone())
                 .unwrap_or_default();
             
@@ -2001,7 +2929,7 @@ impl App {
                 description,
                 active_field: 0,
                 cursor_pos: 0,
-                editing_index: Some(self.selected_injection),
+                editing_id: Some(injection.id.clone()),
             };
             
             // Switch to add view (reuse for editing)
@@ -2017,7 +2945,7 @@ impl App {
             // Load config to get description
             use crate::operations::config_ops;
             let config = config_ops::load_config(&self.config_path);
-            let description = config.detours.get(self.selected_detour)
+            let description = config.detours.iter().find(|e| e.id == detour.id)
                 .and_then(|e| e.description.clone())
                 .unwrap_or_default();
             
@@ -2028,7 +2956,7 @@ impl App {
                 description,
                 active_field: 0,
                 cursor_pos: 0,
-                editing_index: Some(self.selected_detour),
+                editing_id: Some(detour.id.clone()),
             };
             
             // Switch to edit view
@@ -2042,7 +2970,7 @@ impl App {
     pub fn delete_selected_detour(&mut self) {
         if let Some(_detour) = self.detours.get(self.selected_detour) {
             // Show confirmation popup
-            self.pending_action = Some(PendingAction::DeleteDetour(self.selected_detour));
+            self.pending_action = Some(PendingAction::DeleteDetour(self.detours[self.selected_detour].id.clone()));
             self.popup = Some(crate::popup::Popup::Confirm {
                 title: "Confirm Delete".to_string(),
                 message: format!("Delete this detour?\n\n{} ← {}", self.detours[self.selected_detour].original, self.detours[self.selected_detour].custom),
@@ -2051,35 +2979,36 @@ impl App {
         }
     }
 
-    pub fn confirm_delete_detour(&mut self, index: usize) {
+    pub fn confirm_delete_detour(&mut self, id: &str) {
         // Extract values before operations
-        let (was_active, original_path_str) = if let Some(detour) = self.detours.get(index) {
-            (detour.active, detour.original.clone())
+        let (was_active, original_path_str, scope) = if let Some(detour) = self.detours.iter().find(|d| d.id == id) {
+            (detour.active, detour.original.clone(), detour.scope.clone())
         } else {
             return;
         };
         
         // Store original path for closure (managers are Copy-like, create new instance)
         let orig = original_path_str.clone();
+        let scope_id = id.to_string();
         let disable_fn = move || {
             use crate::manager::DetourManager;
             let manager = DetourManager::new();
-            manager.remove_detour(&orig).map(|_| ())
+            manager.remove_entry(&scope_id, &orig, scope.as_ref()).map(|_| ())
         };
         
-        let idx = index; // Copy index for closure
+        let entry_id = id.to_string(); // Owned id for closure
         let remove_fn = move |config: &mut crate::config::DetourConfig| -> Result<Option<String>, String> {
-            if idx < config.detours.len() {
+            if let Some(idx) = config.detours.iter().position(|e| e.id == entry_id) {
                 let removed = config.detours.remove(idx);
                 // Return custom path for file check
                 Ok(Some(removed.custom.clone()))
             } else {
-                Err("Index out of bounds".to_string())
+                Err(format!("Detour {} no longer exists in config", entry_id))
             }
         };
         
         match self.delete_item_generic(
-            index,
+            id,
             was_active,
             "detour",
             disable_fn,
@@ -2090,7 +3019,7 @@ impl App {
             Ok(_) => {}
             Err(e) if e.starts_with("FILE_EXISTS:") => {
                 let file_path = e.trim_start_matches("FILE_EXISTS:");
-                self.pending_action = Some(PendingAction::DeleteDetourAndFile(index, file_path.to_string()));
+                self.pending_action = Some(PendingAction::DeleteDetourAndFile(id.to_string(), file_path.to_string()));
                 self.popup = Some(crate::popup::Popup::Confirm {
                     title: "Delete Custom File?".to_string(),
                     message: format!("The custom file still exists:\n\n{}\n\nDelete it as well?", file_path),
@@ -2171,7 +3100,7 @@ impl App {
     }
     */
     
-    pub fn delete_detour_and_file(&mut self, _index: usize, custom_path: String, delete_file: bool) {
+    pub fn delete_detour_and_file(&mut self, _id: &str, custom_path: String, delete_file: bool) {
         use std::path::Path;
         use crate::operations::file_ops;
         
@@ -2219,6 +3148,15 @@ impl App {
     pub fn form_save_detour(&mut self) {
         use std::path::Path;
         
+        // An empty custom path falls back to the managed directory
+        if self.add_form.custom_path.is_empty() && !self.add_form.original_path.is_empty() {
+            let config = crate::operations::config_ops::load_config(&self.config_path);
+            if let Some(managed) = crate::template::managed_path(&config.templates, &self.add_form.original_path) {
+                self.add_log("INFO", &format!("Using managed path: {}", managed));
+                self.add_form.custom_path = managed;
+            }
+        }
+        
         // Validate input
         use crate::validation;
         if let Err(e) = validation::validate_fields_not_empty(&[
@@ -2234,12 +3172,7 @@ impl App {
         let custom_path = Path::new(&self.add_form.custom_path);
         if !file_ops::file_exists(custom_path) {
             // File doesn't exist - prompt to create
-            self.pending_action = Some(PendingAction::CreateFileAndSaveDetour);
-            self.popup = Some(crate::popup::Popup::Confirm {
-                title: "File Not Found".to_string(),
-                message: format!("Custom file doesn't exist:\n{}\n\nCreate it?", self.add_form.custom_path),
-                selected: 0, // Default to "Yes"
-            });
+            self.prompt_create_custom_file();
             return;
         }
         
@@ -2258,7 +3191,7 @@ impl App {
         let original_for_validate = original.clone();
         let custom_for_validate = custom.clone();
         let custom_for_file_check = custom.clone();
-        let editing_idx = self.add_form.editing_index;
+        let editing_id = self.add_form.editing_id.clone();
         let original_for_update = original.clone();
         let custom_for_update = custom.clone();
         let description = self.add_form.description.clone();
@@ -2281,22 +3214,31 @@ impl App {
         };
         
         let update_fn = move |config: &mut crate::config::DetourConfig| -> Result<bool, String> {
-            if let Some(edit_idx) = editing_idx {
+            if let Some(edit_id) = &editing_id {
                 // Edit existing detour
-                if let Some(entry) = config.detours.get_mut(edit_idx) {
+                if let Some(entry) = config.detours.iter_mut().find(|e| &e.id == edit_id) {
                     entry.original = original_for_update.clone();
                     entry.custom = custom_for_update.clone();
                     entry.description = Self::description_from_str(&description);
                     Ok(true) // Is edit
                 } else {
-                    Err("Edit index out of bounds".to_string())
+                    Err(format!("Detour {} no longer exists in config", edit_id))
                 }
             } else {
                 // Add new detour
                 config.detours.push(DetourEntry {
+                    id: String::new(),
                     original: original_for_update.clone(),
                     custom: custom_for_update.clone(),
                     description: Self::description_from_str(&description),
+                    profile: None,
+                    when: None,
+                    hooks: None,
+                    scope: None,
+                    readonly: false,
+                    match_permissions: false,
+                    deb: None,
+                    service: None,
                     enabled: false,
                 });
                 Ok(false) // Is add
@@ -2311,17 +3253,18 @@ impl App {
             app.active_column = ActiveColumn::Content;
         };
         
-        let custom_path_for_error = custom.clone();
         match self.save_item_generic("detour", validate_fn, Some(file_check), update_fn, success_cb) {
-            Ok(_) => {}
+            Ok(_) => {
+                let id = self.detours.iter()
+                    .find(|d| d.original == original && d.custom == custom)
+                    .map(|d| d.id.clone());
+                if let Some(id) = id {
+                    self.record_history(&id, "Save");
+                }
+            }
             Err(e) if e.starts_with("FILE_MISSING:") => {
                 // This shouldn't happen as form_save_detour checks first, but handle anyway
-                self.pending_action = Some(PendingAction::CreateFileAndSaveDetour);
-                self.popup = Some(crate::popup::Popup::Confirm {
-                    title: "File Not Found".to_string(),
-                    message: format!("Custom file doesn't exist:\n{}\n\nCreate it?", custom_path_for_error),
-                    selected: 0,
-                });
+                self.prompt_create_custom_file();
             }
             Err(e) => {
                 self.show_error("Save Error".to_string(), e);
@@ -2382,22 +3325,31 @@ impl App {
     }
     */
     
-    pub fn create_custom_file_and_save(&mut self) {
+    /// Ask where the missing custom file's contents should come from
+    fn prompt_create_custom_file(&mut self) {
+        let config = crate::operations::config_ops::load_config(&self.config_path);
+        let sources = crate::template::available_sources(&config.templates);
+        let options = sources.iter().map(|source| source.label()).collect();
+        self.popup = Some(Popup::select(format!("Create {}", self.add_form.custom_path), options));
+        self.pending_action = Some(PendingAction::CreateFileAndSaveDetour(sources));
+    }
+    
+    pub fn create_custom_file_and_save(&mut self, source: crate::template::FileSource) {
         use std::path::Path;
-        use crate::operations::file_ops;
-        
-        let original_path = Path::new(&self.add_form.original_path);
-        let custom_path = Path::new(&self.add_form.custom_path);
+        use crate::operations::{config_ops, file_ops};
+        use crate::template;
         
-        match file_ops::duplicate_file(original_path, custom_path) {
-            Ok(contents) => {
-                if !contents.is_empty() {
-                    self.add_log("INFO", &format!("Copied contents from: {}", self.add_form.original_path));
-                }
-        self.add_log("INFO", &format!("Created file: {}", self.add_form.custom_path));
+        let config = config_ops::load_config(&self.config_path);
+        let vars = template::TemplateVars::collect(&self.add_form.original_path, &self.add_form.custom_path);
+        let result = template::generate(&source, &vars, &config.templates)
+            .and_then(|contents| file_ops::write_file(Path::new(&self.add_form.custom_path), &contents));
         
-        // Now save the detour
-        self.save_detour_to_config();
+        match result {
+            Ok(()) => {
+                self.add_log("INFO", &format!("Created file: {} ({})", self.add_form.custom_path, source.label()));
+                
+                // Now save the detour
+                self.save_detour_to_config();
             }
             Err(e) => {
                 self.show_error("File Creation Error".to_string(), e);
@@ -2410,106 +3362,94 @@ impl App {
     }
     
     pub fn validate_detours_all(&mut self) {
-        use std::time::Instant;
-        use std::path::Path;
-        
-        let start = Instant::now();
-        let mut results = vec![];
-        
-        let phase1_start = Instant::now();
-        let mut missing_original = 0;
-        let mut missing_custom = 0;
-        let mut unreadable = 0;
-        
-        for detour in &self.detours {
-            let original_path = Path::new(&detour.original);
-            let custom_path = Path::new(&detour.custom);
-            
-            if !original_path.exists() { missing_original += 1; }
-            if !custom_path.exists() { missing_custom += 1; }
-            
-            // Check readability
-            if original_path.exists() && std::fs::metadata(&detour.original).is_err() {
-                unreadable += 1;
-            }
-            if custom_path.exists() && std::fs::metadata(&detour.custom).is_err() {
-                unreadable += 1;
-            }
-        }
-        let phase1_time = phase1_start.elapsed();
-        results.push(format!(
-            "Phase 1: File Existence Check ({:.2}ms)\n  {} missing original files\n  {} missing custom files\n  {} unreadable files",
-            phase1_time.as_secs_f64()*1000.0,
-            missing_original,
-            missing_custom,
-            unreadable
-        ));
-        
-        let phase2_start = Instant::now();
-        let active_count = self.detours.iter().filter(|d| d.active).count();
-        let phase2_time = phase2_start.elapsed();
-        results.push(format!(
-            "Phase 2: State Validation ({:.2}ms)\n  {} active detours",
-            phase2_time.as_secs_f64()*1000.0,
-            active_count
-        ));
-        
-        let phase3_start = Instant::now();
-        let mut conflicts = 0;
-        for i in 0..self.detours.len() {
-            for j in (i+1)..self.detours.len() {
-                if self.detours[i].original == self.detours[j].original {
-                    conflicts += 1;
-                }
-            }
-        }
-        let phase3_time = phase3_start.elapsed();
-        results.push(format!(
-            "Phase 3: Conflict Detection ({:.2}ms)\n  {} conflicting detours",
-            phase3_time.as_secs_f64()*1000.0,
-            conflicts
-        ));
-        
-        let total_time = start.elapsed();
-        let has_issues = missing_original > 0 || missing_custom > 0 || unreadable > 0 || conflicts > 0;
-        
-        self.validation_report = Some(ValidationReport {
-            content: results.join("\n\n"),
-            has_issues,
-        });
-        
-        if has_issues {
-            self.add_log("WARN", &format!("Validation found issues ({}ms)", total_time.as_secs_f64()*1000.0));
-        } else {
-            self.add_log("SUCCESS", &format!("All detours validated successfully ({}ms)", total_time.as_secs_f64()*1000.0));
-        }
+        self.run_checks(crate::checks::Scope::kind(crate::status::EntryKind::Detour));
     }
 
     pub fn validate_single_detour(&mut self, index: usize) {
-        use std::path::Path;
-
         if let Some(detour) = self.detours.get(index) {
-        let original_path = Path::new(&detour.original);
-        let custom_path = Path::new(&detour.custom);
-            
-            let missing_original = if !original_path.exists() { 1 } else { 0 };
-            let missing_custom = if !custom_path.exists() { 1 } else { 0 };
-        let mut unreadable = 0;
-        if original_path.exists() && std::fs::metadata(&detour.original).is_err() { unreadable += 1; }
-        if custom_path.exists() && std::fs::metadata(&detour.custom).is_err() { unreadable += 1; }
-            
-            let result = format!(
-                "Detour: {} ← {}\n  {} missing original\n  {} missing custom\n  {} unreadable",
-                detour.original, detour.custom, missing_original, missing_custom, unreadable
-            );
-            
-            // Show validation result in a popup
-            self.popup = Some(crate::popup::Popup::info("Validation Result", result));
-            } else {
+            let scope = crate::checks::Scope::entry(crate::status::EntryKind::Detour, &detour.id);
+            self.run_checks(scope);
+        } else {
             self.show_error("Validation Error".to_string(), "Invalid index".to_string());
         }
     }
     
+    /// Run the registered checks against the on-disk config and show the report
+    pub fn run_checks(&mut self, scope: crate::checks::Scope) {
+        use crate::checks::{CheckReport, Severity};
+        use crate::operations::config_ops;
+        
+        let config = config_ops::load_config(&self.config_path);
+        let report = CheckReport::run(&config, &scope);
+        
+        let errors = report.count(Severity::Error);
+        let warnings = report.count(Severity::Warning);
+        if errors > 0 || warnings > 0 {
+            self.add_log("WARN", &format!("Validation found {} errors, {} warnings ({:.2}ms)", errors, warnings, report.duration_ms));
+        } else {
+            self.add_log("SUCCESS", &format!("Validation passed ({:.2}ms)", report.duration_ms));
+        }
+        
+        let selected = self.validation_report.as_ref()
+            .filter(|v| v.scope == scope)
+            .map(|v| v.selected.min(report.findings.len().saturating_sub(1)))
+            .unwrap_or(0);
+        self.validation_report = Some(ValidationReport { report, scope, selected });
+    }
+    
+    pub fn validation_select_prev(&mut self) {
+        if let Some(view) = self.validation_report.as_mut() {
+            view.selected = view.selected.saturating_sub(1);
+        }
+    }
+    
+    pub fn validation_select_next(&mut self) {
+        if let Some(view) = self.validation_report.as_mut() {
+            if view.selected + 1 < view.report.findings.len() {
+                view.selected += 1;
+            }
+        }
+    }
+    
+    /// Close the report and select the entry behind the highlighted finding
+    pub fn validation_jump_to_entry(&mut self) {
+        let entry = self.validation_report.as_ref()
+            .and_then(|v| v.selected_finding())
+            .and_then(|f| f.entry.clone());
+        self.close_validation_report();
+        if let Some(entry) = entry {
+            self.focus_entry(entry.kind, &entry.id);
+        }
+    }
+    
+    /// Apply the highlighted finding's fix, then re-run the same checks
+    pub fn validation_apply_fix(&mut self) {
+        let (fix, scope) = match self.validation_report.as_ref() {
+            Some(view) => (view.selected_finding().and_then(|f| f.fix.clone()), view.scope.clone()),
+            None => return,
+        };
+        let fix = match fix {
+            Some(fix) => fix,
+            None => {
+                self.add_toast("No automatic fix for this finding".to_string(), ToastType::Info);
+                return;
+            }
+        };
+        
+        match fix.apply(&self.config_path) {
+            Ok(msg) => {
+                self.add_log("SUCCESS", &msg);
+                self.add_toast(msg, ToastType::Success);
+                self.reload_config();
+            }
+            Err(e) => {
+                self.add_log("ERROR", &format!("Fix failed: {}", e));
+                self.add_toast(format!("Fix failed: {}", e), ToastType::Error);
+            }
+        }
+        self.run_checks(scope);
+    }
+    
     pub fn form_complete_path(&mut self) {
         self.handle_form_action(FormAction::CompletePath);
     }
@@ -2519,7 +3459,6 @@ impl App {
     }
     
     pub fn form_open_file_browser(&mut self) {
-        use crate::filebrowser::FileBrowser;
         
         // Start from current field's path or home directory
         let start_path = match self.add_form.active_field {
@@ -2546,7 +3485,43 @@ impl App {
             _ => "/home/pi",
         };
         
-        self.file_browser = Some(FileBrowser::new(start_path));
+        let start_path = start_path.to_string();
+        self.open_file_browser(&start_path);
+    }
+    
+    /// Open the file browser with bookmarks from config and recent paths from the state file
+    pub fn open_file_browser(&mut self, start_path: &str) {
+        let config = crate::operations::config_ops::load_config(&self.config_path);
+        let recent = crate::filebrowser::load_recent(&self.recent_paths_file);
+        self.file_browser = Some(crate::filebrowser::FileBrowser::with_places(start_path, config.bookmarks, recent));
+    }
+    
+    /// Persist the browser's bookmarks to config
+    pub fn save_browser_bookmarks(&mut self) {
+        let Some(browser) = &self.file_browser else { return };
+        let bookmarks = browser.bookmarks.clone();
+        if let Err(e) = crate::operations::config_ops::with_config_mut(&self.config_path, |config| {
+            config.bookmarks = bookmarks.clone();
+            Ok(())
+        }) {
+            self.add_toast(format!("Failed to save bookmarks: {}", e), ToastType::Error);
+        }
+    }
+    
+    /// Persist the browser's recent paths to the state file
+    pub fn save_recent_paths(&mut self) {
+        let Some(browser) = &self.file_browser else { return };
+        if let Err(e) = crate::filebrowser::save_recent(&self.recent_paths_file, &browser.recent) {
+            self.add_log("WARN", &format!("Failed to save recent paths: {}", e));
+        }
+    }
+    
+    /// Record a path picked in the browser as recent
+    pub fn remember_browser_path(&mut self, path: &str) {
+        if let Some(browser) = &mut self.file_browser {
+            crate::filebrowser::push_recent(&mut browser.recent, path);
+            self.save_recent_paths();
+        }
     }
     
     pub fn form_close_file_browser(&mut self, selected_path: Option<String>) {
@@ -2584,7 +3559,7 @@ impl App {
     
     pub fn delete_selected_mirror(&mut self) {
         if let Some(_mirror) = self.mirrors.get(self.selected_mirror) {
-            self.pending_action = Some(PendingAction::DeleteMirror(self.selected_mirror));
+            self.pending_action = Some(PendingAction::DeleteMirror(self.mirrors[self.selected_mirror].id.clone()));
             self.popup = Some(crate::popup::Popup::Confirm {
                 title: "Confirm Delete".to_string(),
                 message: format!("Delete this mirror?\n\n{} → {}", self.mirrors[self.selected_mirror].source, self.mirrors[self.selected_mirror].target),
@@ -2593,9 +3568,9 @@ impl App {
         }
     }
     
-    pub fn confirm_delete_mirror(&mut self, index: usize) {
+    pub fn confirm_delete_mirror(&mut self, id: &str) {
         // Extract values before operations
-        let (was_active, source_str, target_str) = if let Some(mirror) = self.mirrors.get(index) {
+        let (was_active, source_str, target_str) = if let Some(mirror) = self.mirrors.iter().find(|m| m.id == id) {
             (mirror.active, mirror.source.clone(), mirror.target.clone())
         } else {
             return;
@@ -2609,19 +3584,19 @@ impl App {
             manager.remove_mirror(&tgt).map(|_| ())
         };
         
-        let idx = index; // Copy index for closure
+        let entry_id = id.to_string(); // Owned id for closure
         let remove_fn = move |config: &mut crate::config::DetourConfig| -> Result<Option<String>, String> {
-            if idx < config.mirrors.len() {
+            if let Some(idx) = config.mirrors.iter().position(|e| e.id == entry_id) {
                 config.mirrors.remove(idx);
                 Ok(None) // Mirrors don't have separate files to check
             } else {
-                Err("Index out of bounds".to_string())
+                Err(format!("Mirror {} no longer exists in config", entry_id))
             }
         };
         
         // Mirrors don't have separate files to check (symlink is the file)
         match self.delete_item_generic(
-            index,
+            id,
             was_active,
             &format!("mirror: {} → {}", source_str, target_str),
             disable_fn,
@@ -2690,7 +3665,7 @@ impl App {
             // Load config to get description
             use crate::operations::config_ops;
             let config = config_ops::load_config(&self.config_path);
-            let description = config.mirrors.get(self.selected_mirror)
+            let description = config.mirrors.iter().find(|e| e.id == mirror.id)
                 .and_then(|e| e.description.clone())
                 .unwrap_or_default();
             
@@ -2701,7 +3676,7 @@ impl App {
                 description,
                 active_field: 0,
                 cursor_pos: 0,
-                editing_index: Some(self.selected_mirror),
+                editing_id: Some(mirror.id.clone()),
             };
             
             // Switch to edit view
@@ -2725,28 +3700,32 @@ impl App {
             ])
         };
         
-        let editing_idx = self.mirror_form.editing_index;
+        let editing_id = self.mirror_form.editing_id.clone();
         let source_clone = source.clone();
         let target_clone = target.clone();
         let description_clone = description.clone();
         
         let update_fn = move |config: &mut crate::config::DetourConfig| -> Result<bool, String> {
-            if let Some(edit_idx) = editing_idx {
+            if let Some(edit_id) = &editing_id {
                 // Edit existing mirror
-                if let Some(entry) = config.mirrors.get_mut(edit_idx) {
+                if let Some(entry) = config.mirrors.iter_mut().find(|e| &e.id == edit_id) {
                     entry.source = source_clone.clone();
                     entry.target = target_clone.clone();
                     entry.description = Self::description_from_str(&description_clone);
                     Ok(true) // Is edit
                 } else {
-                    Err("Edit index out of bounds".to_string())
+                    Err(format!("Mirror {} no longer exists in config", edit_id))
                 }
             } else {
                 // Add new mirror (inactive by default - user must activate)
                 config.mirrors.push(crate::config::MirrorEntry { 
+                    id: String::new(),
                     source: source_clone.clone(), 
                     target: target_clone.clone(), 
                     description: Self::description_from_str(&description_clone), 
+                    profile: None,
+                    when: None,
+                    hooks: None,
                     enabled: false 
                 });
                 Ok(false) // Is add
@@ -2828,7 +3807,6 @@ impl App {
     */
     
     pub fn mirror_form_open_file_browser(&mut self) {
-        use crate::filebrowser::FileBrowser;
         let start_path = match self.mirror_form.active_field {
             0 => {
                 if !self.mirror_form.source_path.is_empty() {
@@ -2852,7 +3830,8 @@ impl App {
             }
             _ => "/home/pi",
         };
-        self.file_browser = Some(FileBrowser::new(start_path));
+        let start_path = start_path.to_string();
+        self.open_file_browser(&start_path);
     }
     
     pub fn mirror_form_close_file_browser(&mut self, selected_path: Option<String>) {
@@ -2897,26 +3876,9 @@ impl App {
     }
     
     pub fn validate_single_injection(&mut self, index: usize) {
-        use std::path::Path;
-        
         if let Some(injection) = self.injections.get(index) {
-            let target_path = Path::new(&injection.target);
-            let include_path = Path::new(&injection.include_file);
-            
-            let missing_target = if !target_path.exists() { 1 } else { 0 };
-            let missing_include = if !include_path.exists() { 1 } else { 0 };
-            let mut unreadable = 0;
-            if target_path.exists() && std::fs::metadata(&injection.target).is_err() { unreadable += 1; }
-            if include_path.exists() && std::fs::metadata(&injection.include_file).is_err() { unreadable += 1; }
-            
-            let result = format!(
-                "Injection: {} ← {}\n  {} missing target\n  {} missing injection file\n  {} unreadable\n  Active: {}",
-                injection.target, injection.include_file, missing_target, missing_include, unreadable,
-                if injection.active { "Yes" } else { "No" }
-            );
-            
-            // Show validation result in a popup
-            self.popup = Some(crate::popup::Popup::info("Validation Result", result));
+            let scope = crate::checks::Scope::entry(crate::status::EntryKind::Injection, &injection.id);
+            self.run_checks(scope);
         } else {
             self.show_error("Validation Error".to_string(), "Invalid index".to_string());
         }
@@ -0,0 +1,341 @@
+// In-memory backends for tests - no root, no real files touched
+
+use super::{Backend, Filesystem, Mounts, Privileged};
+use std::collections::{BTreeMap, HashSet};
+use std::io;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Node {
+    File { contents: String, owner_mode: (u32, u32, u32) },
+    Dir,
+    Symlink(PathBuf),
+}
+
+/// Symlink hops before resolution gives up (ELOOP)
+const MAX_HOPS: usize = 40;
+
+/// Lexically resolve `.` and `..`; paths are absolute
+fn normalize(path: &Path) -> PathBuf {
+    let mut out = PathBuf::from("/");
+    for component in path.components() {
+        match component {
+            Component::ParentDir => {
+                out.pop();
+            }
+            Component::Normal(name) => out.push(name),
+            _ => {}
+        }
+    }
+    out
+}
+
+fn not_found(path: &Path) -> io::Error {
+    io::Error::new(io::ErrorKind::NotFound, format!("{}: not found", path.display()))
+}
+
+/// Filesystem tree keyed by absolute path
+pub struct MemoryFs {
+    nodes: Mutex<BTreeMap<PathBuf, Node>>,
+    /// Paths unprivileged writes are denied on
+    protected: Mutex<HashSet<PathBuf>>,
+}
+
+impl Default for MemoryFs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MemoryFs {
+    pub fn new() -> Self {
+        let mut nodes = BTreeMap::new();
+        nodes.insert(PathBuf::from("/"), Node::Dir);
+        Self { nodes: Mutex::new(nodes), protected: Mutex::new(HashSet::new()) }
+    }
+
+    /// Follow every symlink in `path`, including the last component
+    fn resolve(nodes: &BTreeMap<PathBuf, Node>, path: &Path) -> Option<PathBuf> {
+        let mut path = normalize(path);
+        'hop: for _ in 0..MAX_HOPS {
+            let mut resolved = PathBuf::from("/");
+            let components: Vec<_> = path.components().skip(1).collect();
+            for (i, component) in components.iter().enumerate() {
+                resolved.push(component);
+                if let Some(Node::Symlink(target)) = nodes.get(&resolved) {
+                    let mut next = resolved.parent().unwrap_or(Path::new("/")).join(target);
+                    next.extend(&components[i + 1..]);
+                    path = normalize(&next);
+                    continue 'hop;
+                }
+            }
+            return Some(resolved);
+        }
+        None
+    }
+
+    /// Resolve the parent only, so `path` itself may be a symlink
+    fn locate(nodes: &BTreeMap<PathBuf, Node>, path: &Path) -> Option<PathBuf> {
+        let path = normalize(path);
+        match (path.parent(), path.file_name()) {
+            (Some(parent), Some(name)) => Some(Self::resolve(nodes, parent)?.join(name)),
+            _ => Some(path),
+        }
+    }
+
+    fn insert(&self, path: &Path, node: Node) {
+        let mut nodes = self.nodes.lock().unwrap();
+        let path = normalize(path);
+        for ancestor in path.ancestors().skip(1) {
+            nodes.entry(ancestor.to_path_buf()).or_insert(Node::Dir);
+        }
+        nodes.insert(path, node);
+    }
+
+    pub fn add_file(&self, path: impl AsRef<Path>, contents: &str) {
+        self.insert(path.as_ref(), Node::File { contents: contents.to_string(), owner_mode: (0, 0, 0o644) });
+    }
+
+    pub fn add_dir(&self, path: impl AsRef<Path>) {
+        self.insert(path.as_ref(), Node::Dir);
+    }
+
+    pub fn add_symlink(&self, link: impl AsRef<Path>, target: impl AsRef<Path>) {
+        self.insert(link.as_ref(), Node::Symlink(target.as_ref().to_path_buf()));
+    }
+
+    /// Deny unprivileged writes, as for root-owned files
+    pub fn protect(&self, path: impl AsRef<Path>) {
+        self.protected.lock().unwrap().insert(normalize(path.as_ref()));
+    }
+
+    pub fn set_owner_mode(&self, path: impl AsRef<Path>, uid: u32, gid: u32, mode: u32) {
+        let mut nodes = self.nodes.lock().unwrap();
+        if let Some(Node::File { owner_mode, .. }) = nodes.get_mut(&normalize(path.as_ref())) {
+            *owner_mode = (uid, gid, mode);
+        }
+    }
+
+    /// Contents of the file `path` resolves to
+    pub fn contents(&self, path: impl AsRef<Path>) -> Option<String> {
+        self.read_to_string(path.as_ref()).ok()
+    }
+
+    /// The node stored at `path` itself, without following it
+    pub fn node(&self, path: impl AsRef<Path>) -> Option<Node> {
+        self.nodes.lock().unwrap().get(&normalize(path.as_ref())).cloned()
+    }
+
+    fn write_file(&self, path: &Path, contents: &str, privileged: bool) -> io::Result<()> {
+        let mut nodes = self.nodes.lock().unwrap();
+        let resolved = Self::resolve(&nodes, path).ok_or_else(|| not_found(path))?;
+        if !matches!(resolved.parent().and_then(|p| nodes.get(p)), Some(Node::Dir)) {
+            return Err(not_found(path));
+        }
+        if !privileged && self.protected.lock().unwrap().contains(&resolved) {
+            return Err(io::Error::new(io::ErrorKind::PermissionDenied, format!("{}: permission denied", path.display())));
+        }
+        let owner_mode = match nodes.get(&resolved) {
+            Some(Node::Dir) => return Err(io::Error::other("is a directory")),
+            Some(Node::File { owner_mode, .. }) => *owner_mode,
+            _ => (0, 0, 0o644),
+        };
+        nodes.insert(resolved, Node::File { contents: contents.to_string(), owner_mode });
+        Ok(())
+    }
+}
+
+impl Filesystem for MemoryFs {
+    fn exists(&self, path: &Path) -> bool {
+        let nodes = self.nodes.lock().unwrap();
+        Self::resolve(&nodes, path).is_some_and(|p| nodes.contains_key(&p))
+    }
+
+    fn is_symlink(&self, path: &Path) -> bool {
+        let nodes = self.nodes.lock().unwrap();
+        Self::locate(&nodes, path).is_some_and(|p| matches!(nodes.get(&p), Some(Node::Symlink(_))))
+    }
+
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        let nodes = self.nodes.lock().unwrap();
+        match Self::resolve(&nodes, path).and_then(|p| nodes.get(&p)) {
+            Some(Node::File { contents, .. }) => Ok(contents.clone()),
+            Some(_) => Err(io::Error::other("is a directory")),
+            None => Err(not_found(path)),
+        }
+    }
+
+    fn write(&self, path: &Path, contents: &str) -> io::Result<()> {
+        self.write_file(path, contents, false)
+    }
+
+    fn create_dir_all(&self, path: &Path) -> io::Result<()> {
+        let mut nodes = self.nodes.lock().unwrap();
+        let resolved = Self::resolve(&nodes, path).ok_or_else(|| not_found(path))?;
+        let mut ancestors: Vec<_> = resolved.ancestors().map(Path::to_path_buf).collect();
+        ancestors.reverse();
+        for dir in ancestors {
+            match nodes.get(&dir) {
+                Some(Node::Dir) => {}
+                Some(_) => return Err(io::Error::new(io::ErrorKind::AlreadyExists, format!("{} is not a directory", dir.display()))),
+                None => {
+                    nodes.insert(dir, Node::Dir);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn remove_file(&self, path: &Path) -> io::Result<()> {
+        let mut nodes = self.nodes.lock().unwrap();
+        let located = Self::locate(&nodes, path).ok_or_else(|| not_found(path))?;
+        match nodes.get(&located) {
+            Some(Node::Dir) => Err(io::Error::other("is a directory")),
+            Some(_) => {
+                nodes.remove(&located);
+                Ok(())
+            }
+            None => Err(not_found(path)),
+        }
+    }
+
+    fn read_link(&self, path: &Path) -> io::Result<PathBuf> {
+        let nodes = self.nodes.lock().unwrap();
+        match Self::locate(&nodes, path).and_then(|p| nodes.get(&p)) {
+            Some(Node::Symlink(target)) => Ok(target.clone()),
+            Some(_) => Err(io::Error::new(io::ErrorKind::InvalidInput, "not a symlink")),
+            None => Err(not_found(path)),
+        }
+    }
+
+    fn symlink(&self, source: &Path, link: &Path) -> io::Result<()> {
+        let mut nodes = self.nodes.lock().unwrap();
+        let located = Self::locate(&nodes, link).ok_or_else(|| not_found(link))?;
+        if nodes.contains_key(&located) {
+            return Err(io::Error::new(io::ErrorKind::AlreadyExists, format!("{}: file exists", link.display())));
+        }
+        if !matches!(located.parent().and_then(|p| nodes.get(p)), Some(Node::Dir)) {
+            return Err(not_found(link));
+        }
+        nodes.insert(located, Node::Symlink(source.to_path_buf()));
+        Ok(())
+    }
+
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        let nodes = self.nodes.lock().unwrap();
+        Self::resolve(&nodes, path)
+            .filter(|p| nodes.contains_key(p))
+            .ok_or_else(|| not_found(path))
+    }
+
+    fn owner_mode(&self, path: &Path) -> Option<(u32, u32, u32)> {
+        let nodes = self.nodes.lock().unwrap();
+        match nodes.get(&Self::resolve(&nodes, path)?)? {
+            Node::File { owner_mode, .. } => Some(*owner_mode),
+            _ => None,
+        }
+    }
+}
+
+impl Privileged for MemoryFs {
+    fn write(&self, path: &Path, contents: &str) -> Result<(), String> {
+        self.write_file(path, contents, true).map_err(|e| e.to_string())
+    }
+
+    fn copy_owner_mode(&self, reference: &str, path: &str) -> Result<(), String> {
+        let (uid, gid, mode) = self.owner_mode(Path::new(reference)).ok_or_else(|| format!("{}: not found", reference))?;
+        self.set_owner_mode(path, uid, gid, mode);
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FakeMount {
+    pub source: String,
+    pub target: String,
+    pub read_only: bool,
+}
+
+/// Mount table over a `MemoryFs`; both ends of a bind must exist
+pub struct FakeMounts {
+    fs: Arc<MemoryFs>,
+    table: Mutex<Vec<FakeMount>>,
+    /// Make `remount_read_only` fail, as on filesystems that refuse it
+    pub fail_remount: Mutex<bool>,
+}
+
+impl FakeMounts {
+    pub fn new(fs: Arc<MemoryFs>) -> Self {
+        Self { fs, table: Mutex::new(vec![]), fail_remount: Mutex::new(false) }
+    }
+
+    pub fn table(&self) -> Vec<FakeMount> {
+        self.table.lock().unwrap().clone()
+    }
+}
+
+impl Mounts for FakeMounts {
+    fn bind(&self, source: &str, target: &str) -> Result<(), String> {
+        for path in [source, target] {
+            if !self.fs.exists(Path::new(path)) {
+                return Err(format!("mount: {}: special device {} does not exist.", target, path));
+            }
+        }
+        self.table.lock().unwrap().push(FakeMount { source: source.to_string(), target: target.to_string(), read_only: false });
+        Ok(())
+    }
+
+    fn remount_read_only(&self, target: &str) -> Result<(), String> {
+        if *self.fail_remount.lock().unwrap() {
+            return Err(format!("mount: {}: cannot remount read-only", target));
+        }
+        let mut table = self.table.lock().unwrap();
+        match table.iter_mut().rev().find(|m| m.target == target) {
+            Some(mount) => {
+                mount.read_only = true;
+                Ok(())
+            }
+            None => Err(format!("mount: {}: not mounted", target)),
+        }
+    }
+
+    fn unmount(&self, target: &str) -> Result<(), String> {
+        let mut table = self.table.lock().unwrap();
+        match table.iter().rposition(|m| m.target == target) {
+            Some(index) => {
+                table.remove(index);
+                Ok(())
+            }
+            None => Err(format!("umount: {}: not mounted.", target)),
+        }
+    }
+
+    fn is_mounted(&self, target: &str) -> bool {
+        self.table.lock().unwrap().iter().any(|m| m.target == target)
+    }
+}
+
+/// A `MemoryFs` with a mount table, wired up as a `Backend`
+pub struct FakeSystem {
+    pub fs: Arc<MemoryFs>,
+    pub mounts: Arc<FakeMounts>,
+}
+
+impl Default for FakeSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FakeSystem {
+    pub fn new() -> Self {
+        let fs = Arc::new(MemoryFs::new());
+        let mounts = Arc::new(FakeMounts::new(fs.clone()));
+        Self { fs, mounts }
+    }
+
+    pub fn backend(&self) -> Backend {
+        Backend { fs: self.fs.clone(), mounts: self.mounts.clone(), privileged: self.fs.clone() }
+    }
+}
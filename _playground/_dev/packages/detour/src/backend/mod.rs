@@ -0,0 +1,167 @@
+// System access behind traits - the managers never touch std::fs, mount or sudo
+// directly, so their logic runs against the in-memory fakes in `fake`
+
+pub mod fake;
+
+use std::fs;
+use std::io;
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Arc;
+
+/// Unprivileged file access
+pub trait Filesystem: Send + Sync {
+    /// Follows symlinks, like `Path::exists`
+    fn exists(&self, path: &Path) -> bool;
+    /// Does not follow the final component
+    fn is_symlink(&self, path: &Path) -> bool;
+    fn read_to_string(&self, path: &Path) -> io::Result<String>;
+    fn write(&self, path: &Path, contents: &str) -> io::Result<()>;
+    fn create_dir_all(&self, path: &Path) -> io::Result<()>;
+    fn remove_file(&self, path: &Path) -> io::Result<()>;
+    fn read_link(&self, path: &Path) -> io::Result<PathBuf>;
+    /// Create `link` pointing at `source`
+    fn symlink(&self, source: &Path, link: &Path) -> io::Result<()>;
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf>;
+    /// (uid, gid, mode) of the file `path` resolves to
+    fn owner_mode(&self, path: &Path) -> Option<(u32, u32, u32)>;
+}
+
+/// Bind mounts
+pub trait Mounts: Send + Sync {
+    fn bind(&self, source: &str, target: &str) -> Result<(), String>;
+    fn remount_read_only(&self, target: &str) -> Result<(), String>;
+    fn unmount(&self, target: &str) -> Result<(), String>;
+    fn is_mounted(&self, target: &str) -> bool;
+}
+
+/// Writes that need root
+pub trait Privileged: Send + Sync {
+    fn write(&self, path: &Path, contents: &str) -> Result<(), String>;
+    /// Give `path` the owner, group and mode of `reference`
+    fn copy_owner_mode(&self, reference: &str, path: &str) -> Result<(), String>;
+}
+
+/// The set of backends a manager runs against
+#[derive(Clone)]
+pub struct Backend {
+    pub fs: Arc<dyn Filesystem>,
+    pub mounts: Arc<dyn Mounts>,
+    pub privileged: Arc<dyn Privileged>,
+}
+
+impl Backend {
+    pub fn system() -> Self {
+        Self { fs: Arc::new(SystemFs), mounts: Arc::new(SystemMounts), privileged: Arc::new(Sudo) }
+    }
+}
+
+impl Default for Backend {
+    fn default() -> Self {
+        Self::system()
+    }
+}
+
+pub struct SystemFs;
+
+impl Filesystem for SystemFs {
+    fn exists(&self, path: &Path) -> bool {
+        path.exists()
+    }
+
+    fn is_symlink(&self, path: &Path) -> bool {
+        path.is_symlink()
+    }
+
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        fs::read_to_string(path)
+    }
+
+    fn write(&self, path: &Path, contents: &str) -> io::Result<()> {
+        fs::write(path, contents)
+    }
+
+    fn create_dir_all(&self, path: &Path) -> io::Result<()> {
+        fs::create_dir_all(path)
+    }
+
+    fn remove_file(&self, path: &Path) -> io::Result<()> {
+        fs::remove_file(path)
+    }
+
+    fn read_link(&self, path: &Path) -> io::Result<PathBuf> {
+        fs::read_link(path)
+    }
+
+    fn symlink(&self, source: &Path, link: &Path) -> io::Result<()> {
+        std::os::unix::fs::symlink(source, link)
+    }
+
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        path.canonicalize()
+    }
+
+    fn owner_mode(&self, path: &Path) -> Option<(u32, u32, u32)> {
+        let meta = fs::metadata(path).ok()?;
+        Some((meta.uid(), meta.gid(), meta.mode() & 0o7777))
+    }
+}
+
+fn sudo(args: &[&str]) -> Result<(), String> {
+    let output = Command::new("sudo")
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to execute {}: {}", args[0], e))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).to_string())
+    }
+}
+
+pub struct SystemMounts;
+
+impl Mounts for SystemMounts {
+    fn bind(&self, source: &str, target: &str) -> Result<(), String> {
+        sudo(&["mount", "--bind", source, target])
+    }
+
+    fn remount_read_only(&self, target: &str) -> Result<(), String> {
+        // A bind mount inherits the source's flags; read-only needs a remount
+        sudo(&["mount", "-o", "remount,bind,ro", target])
+    }
+
+    fn unmount(&self, target: &str) -> Result<(), String> {
+        sudo(&["umount", target])
+    }
+
+    fn is_mounted(&self, target: &str) -> bool {
+        Command::new("mount")
+            .output()
+            .map(|output| String::from_utf8_lossy(&output.stdout).contains(target))
+            .unwrap_or(false)
+    }
+}
+
+pub struct Sudo;
+
+impl Privileged for Sudo {
+    fn write(&self, path: &Path, contents: &str) -> Result<(), String> {
+        // Write to a temp file, then use sudo to copy it over the target
+        let temp_file = format!("/tmp/detour_include_{}", std::process::id());
+        fs::write(&temp_file, contents)
+            .map_err(|e| format!("Failed to write temp file: {}", e))?;
+        let result = sudo(&["cp", &temp_file, &path.to_string_lossy()]);
+        let _ = fs::remove_file(&temp_file);
+        result.map_err(|e| format!("Failed to write file with sudo: {}", e))
+    }
+
+    fn copy_owner_mode(&self, reference: &str, path: &str) -> Result<(), String> {
+        let reference = format!("--reference={}", reference);
+        for tool in ["chown", "chmod"] {
+            sudo(&[tool, &reference, path]).map_err(|e| format!("{} failed: {}", tool, e.trim()))?;
+        }
+        Ok(())
+    }
+}
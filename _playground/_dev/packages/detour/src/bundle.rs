@@ -0,0 +1,467 @@
+// Portable bundles - config plus every referenced custom/include/source file
+
+use crate::config::{DetourConfig, DetourEntry, EntryKind, InjectionEntry, MirrorEntry, ServiceEntry};
+use crate::operations::{config_ops, file_ops};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+pub const MANIFEST_VERSION: u32 = 1;
+
+const CONFIG_FILE: &str = "config.yaml";
+const MANIFEST_FILE: &str = "manifest.json";
+const FILES_DIR: &str = "files";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundledFile {
+    /// Absolute path on the exporting host
+    pub path: String,
+    pub kind: EntryKind,
+    pub entry_id: String,
+    pub sha256: String,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Manifest {
+    pub version: u32,
+    pub created_at: String,
+    pub hostname: String,
+    pub files: Vec<BundledFile>,
+    /// Referenced paths that did not exist at export time
+    #[serde(default)]
+    pub missing: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct ExportSummary {
+    pub files: usize,
+    pub bytes: u64,
+    pub missing: Vec<String>,
+}
+
+/// Scratch directory removed on drop
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new(purpose: &str) -> Result<Self, String> {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.subsec_nanos())
+            .unwrap_or(0);
+        let path = std::env::temp_dir().join(format!("detour-{}-{}-{}", purpose, std::process::id(), nanos));
+        fs::create_dir_all(&path).map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+        Ok(Self(path))
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Location of an absolute path inside the bundle's files/ directory
+fn bundle_path(root: &Path, path: &str) -> PathBuf {
+    root.join(FILES_DIR).join(path.trim_start_matches('/'))
+}
+
+/// Regular files under `path` (the path itself when it is a file)
+fn collect_files(path: &Path, out: &mut Vec<PathBuf>) {
+    if path.is_dir() {
+        if let Ok(entries) = fs::read_dir(path) {
+            for entry in entries.flatten() {
+                collect_files(&entry.path(), out);
+            }
+        }
+    } else if path.is_file() {
+        out.push(path.to_path_buf());
+    }
+}
+
+fn run_tar(args: &[&str]) -> Result<(), String> {
+    let output = Command::new("tar")
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to execute tar: {}", e))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!("tar failed: {}", String::from_utf8_lossy(&output.stderr).trim()))
+    }
+}
+
+/// Write `config_path` and every file it references to a gzipped tarball
+pub fn export(config_path: &str, out: &Path) -> Result<ExportSummary, String> {
+    let mut config = DetourConfig::parse(config_path)?;
+    config.ensure_ids();
+    let staging = TempDir::new("export")?;
+
+    let mut referenced: Vec<(EntryKind, &str, &str)> = vec![];
+    referenced.extend(config.detours.iter().map(|e| (EntryKind::Detour, e.id.as_str(), e.custom.as_str())));
+    referenced.extend(config.injections.iter().map(|e| (EntryKind::Injection, e.id.as_str(), e.include_file.as_str())));
+    referenced.extend(config.mirrors.iter().map(|e| (EntryKind::Mirror, e.id.as_str(), e.source.as_str())));
+
+    let mut manifest = Manifest {
+        version: MANIFEST_VERSION,
+        created_at: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+        hostname: crate::template::hostname(),
+        files: vec![],
+        missing: vec![],
+    };
+
+    for (kind, id, path) in referenced {
+        let mut files = vec![];
+        collect_files(Path::new(path), &mut files);
+        if files.is_empty() {
+            manifest.missing.push(path.to_string());
+            continue;
+        }
+        for file in files {
+            let file_str = file.to_string_lossy().to_string();
+            // Several entries may share a file
+            if manifest.files.iter().any(|f| f.path == file_str) {
+                continue;
+            }
+            let dest = bundle_path(&staging.0, &file_str);
+            file_ops::ensure_parent_dirs(&dest)?;
+            let size = fs::copy(&file, &dest).map_err(|e| format!("Failed to copy {}: {}", file_str, e))?;
+            let sha256 = file_ops::sha256(&file).ok_or_else(|| format!("Failed to checksum {}", file_str))?;
+            manifest.files.push(BundledFile { path: file_str, kind, entry_id: id.to_string(), sha256, size });
+        }
+    }
+
+    let yaml = serde_yaml::to_string(&config).map_err(|e| format!("Failed to serialize config: {}", e))?;
+    fs::write(staging.0.join(CONFIG_FILE), yaml).map_err(|e| format!("Failed to write config: {}", e))?;
+    let json = serde_json::to_string_pretty(&manifest).map_err(|e| format!("Failed to serialize manifest: {}", e))?;
+    fs::write(staging.0.join(MANIFEST_FILE), json).map_err(|e| format!("Failed to write manifest: {}", e))?;
+    fs::create_dir_all(staging.0.join(FILES_DIR)).map_err(|e| format!("Failed to create files dir: {}", e))?;
+
+    let out_str = out.to_string_lossy();
+    let staging_str = staging.0.to_string_lossy();
+    run_tar(&["-czf", &out_str, "-C", &staging_str, CONFIG_FILE, MANIFEST_FILE, FILES_DIR])?;
+
+    Ok(ExportSummary {
+        files: manifest.files.len(),
+        bytes: manifest.files.iter().map(|f| f.size).sum(),
+        missing: manifest.missing,
+    })
+}
+
+/// Path prefix rewrite applied on import (`--map FROM=TO`)
+#[derive(Debug, Clone)]
+pub struct Remap {
+    pub from: String,
+    pub to: String,
+}
+
+impl Remap {
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let (from, to) = spec.split_once('=')
+            .ok_or_else(|| format!("Invalid mapping '{}', expected FROM=TO", spec))?;
+        if from.is_empty() {
+            return Err(format!("Invalid mapping '{}', FROM is empty", spec));
+        }
+        Ok(Self { from: from.trim_end_matches('/').to_string(), to: to.trim_end_matches('/').to_string() })
+    }
+}
+
+/// Apply the first remap whose prefix matches on a path component boundary
+pub fn remap_path(path: &str, remaps: &[Remap]) -> String {
+    for remap in remaps {
+        if let Some(rest) = path.strip_prefix(&remap.from) {
+            if rest.is_empty() || rest.starts_with('/') {
+                return format!("{}{}", remap.to, rest);
+            }
+        }
+    }
+    path.to_string()
+}
+
+/// Config entry kinds that can be merged from a bundle
+trait Portable: Clone {
+    const KIND: EntryKind;
+    fn id(&self) -> &str;
+    /// What must be unique among entries of this kind (e.g. a detour's original)
+    fn slot(&self) -> String;
+    /// Every path/field that matters when comparing two entries
+    fn signature(&self) -> String;
+    fn remap(&mut self, remaps: &[Remap]);
+    fn list(config: &DetourConfig) -> &Vec<Self>;
+    fn list_mut(config: &mut DetourConfig) -> &mut Vec<Self>;
+}
+
+impl Portable for DetourEntry {
+    const KIND: EntryKind = EntryKind::Detour;
+    fn id(&self) -> &str { &self.id }
+    fn slot(&self) -> String { self.original.clone() }
+    fn signature(&self) -> String { format!("{} ← {}", self.original, self.custom) }
+    fn remap(&mut self, remaps: &[Remap]) {
+        self.original = remap_path(&self.original, remaps);
+        self.custom = remap_path(&self.custom, remaps);
+    }
+    fn list(config: &DetourConfig) -> &Vec<Self> { &config.detours }
+    fn list_mut(config: &mut DetourConfig) -> &mut Vec<Self> { &mut config.detours }
+}
+
+impl Portable for InjectionEntry {
+    const KIND: EntryKind = EntryKind::Injection;
+    fn id(&self) -> &str { &self.id }
+    fn slot(&self) -> String { format!("{} ← {}", self.target, self.include_file) }
+    fn signature(&self) -> String { self.slot() }
+    fn remap(&mut self, remaps: &[Remap]) {
+        self.target = remap_path(&self.target, remaps);
+        self.include_file = remap_path(&self.include_file, remaps);
+    }
+    fn list(config: &DetourConfig) -> &Vec<Self> { &config.injections }
+    fn list_mut(config: &mut DetourConfig) -> &mut Vec<Self> { &mut config.injections }
+}
+
+impl Portable for MirrorEntry {
+    const KIND: EntryKind = EntryKind::Mirror;
+    fn id(&self) -> &str { &self.id }
+    fn slot(&self) -> String { self.target.clone() }
+    fn signature(&self) -> String { format!("{} → {}", self.source, self.target) }
+    fn remap(&mut self, remaps: &[Remap]) {
+        self.source = remap_path(&self.source, remaps);
+        self.target = remap_path(&self.target, remaps);
+    }
+    fn list(config: &DetourConfig) -> &Vec<Self> { &config.mirrors }
+    fn list_mut(config: &mut DetourConfig) -> &mut Vec<Self> { &mut config.mirrors }
+}
+
+impl Portable for ServiceEntry {
+    const KIND: EntryKind = EntryKind::Service;
+    fn id(&self) -> &str { &self.id }
+    fn slot(&self) -> String { self.name.clone() }
+    fn signature(&self) -> String { format!("{} ({})", self.name, self.action) }
+    fn remap(&mut self, _remaps: &[Remap]) {}
+    fn list(config: &DetourConfig) -> &Vec<Self> { &config.services }
+    fn list_mut(config: &mut DetourConfig) -> &mut Vec<Self> { &mut config.services }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Action {
+    Create,
+    /// Already present with the same content
+    Unchanged,
+    /// Present with different content; `local_id` is the entry that would be replaced
+    Conflict { reason: String, local_id: Option<String> },
+}
+
+impl Action {
+    fn symbol(&self) -> &'static str {
+        match self {
+            Action::Create => "+",
+            Action::Unchanged => "=",
+            Action::Conflict { .. } => "!",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PlannedFile {
+    pub source: PathBuf,
+    pub dest: String,
+    pub action: Action,
+}
+
+#[derive(Debug, Clone)]
+pub struct PlannedEntry {
+    pub kind: EntryKind,
+    pub id: String,
+    pub label: String,
+    pub action: Action,
+}
+
+/// Everything an import would do, computed without touching the system
+pub struct ImportPlan {
+    /// Where `files` are read from - removed when the plan is dropped
+    _extracted: TempDir,
+    pub manifest: Manifest,
+    /// Bundle config with remaps applied
+    pub config: DetourConfig,
+    pub files: Vec<PlannedFile>,
+    pub entries: Vec<PlannedEntry>,
+    pub checksum_errors: Vec<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct ImportSummary {
+    pub files_written: usize,
+    pub entries_added: usize,
+    pub skipped: Vec<String>,
+}
+
+fn plan_entries<T: Portable>(bundle: &DetourConfig, local: &DetourConfig, out: &mut Vec<PlannedEntry>) {
+    for entry in T::list(bundle) {
+        let existing = T::list(local).iter()
+            .find(|e| e.id() == entry.id())
+            .or_else(|| T::list(local).iter().find(|e| e.slot() == entry.slot()));
+        let action = match existing {
+            None => Action::Create,
+            Some(e) if e.signature() == entry.signature() => Action::Unchanged,
+            Some(e) if e.id() == entry.id() => Action::Conflict {
+                reason: format!("id already used by {}", e.signature()),
+                local_id: Some(e.id().to_string()),
+            },
+            Some(e) => Action::Conflict {
+                reason: format!("already configured as {} ({})", e.id(), e.signature()),
+                local_id: Some(e.id().to_string()),
+            },
+        };
+        out.push(PlannedEntry { kind: T::KIND, id: entry.id().to_string(), label: entry.signature(), action });
+    }
+}
+
+fn apply_entries<T: Portable>(bundle: &DetourConfig, local: &mut DetourConfig, plan: &[PlannedEntry], overwrite: bool, summary: &mut ImportSummary) {
+    for entry in T::list(bundle) {
+        let Some(planned) = plan.iter().find(|p| p.kind == T::KIND && p.id == entry.id()) else { continue };
+        match &planned.action {
+            Action::Create => {
+                T::list_mut(local).push(entry.clone());
+                summary.entries_added += 1;
+            }
+            Action::Unchanged => {}
+            Action::Conflict { local_id, .. } if overwrite => {
+                let list = T::list_mut(local);
+                match list.iter().position(|e| Some(e.id()) == local_id.as_deref()) {
+                    Some(i) => list[i] = entry.clone(),
+                    None => list.push(entry.clone()),
+                }
+                summary.entries_added += 1;
+            }
+            Action::Conflict { .. } => summary.skipped.push(format!("{:?} {}", T::KIND, planned.label)),
+        }
+    }
+}
+
+impl ImportPlan {
+    /// Extract and verify `bundle`, then diff it against the local config and filesystem
+    pub fn prepare(bundle: &Path, remaps: &[Remap], local_config_path: &str) -> Result<Self, String> {
+        let extracted = TempDir::new("import")?;
+        run_tar(&["-xzf", &bundle.to_string_lossy(), "-C", &extracted.0.to_string_lossy()])?;
+
+        let manifest: Manifest = serde_json::from_str(
+            &fs::read_to_string(extracted.0.join(MANIFEST_FILE)).map_err(|e| format!("Bundle has no manifest: {}", e))?,
+        ).map_err(|e| format!("Invalid manifest: {}", e))?;
+        if manifest.version > MANIFEST_VERSION {
+            return Err(format!("Bundle manifest version {} is newer than supported ({})", manifest.version, MANIFEST_VERSION));
+        }
+        let mut config = DetourConfig::parse(&extracted.0.join(CONFIG_FILE).to_string_lossy())?;
+        config.ensure_ids();
+        for e in &mut config.detours { e.remap(remaps); }
+        for e in &mut config.injections { e.remap(remaps); }
+        for e in &mut config.mirrors { e.remap(remaps); }
+
+        let mut checksum_errors = vec![];
+        let mut files = vec![];
+        for file in &manifest.files {
+            let source = bundle_path(&extracted.0, &file.path);
+            let sha256 = file_ops::sha256(&source);
+            if sha256.as_deref() != Some(file.sha256.as_str()) {
+                checksum_errors.push(file.path.clone());
+                continue;
+            }
+            let dest = remap_path(&file.path, remaps);
+            let action = match file_ops::sha256(Path::new(&dest)) {
+                None if !Path::new(&dest).exists() => Action::Create,
+                Some(existing) if existing == file.sha256 => Action::Unchanged,
+                _ => Action::Conflict { reason: "file exists with different content".to_string(), local_id: None },
+            };
+            files.push(PlannedFile { source, dest, action });
+        }
+
+        let local = config_ops::load_config(local_config_path);
+        let mut entries = vec![];
+        plan_entries::<DetourEntry>(&config, &local, &mut entries);
+        plan_entries::<InjectionEntry>(&config, &local, &mut entries);
+        plan_entries::<MirrorEntry>(&config, &local, &mut entries);
+        plan_entries::<ServiceEntry>(&config, &local, &mut entries);
+
+        Ok(Self { _extracted: extracted, manifest, config, files, entries, checksum_errors })
+    }
+
+    pub fn has_conflicts(&self) -> bool {
+        self.files.iter().map(|f| &f.action)
+            .chain(self.entries.iter().map(|e| &e.action))
+            .any(|a| matches!(a, Action::Conflict { .. }))
+    }
+
+    /// Human-readable plan, with a unified diff for every conflicting file
+    pub fn preview(&self) -> String {
+        let mut out = vec![format!("Bundle from {} created {} ({} files)",
+            self.manifest.hostname, self.manifest.created_at, self.manifest.files.len())];
+        if !self.checksum_errors.is_empty() {
+            out.push("Checksum mismatch (bundle corrupted):".to_string());
+            out.extend(self.checksum_errors.iter().map(|p| format!("  ✗ {}", p)));
+        }
+
+        out.push(String::new());
+        out.push("Files:".to_string());
+        for file in &self.files {
+            let reason = match &file.action {
+                Action::Conflict { reason, .. } => format!("  ({})", reason),
+                _ => String::new(),
+            };
+            out.push(format!("  {} {}{}", file.action.symbol(), file.dest, reason));
+        }
+
+        out.push(String::new());
+        out.push("Config entries:".to_string());
+        for entry in &self.entries {
+            let reason = match &entry.action {
+                Action::Conflict { reason, .. } => format!("  ({})", reason),
+                _ => String::new(),
+            };
+            out.push(format!("  {} {:?} {} {}{}", entry.action.symbol(), entry.kind, entry.id, entry.label, reason));
+        }
+
+        for file in self.files.iter().filter(|f| matches!(f.action, Action::Conflict { .. })) {
+            out.push(String::new());
+            let diff = Command::new("diff")
+                .arg("-u")
+                .arg("--label").arg(format!("{} (local)", file.dest))
+                .arg("--label").arg(format!("{} (bundle)", file.dest))
+                .arg(&file.dest)
+                .arg(&file.source)
+                .output()
+                .map(|o| String::from_utf8_lossy(&o.stdout).trim_end().to_string())
+                .unwrap_or_else(|e| format!("(diff unavailable: {})", e));
+            out.push(diff);
+        }
+        out.join("\n")
+    }
+
+    /// Write files and merge entries. Conflicts are skipped unless `overwrite`.
+    pub fn apply(&self, config_path: &str, overwrite: bool) -> Result<ImportSummary, String> {
+        if !self.checksum_errors.is_empty() {
+            return Err(format!("Refusing to import: {} files failed checksum verification", self.checksum_errors.len()));
+        }
+        let mut summary = ImportSummary::default();
+
+        for file in &self.files {
+            match file.action {
+                Action::Unchanged => continue,
+                Action::Conflict { .. } if !overwrite => {
+                    summary.skipped.push(file.dest.clone());
+                    continue;
+                }
+                _ => {}
+            }
+            file_ops::ensure_parent_dirs(Path::new(&file.dest))?;
+            fs::copy(&file.source, &file.dest).map_err(|e| format!("Failed to write {}: {}", file.dest, e))?;
+            summary.files_written += 1;
+        }
+
+        config_ops::with_config_mut(config_path, |local| {
+            apply_entries::<DetourEntry>(&self.config, local, &self.entries, overwrite, &mut summary);
+            apply_entries::<InjectionEntry>(&self.config, local, &self.entries, overwrite, &mut summary);
+            apply_entries::<MirrorEntry>(&self.config, local, &self.entries, overwrite, &mut summary);
+            apply_entries::<ServiceEntry>(&self.config, local, &self.entries, overwrite, &mut summary);
+            Ok(())
+        })?;
+        Ok(summary)
+    }
+}
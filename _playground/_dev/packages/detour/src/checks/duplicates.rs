@@ -0,0 +1,61 @@
+// Entries that collide: shared detour originals and reused ids
+
+use super::{Check, Finding, Fix, Severity};
+use crate::config::{DetourConfig, DetourEntry};
+use crate::status::EntryKind;
+use std::collections::{HashMap, HashSet};
+
+/// Two detours bind-mounting over the same original shadow each other
+pub struct DuplicateOriginals;
+
+impl Check for DuplicateOriginals {
+    fn name(&self) -> &'static str {
+        "duplicate-originals"
+    }
+
+    fn run(&self, config: &DetourConfig) -> Vec<Finding> {
+        let mut findings = vec![];
+        let mut first_seen: HashMap<&str, &DetourEntry> = HashMap::new();
+
+        for entry in &config.detours {
+            match first_seen.get(entry.original.as_str()) {
+                Some(first) => {
+                    let same_custom = first.custom == entry.custom;
+                    findings.push(Finding::new(
+                            self.name(),
+                            if same_custom { Severity::Warning } else { Severity::Error },
+                            format!("{} is also detoured by {}", entry.original, first.id),
+                        )
+                        .entry(EntryKind::Detour, &entry.id, format!("{} ← {}", entry.original, entry.custom))
+                        .fix(Fix::RemoveEntry { kind: EntryKind::Detour, id: entry.id.clone() }));
+                }
+                None => {
+                    first_seen.insert(&entry.original, entry);
+                }
+            }
+        }
+
+        findings
+    }
+}
+
+/// Ids address entries from the TUI and CLI, so they must be unique
+pub struct DuplicateIds;
+
+impl Check for DuplicateIds {
+    fn name(&self) -> &'static str {
+        "duplicate-ids"
+    }
+
+    fn run(&self, config: &DetourConfig) -> Vec<Finding> {
+        let mut seen = HashSet::new();
+        config.all_ids().into_iter()
+            .filter(|(_, id)| !seen.insert(*id))
+            .map(|(kind, id)| {
+                Finding::new(self.name(), Severity::Error, format!("Id {} is used by more than one entry", id))
+                    .entry(kind, id, format!("{:?} {}", kind, id))
+                    .fix(Fix::RegenerateId { id: id.to_string() })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,45 @@
+// Injections should actually be present in their target files
+
+use super::{Check, Finding, Fix, Severity};
+use crate::config::DetourConfig;
+use crate::injection::InjectionManager;
+use crate::status::EntryKind;
+use std::path::Path;
+
+pub struct InjectionPresent;
+
+impl Check for InjectionPresent {
+    fn name(&self) -> &'static str {
+        "injection-present"
+    }
+
+    fn run(&self, config: &DetourConfig) -> Vec<Finding> {
+        let manager = InjectionManager::new();
+        let mut findings = vec![];
+
+        for entry in &config.injections {
+            let target = Path::new(&entry.target);
+            let include = Path::new(&entry.include_file);
+            // Missing files are reported by paths-exist
+            if !target.exists() || !include.exists() {
+                continue;
+            }
+            let injected = entry.injected_file();
+            if !manager.is_applied(target, Path::new(&injected)) {
+                let finding = Finding::new(self.name(), Severity::Warning, format!("Include not present in {}", entry.target))
+                    .entry(EntryKind::Injection, &entry.id, format!("{} ← {}", entry.target, entry.include_file));
+                // Templates need rendering first, which only activation does
+                findings.push(if entry.template {
+                    finding.suggest(format!("Activate {} to render and inject the template", entry.id))
+                } else {
+                    finding.fix(Fix::ApplyInjection {
+                        target: entry.target.clone(),
+                        include_file: injected,
+                    })
+                });
+            }
+        }
+
+        findings
+    }
+}
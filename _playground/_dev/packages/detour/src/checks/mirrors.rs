@@ -0,0 +1,106 @@
+// Mirror symlinks must point at their source and never form loops
+
+use super::{Check, Finding, Fix, Severity};
+use crate::config::DetourConfig;
+use crate::status::EntryKind;
+use std::fs;
+use std::path::Path;
+
+pub struct MirrorTarget;
+
+impl Check for MirrorTarget {
+    fn name(&self) -> &'static str {
+        "mirror-target"
+    }
+
+    fn run(&self, config: &DetourConfig) -> Vec<Finding> {
+        let mut findings = vec![];
+
+        for entry in &config.mirrors {
+            let label = format!("{} → {}", entry.source, entry.target);
+            let target = Path::new(&entry.target);
+            let relink = Fix::RelinkMirror { source: entry.source.clone(), target: entry.target.clone() };
+
+            if target.is_symlink() {
+                let points_to = fs::read_link(target).unwrap_or_default();
+                if points_to != Path::new(&entry.source) {
+                    findings.push(Finding::new(self.name(), Severity::Error,
+                            format!("{} points to {} instead of {}", entry.target, points_to.display(), entry.source))
+                        .entry(EntryKind::Mirror, &entry.id, label)
+                        .fix(relink));
+                }
+            } else if target.exists() {
+                findings.push(Finding::new(self.name(), Severity::Warning,
+                        format!("{} is a regular file, not a mirror", entry.target))
+                    .entry(EntryKind::Mirror, &entry.id, label)
+                    .suggest("Move the existing file aside, then apply the mirror"));
+            } else if Path::new(&entry.source).exists() {
+                findings.push(Finding::new(self.name(), Severity::Info, format!("{} is not linked", entry.target))
+                    .entry(EntryKind::Mirror, &entry.id, label)
+                    .fix(relink));
+            }
+        }
+
+        findings
+    }
+}
+
+pub struct CircularMirrors;
+
+impl CircularMirrors {
+    /// Whether `path` is `base` or lives underneath it
+    fn within(path: &str, base: &str) -> bool {
+        let path = path.trim_end_matches('/');
+        let base = base.trim_end_matches('/');
+        path == base || path.starts_with(&format!("{}/", base))
+    }
+}
+
+impl Check for CircularMirrors {
+    fn name(&self) -> &'static str {
+        "circular-mirrors"
+    }
+
+    fn run(&self, config: &DetourConfig) -> Vec<Finding> {
+        let mirrors = &config.mirrors;
+        let mut findings = vec![];
+
+        // Edge i -> j when mirror i's target feeds mirror j's source
+        let edges: Vec<Vec<usize>> = mirrors.iter().map(|from| {
+            mirrors.iter().enumerate()
+                .filter(|(_, to)| Self::within(&to.source, &from.target))
+                .map(|(j, _)| j)
+                .collect()
+        }).collect();
+
+        for (i, entry) in mirrors.iter().enumerate() {
+            let label = format!("{} → {}", entry.source, entry.target);
+            if Self::within(&entry.target, &entry.source) {
+                findings.push(Finding::new(self.name(), Severity::Error,
+                        format!("{} mirrors into itself", entry.source))
+                    .entry(EntryKind::Mirror, &entry.id, label)
+                    .fix(Fix::RemoveEntry { kind: EntryKind::Mirror, id: entry.id.clone() }));
+                continue;
+            }
+
+            // Depth-first walk looking for a path back to i
+            let mut stack = edges[i].clone();
+            let mut seen = vec![false; mirrors.len()];
+            while let Some(j) = stack.pop() {
+                if j == i {
+                    findings.push(Finding::new(self.name(), Severity::Error,
+                            "Mirror is part of a cycle".to_string())
+                        .entry(EntryKind::Mirror, &entry.id, label)
+                        .suggest("Remove one of the mirrors in the cycle"));
+                    break;
+                }
+                if !seen[j] {
+                    seen[j] = true;
+                    stack.extend(edges[j].iter().copied());
+                }
+            }
+        }
+
+        findings
+    }
+}
@@ -0,0 +1,292 @@
+// Validation framework - pluggable checks producing structured findings
+
+pub mod paths;
+pub mod duplicates;
+pub mod injections;
+pub mod mirrors;
+pub mod permissions;
+pub mod packages;
+
+use crate::config::DetourConfig;
+use crate::status::EntryKind;
+use serde::Serialize;
+use std::path::Path;
+use std::process::Command;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Severity::Info => "ℹ",
+            Severity::Warning => "⚠",
+            Severity::Error => "✗",
+        }
+    }
+}
+
+/// Which config entry a finding is about
+#[derive(Debug, Clone, Serialize)]
+pub struct EntryRef {
+    pub kind: EntryKind,
+    pub id: String,
+    pub label: String,
+}
+
+/// Automatic remediation for a finding
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum Fix {
+    /// Seed a missing file from `source` (or create it empty)
+    CreateFile { path: String, source: Option<String> },
+    ApplyInjection { target: String, include_file: String },
+    RelinkMirror { source: String, target: String },
+    RemoveEntry { kind: EntryKind, id: String },
+    /// Hand the file to `group` and make it group-readable
+    GrantRead { path: String, group: String },
+    /// chown/chmod `custom` to match `original`
+    MatchPermissions { original: String, custom: String },
+    /// Give every entry but the first sharing `id` a fresh one
+    RegenerateId { id: String },
+}
+
+impl Fix {
+    pub fn describe(&self) -> String {
+        match self {
+            Fix::CreateFile { path, source: Some(source) } => format!("Copy {} to {}", source, path),
+            Fix::CreateFile { path, source: None } => format!("Create empty {}", path),
+            Fix::ApplyInjection { target, .. } => format!("Inject include into {}", target),
+            Fix::RelinkMirror { source, target } => format!("Relink {} → {}", target, source),
+            Fix::RemoveEntry { kind, id } => format!("Remove {:?} {} from config", kind, id),
+            Fix::GrantRead { path, group } => format!("chgrp {} and chmod g+r {}", group, path),
+            Fix::MatchPermissions { original, custom } => format!("Copy owner and mode of {} to {}", original, custom),
+            Fix::RegenerateId { id } => format!("Assign new ids to duplicates of {}", id),
+        }
+    }
+
+    pub fn apply(&self, config_path: &str) -> Result<String, String> {
+        match self {
+            Fix::CreateFile { path, source: Some(source) } => {
+                crate::operations::file_ops::duplicate_file(Path::new(source), Path::new(path))?;
+                Ok(format!("Created {}", path))
+            }
+            Fix::CreateFile { path, source: None } => {
+                crate::operations::file_ops::write_file(Path::new(path), "")?;
+                Ok(format!("Created empty {}", path))
+            }
+            Fix::ApplyInjection { target, include_file } => {
+                crate::injection::InjectionManager::new()
+                    .apply(Path::new(target), Path::new(include_file))?;
+                Ok(format!("Injected into {}", target))
+            }
+            Fix::RelinkMirror { source, target } => {
+                crate::mirror::MirrorManager::new().apply_mirror(source, target)
+            }
+            Fix::RemoveEntry { kind, id } => {
+                crate::operations::config_ops::with_config_mut(config_path, |config| {
+                    match config.find(id) {
+                        Some((found, index)) if found == *kind => match found {
+                            EntryKind::Detour => { config.detours.remove(index); }
+                            EntryKind::Injection => { config.injections.remove(index); }
+                            EntryKind::Mirror => { config.mirrors.remove(index); }
+                            EntryKind::Service => { config.services.remove(index); }
+                        },
+                        _ => return Err(format!("{:?} {} no longer exists", kind, id)),
+                    }
+                    Ok(format!("Removed {:?} {}", kind, id))
+                })
+            }
+            Fix::GrantRead { path, group } => {
+                for args in [["chgrp", group.as_str()], ["chmod", "g+r"]] {
+                    let output = Command::new("sudo")
+                        .args(args)
+                        .arg(path)
+                        .output()
+                        .map_err(|e| format!("Failed to execute {}: {}", args[0], e))?;
+                    if !output.status.success() {
+                        return Err(format!("{} failed: {}", args[0], String::from_utf8_lossy(&output.stderr).trim()));
+                    }
+                }
+                Ok(format!("Made {} readable by group {}", path, group))
+            }
+            Fix::MatchPermissions { original, custom } => {
+                crate::manager::DetourManager::new().match_permissions(original, custom)?;
+                Ok(format!("Matched owner and mode of {}", custom))
+            }
+            Fix::RegenerateId { id } => {
+                crate::operations::config_ops::with_config_mut(config_path, |config| {
+                    let mut seen = false;
+                    let mut clear = |entry_id: &mut String| {
+                        if entry_id == id {
+                            if seen {
+                                // with_config_mut assigns a new id before saving
+                                entry_id.clear();
+                            }
+                            seen = true;
+                        }
+                    };
+                    config.detours.iter_mut().for_each(|e| clear(&mut e.id));
+                    config.injections.iter_mut().for_each(|e| clear(&mut e.id));
+                    config.mirrors.iter_mut().for_each(|e| clear(&mut e.id));
+                    config.services.iter_mut().for_each(|e| clear(&mut e.id));
+                    Ok(format!("Regenerated duplicate ids of {}", id))
+                })
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Finding {
+    pub check: &'static str,
+    pub severity: Severity,
+    pub entry: Option<EntryRef>,
+    pub message: String,
+    pub suggestion: Option<String>,
+    pub fix: Option<Fix>,
+}
+
+impl Finding {
+    pub fn new(check: &'static str, severity: Severity, message: impl Into<String>) -> Self {
+        Self {
+            check,
+            severity,
+            entry: None,
+            message: message.into(),
+            suggestion: None,
+            fix: None,
+        }
+    }
+
+    pub fn entry(mut self, kind: EntryKind, id: &str, label: impl Into<String>) -> Self {
+        self.entry = Some(EntryRef { kind, id: id.to_string(), label: label.into() });
+        self
+    }
+
+    pub fn suggest(mut self, suggestion: impl Into<String>) -> Self {
+        self.suggestion = Some(suggestion.into());
+        self
+    }
+
+    pub fn fix(mut self, fix: Fix) -> Self {
+        if self.suggestion.is_none() {
+            self.suggestion = Some(fix.describe());
+        }
+        self.fix = Some(fix);
+        self
+    }
+}
+
+/// A single validation rule run against the whole config
+pub trait Check {
+    fn name(&self) -> &'static str;
+    fn run(&self, config: &DetourConfig) -> Vec<Finding>;
+}
+
+/// Registered checks, in the order they are reported
+pub fn default_checks() -> Vec<Box<dyn Check>> {
+    vec![
+        Box::new(paths::PathsExist),
+        Box::new(duplicates::DuplicateIds),
+        Box::new(duplicates::DuplicateOriginals),
+        Box::new(injections::InjectionPresent),
+        Box::new(mirrors::MirrorTarget),
+        Box::new(mirrors::CircularMirrors),
+        Box::new(permissions::ServiceUserReadable),
+        Box::new(permissions::OwnerModeMatch),
+        Box::new(packages::ModifiedConffiles),
+    ]
+}
+
+/// Limits a run to one category or a set of its entries
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Scope {
+    pub kind: Option<EntryKind>,
+    /// Empty means every entry of `kind`
+    pub ids: Vec<String>,
+}
+
+impl Scope {
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    pub fn kind(kind: EntryKind) -> Self {
+        Self { kind: Some(kind), ids: vec![] }
+    }
+
+    pub fn entry(kind: EntryKind, id: &str) -> Self {
+        Self::entries(kind, vec![id.to_string()])
+    }
+
+    pub fn entries(kind: EntryKind, ids: Vec<String>) -> Self {
+        Self { kind: Some(kind), ids }
+    }
+
+    fn matches(&self, finding: &Finding) -> bool {
+        match (&finding.entry, self.kind) {
+            (_, None) => true,
+            (None, Some(_)) => false,
+            (Some(entry), Some(kind)) => entry.kind == kind && (self.ids.is_empty() || self.ids.contains(&entry.id)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckReport {
+    pub checks: Vec<&'static str>,
+    pub findings: Vec<Finding>,
+    pub duration_ms: f64,
+}
+
+impl CheckReport {
+    pub fn run(config: &DetourConfig, scope: &Scope) -> Self {
+        let start = std::time::Instant::now();
+        let checks = default_checks();
+        let mut findings: Vec<Finding> = checks.iter()
+            .flat_map(|check| check.run(config))
+            .filter(|finding| scope.matches(finding))
+            .collect();
+        // Most severe first, stable within a severity
+        findings.sort_by_key(|f| std::cmp::Reverse(f.severity));
+
+        Self {
+            checks: checks.iter().map(|c| c.name()).collect(),
+            findings,
+            duration_ms: start.elapsed().as_secs_f64() * 1000.0,
+        }
+    }
+
+    pub fn count(&self, severity: Severity) -> usize {
+        self.findings.iter().filter(|f| f.severity == severity).count()
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.count(Severity::Error) > 0
+    }
+
+    /// Plain-text listing for the CLI
+    pub fn to_text(&self) -> String {
+        let mut out = vec![];
+        for finding in &self.findings {
+            let entry = finding.entry.as_ref().map(|e| format!(" [{}]", e.label)).unwrap_or_default();
+            out.push(format!("{} {}:{} {}", finding.severity.icon(), finding.check, entry, finding.message));
+            if let Some(suggestion) = &finding.suggestion {
+                out.push(format!("    → {}", suggestion));
+            }
+        }
+        out.push(format!("{} errors, {} warnings, {} info ({} checks, {:.2}ms)",
+            self.count(Severity::Error),
+            self.count(Severity::Warning),
+            self.count(Severity::Info),
+            self.checks.len(),
+            self.duration_ms));
+        out.join("\n")
+    }
+}
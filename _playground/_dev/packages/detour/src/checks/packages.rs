@@ -0,0 +1,28 @@
+// Detoured conffiles edited in place since the package installed them
+
+use super::{Check, Finding, Severity};
+use crate::config::DetourConfig;
+use crate::status::EntryKind;
+
+/// Local edits to a conffile stay hidden under the detour and resurface on removal
+pub struct ModifiedConffiles;
+
+impl Check for ModifiedConffiles {
+    fn name(&self) -> &'static str {
+        "modified-conffile"
+    }
+
+    fn run(&self, config: &DetourConfig) -> Vec<Finding> {
+        let manager = crate::manager::DetourManager::new();
+        config.detours.iter()
+            // While mounted, `original` reads back the custom file
+            .filter(|d| !manager.is_active(&d.original))
+            .filter_map(|d| {
+                let conffile = crate::package::modified_conffile(&d.original)?;
+                Some(Finding::new(self.name(), Severity::Info,
+                        format!("{} differs from the md5 {} recorded in {}", d.original, conffile.package, crate::package::DPKG_STATUS))
+                    .entry(EntryKind::Detour, &d.id, format!("{} ← {}", d.original, d.custom)))
+            })
+            .collect()
+    }
+}
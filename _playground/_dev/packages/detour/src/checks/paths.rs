@@ -0,0 +1,58 @@
+// Every path referenced by the config must exist
+
+use super::{Check, Finding, Fix, Severity};
+use crate::config::DetourConfig;
+use crate::status::EntryKind;
+use std::path::Path;
+
+pub struct PathsExist;
+
+impl Check for PathsExist {
+    fn name(&self) -> &'static str {
+        "paths-exist"
+    }
+
+    fn run(&self, config: &DetourConfig) -> Vec<Finding> {
+        let mut findings = vec![];
+        let missing = |p: &str| !Path::new(p).exists();
+
+        for entry in &config.detours {
+            let label = format!("{} ← {}", entry.original, entry.custom);
+            if missing(&entry.original) {
+                findings.push(Finding::new(self.name(), Severity::Error, format!("Original file missing: {}", entry.original))
+                    .entry(EntryKind::Detour, &entry.id, label.clone())
+                    .suggest("Fix the original path or remove the detour"));
+            }
+            if missing(&entry.custom) {
+                let source = (!missing(&entry.original)).then(|| entry.original.clone());
+                findings.push(Finding::new(self.name(), Severity::Error, format!("Custom file missing: {}", entry.custom))
+                    .entry(EntryKind::Detour, &entry.id, label)
+                    .fix(Fix::CreateFile { path: entry.custom.clone(), source }));
+            }
+        }
+
+        for entry in &config.injections {
+            let label = format!("{} ← {}", entry.target, entry.include_file);
+            if missing(&entry.target) {
+                findings.push(Finding::new(self.name(), Severity::Error, format!("Target file missing: {}", entry.target))
+                    .entry(EntryKind::Injection, &entry.id, label.clone())
+                    .suggest("Fix the target path or remove the injection"));
+            }
+            if missing(&entry.include_file) {
+                findings.push(Finding::new(self.name(), Severity::Error, format!("Include file missing: {}", entry.include_file))
+                    .entry(EntryKind::Injection, &entry.id, label)
+                    .fix(Fix::CreateFile { path: entry.include_file.clone(), source: None }));
+            }
+        }
+
+        for entry in &config.mirrors {
+            if missing(&entry.source) {
+                findings.push(Finding::new(self.name(), Severity::Error, format!("Mirror source missing: {}", entry.source))
+                    .entry(EntryKind::Mirror, &entry.id, format!("{} → {}", entry.source, entry.target))
+                    .suggest("Fix the source path or remove the mirror"));
+            }
+        }
+
+        findings
+    }
+}
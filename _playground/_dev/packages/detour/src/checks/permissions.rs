@@ -0,0 +1,110 @@
+// Custom files must be readable by the services that consume them
+
+use super::{Check, Finding, Fix, Severity};
+use crate::config::DetourConfig;
+use crate::status::EntryKind;
+use std::collections::HashMap;
+use std::os::unix::fs::MetadataExt;
+use std::process::Command;
+
+pub struct ServiceUserReadable;
+
+struct ServiceUser {
+    name: String,
+    group: String,
+    uid: u32,
+    gids: Vec<u32>,
+}
+
+impl ServiceUserReadable {
+    /// User and group `unit` runs as, or None for root and units without a `User=`
+    fn service_user(unit: &str) -> Option<ServiceUser> {
+        let output = Command::new("systemctl")
+            .args(["show", "-p", "User", "-p", "Group", "--value", unit])
+            .output()
+            .ok()?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let mut lines = stdout.lines().map(str::trim);
+        let (user, group) = (lines.next().unwrap_or(""), lines.next().unwrap_or(""));
+        if user.is_empty() || user == "root" {
+            return None;
+        }
+        let id = |flag: &str| Command::new("id").arg(flag).arg(user).output().ok()
+            .filter(|o| o.status.success())
+            .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string());
+        let ids = |flag: &str| id(flag).unwrap_or_default().split_whitespace()
+            .filter_map(|n| n.parse::<u32>().ok())
+            .collect::<Vec<_>>();
+        let uid = *ids("-u").first()?;
+        let group = if group.is_empty() { id("-gn")? } else { group.to_string() };
+        Some(ServiceUser { name: user.to_string(), group, uid, gids: ids("-G") })
+    }
+
+    fn readable_by(path: &str, user: &ServiceUser) -> bool {
+        let meta = match std::fs::metadata(path) {
+            Ok(meta) => meta,
+            // Missing files are reported by paths-exist
+            Err(_) => return true,
+        };
+        let mode = meta.mode();
+        if meta.uid() == user.uid {
+            mode & 0o400 != 0
+        } else if user.gids.contains(&meta.gid()) {
+            mode & 0o040 != 0
+        } else {
+            mode & 0o004 != 0
+        }
+    }
+}
+
+impl Check for ServiceUserReadable {
+    fn name(&self) -> &'static str {
+        "service-user-readable"
+    }
+
+    fn run(&self, config: &DetourConfig) -> Vec<Finding> {
+        // Only the unit that consumes a file has to read it
+        let files = config.detours.iter()
+            .filter_map(|d| Some((d.consumer()?, EntryKind::Detour, &d.id, d.custom.as_str(), format!("{} ← {}", d.original, d.custom))))
+            .chain(config.injections.iter()
+                .filter_map(|inj| Some((inj.service.as_deref()?, EntryKind::Injection, &inj.id, inj.include_file.as_str(), format!("{} ← {}", inj.target, inj.include_file)))));
+
+        let mut users: HashMap<&str, Option<ServiceUser>> = HashMap::new();
+        let mut findings = vec![];
+        for (unit, kind, id, path, label) in files {
+            let Some(user) = users.entry(unit).or_insert_with(|| Self::service_user(unit)) else {
+                continue;
+            };
+            if !Self::readable_by(path, user) {
+                findings.push(Finding::new(self.name(), Severity::Warning,
+                        format!("{} is not readable by {} ({})", path, unit, user.name))
+                    .entry(kind, id, label)
+                    .fix(Fix::GrantRead { path: path.to_string(), group: user.group.clone() }));
+            }
+        }
+
+        findings
+    }
+}
+
+/// Detour custom files should carry the owner and mode of the file they replace
+pub struct OwnerModeMatch;
+
+impl Check for OwnerModeMatch {
+    fn name(&self) -> &'static str {
+        "owner-mode-match"
+    }
+
+    fn run(&self, config: &DetourConfig) -> Vec<Finding> {
+        config.detours.iter()
+            // Those are matched on every activate
+            .filter(|d| !d.match_permissions)
+            .filter_map(|d| {
+                let mismatch = crate::manager::DetourManager::new().permission_mismatch(&d.original, &d.custom)?;
+                Some(Finding::new(self.name(), Severity::Warning, mismatch)
+                    .entry(EntryKind::Detour, &d.id, format!("{} ← {}", d.original, d.custom))
+                    .fix(Fix::MatchPermissions { original: d.original.clone(), custom: d.custom.clone() }))
+            })
+            .collect()
+    }
+}
@@ -1,5 +1,5 @@
 use ratatui::{
-    style::{Color, Style},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, BorderType, Borders, List, ListItem},
     Frame,
@@ -23,10 +23,40 @@ fn get_selection_style(is_active: bool) -> Style {
     }
 }
 
+/// Split `text` into spans, styling the chars at `positions`
+fn highlight_spans(text: &str, positions: &[usize]) -> Vec<Span<'static>> {
+    if positions.is_empty() {
+        return vec![Span::raw(text.to_string())];
+    }
+    let emphasis = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+    let mut spans = vec![];
+    let mut run = String::new();
+    let mut run_highlighted = false;
+    for (i, c) in text.chars().enumerate() {
+        let highlighted = positions.contains(&i);
+        if highlighted != run_highlighted && !run.is_empty() {
+            let style = if run_highlighted { emphasis } else { Style::default() };
+            spans.push(Span::styled(std::mem::take(&mut run), style));
+        }
+        run_highlighted = highlighted;
+        run.push(c);
+    }
+    if !run.is_empty() {
+        spans.push(Span::styled(run, if run_highlighted { emphasis } else { Style::default() }));
+    }
+    spans
+}
+
 pub struct ItemRow {
     pub line1: String,
     pub line2: Option<String>,
     pub status_icon: Option<String>,
+    /// Char positions in `line1` (excluding `status_icon`) to emphasise
+    pub highlights: Vec<usize>,
+    /// Part of the current multi-selection
+    pub marked: bool,
+    /// Greyed out (e.g. `when:` does not match this host)
+    pub dimmed: bool,
 }
 
 pub struct ListPanelTheme {
@@ -39,6 +69,29 @@ impl Default for ListPanelTheme {
     }
 }
 
+/// Screen rect of each visible row as (position in the list, rect), for a
+/// bordered list drawn into `area` scrolled to `offset`
+pub fn row_areas(area: Rect, heights: &[u16], offset: usize) -> Vec<(usize, Rect)> {
+    let top = area.y + 1;
+    let bottom = (area.y + area.height).saturating_sub(1);
+    let mut rows = vec![];
+    let mut y = top;
+    for (position, &height) in heights.iter().enumerate().skip(offset) {
+        if y >= bottom {
+            break;
+        }
+        rows.push((position, Rect {
+            x: area.x + 1,
+            y,
+            width: area.width.saturating_sub(2),
+            height: height.min(bottom - y),
+        }));
+        y += height;
+    }
+    rows
+}
+
+/// Draws the list and returns its visible rows (see `row_areas`)
 pub fn draw_list_panel(
     f: &mut Frame,
     area: Rect,
@@ -48,7 +101,7 @@ pub fn draw_list_panel(
     is_active: bool,
     modal_visible: bool,
     theme: &ListPanelTheme,
-) {
+) -> Vec<(usize, Rect)> {
     let border_style = if modal_visible {
         Style::default().fg(hex_color(0x222222))
     } else if is_active {
@@ -70,6 +123,9 @@ pub fn draw_list_panel(
         if is_active { Style::default().fg(Color::Cyan) } else { Style::default().fg(text_color) },
     );
 
+    // Reserve a mark gutter only while something is marked
+    let show_marks = items.iter().any(|row| row.marked);
+    
     let list_items: Vec<ListItem> = if items.is_empty() {
         vec![ListItem::new(" No items").style(Style::default().fg(Color::DarkGray))]
     } else {
@@ -77,17 +133,25 @@ pub fn draw_list_panel(
             .iter()
             .map(|row| {
                 let mut lines: Vec<Line> = Vec::new();
-                let mut line1 = String::new();
+                let mut spans = vec![];
+                if show_marks {
+                    spans.push(if row.marked {
+                        Span::styled("● ", Style::default().fg(Color::Magenta))
+                    } else {
+                        Span::raw("  ")
+                    });
+                }
                 if let Some(icon) = &row.status_icon {
-                    line1.push_str(icon);
-                    line1.push(' ');
+                    spans.push(Span::raw(format!("{} ", icon)));
                 }
-                line1.push_str(&row.line1);
-                lines.push(Line::from(line1));
+                spans.extend(highlight_spans(&row.line1, &row.highlights));
+                lines.push(Line::from(spans));
+                let secondary = if row.dimmed { hex_color(0x555555) } else { theme.secondary_text };
                 if let Some(second) = &row.line2 {
-                    lines.push(Line::from(Span::styled(second.clone(), Style::default().fg(theme.secondary_text))));
+                    lines.push(Line::from(Span::styled(second.clone(), Style::default().fg(secondary))));
                 }
-                ListItem::new(lines).style(Style::default().fg(text_color))
+                let fg = if row.dimmed { hex_color(0x555555) } else { text_color };
+                ListItem::new(lines).style(Style::default().fg(fg))
             })
             .collect()
     };
@@ -111,5 +175,8 @@ pub fn draw_list_panel(
         .highlight_style(highlight_style);
 
     f.render_stateful_widget(list, area, state);
+
+    let heights: Vec<u16> = items.iter().map(|row| 1 + row.line2.is_some() as u16).collect();
+    row_areas(area, &heights, state.offset())
 }
 
@@ -0,0 +1,156 @@
+// `when:` host predicates - decide whether an entry applies on this machine
+
+use crate::config::{DetourConfig, EntryKind, When};
+use std::process::Command;
+
+/// Host facts gathered once per load
+#[derive(Debug, Clone)]
+pub struct Host {
+    pub hostname: String,
+    pub arch: String,
+    pub model: String,
+}
+
+impl Host {
+    pub fn detect() -> Self {
+        let arch = Command::new("uname")
+            .arg("-m")
+            .output()
+            .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+            .unwrap_or_else(|_| std::env::consts::ARCH.to_string());
+        // The device-tree string is NUL terminated
+        let model = std::fs::read_to_string("/proc/device-tree/model")
+            .map(|m| m.trim_end_matches('\0').trim().to_string())
+            .unwrap_or_default();
+        Self { hostname: crate::template::hostname(), arch, model }
+    }
+}
+
+/// Shell-style glob with `*` and `?`
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let p: Vec<char> = pattern.chars().collect();
+    let t: Vec<char> = text.chars().collect();
+    let (mut pi, mut ti) = (0, 0);
+    // Position of the last `*` and the text index it matched up to
+    let mut star: Option<(usize, usize)> = None;
+    while ti < t.len() {
+        if pi < p.len() && (p[pi] == '?' || p[pi] == t[ti]) {
+            pi += 1;
+            ti += 1;
+        } else if pi < p.len() && p[pi] == '*' {
+            star = Some((pi, ti));
+            pi += 1;
+        } else if let Some((sp, st)) = star {
+            pi = sp + 1;
+            ti = st + 1;
+            star = Some((sp, st + 1));
+        } else {
+            return false;
+        }
+    }
+    p[pi..].iter().all(|&c| c == '*')
+}
+
+/// Split `name>=1.2` into ("name", Some((">=", "1.2")))
+pub fn parse_package(spec: &str) -> (&str, Option<(&str, &str)>) {
+    let Some(at) = spec.find(['<', '>', '=']) else {
+        return (spec.trim(), None);
+    };
+    let rest = &spec[at..];
+    let op_len = if rest.starts_with(">=") || rest.starts_with("<=") { 2 } else { 1 };
+    (spec[..at].trim(), Some((&rest[..op_len], rest[op_len..].trim())))
+}
+
+fn installed_version(package: &str) -> Option<String> {
+    let output = Command::new("dpkg-query")
+        .args(["-W", "-f=${Status}\t${Version}", package])
+        .output()
+        .ok()?;
+    let out = String::from_utf8_lossy(&output.stdout).to_string();
+    let (status, version) = out.split_once('\t')?;
+    status.ends_with(" installed").then(|| version.trim().to_string())
+}
+
+fn version_satisfies(installed: &str, op: &str, wanted: &str) -> bool {
+    let dpkg_op = match op {
+        ">=" => "ge",
+        "<=" => "le",
+        ">" => "gt",
+        "<" => "lt",
+        _ => "eq",
+    };
+    Command::new("dpkg")
+        .args(["--compare-versions", installed, dpkg_op, wanted])
+        .status()
+        .map(|s| s.success())
+        .unwrap_or(false)
+}
+
+impl When {
+    /// Ok when every predicate matches, otherwise the first mismatch
+    pub fn evaluate(&self, host: &Host) -> Result<(), String> {
+        if let Some(pattern) = &self.hostname {
+            if !glob_match(pattern, &host.hostname) {
+                return Err(format!("hostname {} ≠ {}", host.hostname, pattern));
+            }
+        }
+        if let Some(pattern) = &self.arch {
+            if !glob_match(pattern, &host.arch) {
+                return Err(format!("arch {} ≠ {}", host.arch, pattern));
+            }
+        }
+        if let Some(pattern) = &self.model {
+            if !glob_match(pattern, &host.model) {
+                let model = if host.model.is_empty() { "unknown" } else { &host.model };
+                return Err(format!("model {} ≠ {}", model, pattern));
+            }
+        }
+        if let Some(path) = &self.file_exists {
+            if !std::path::Path::new(path).exists() {
+                return Err(format!("{} does not exist", path));
+            }
+        }
+        if let Some(spec) = &self.env {
+            let (name, pattern) = match spec.split_once('=') {
+                Some((name, pattern)) => (name, Some(pattern)),
+                None => (spec.as_str(), None),
+            };
+            let value = std::env::var(name).unwrap_or_default();
+            let matched = match pattern {
+                Some(pattern) => glob_match(pattern, &value),
+                None => !value.is_empty(),
+            };
+            if !matched {
+                return Err(format!("env {} not matched", spec));
+            }
+        }
+        if let Some(spec) = &self.package {
+            let (name, constraint) = parse_package(spec);
+            let Some(installed) = installed_version(name) else {
+                return Err(format!("package {} not installed", name));
+            };
+            if let Some((op, wanted)) = constraint {
+                if !version_satisfies(&installed, op, wanted) {
+                    return Err(format!("{} {} not {} {}", name, installed, op, wanted));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Why an entry with `when` is skipped on this host, None when it applies
+pub fn skip_reason(when: Option<&When>, host: &Host) -> Option<String> {
+    when.and_then(|w| w.evaluate(host).err())
+}
+
+/// `skip_reason` for the entry with `id`
+pub fn entry_skip_reason(config: &DetourConfig, id: &str, host: &Host) -> Option<String> {
+    let when = match config.find(id)? {
+        (EntryKind::Detour, i) => config.detours[i].when.as_ref(),
+        (EntryKind::Injection, i) => config.injections[i].when.as_ref(),
+        (EntryKind::Mirror, i) => config.mirrors[i].when.as_ref(),
+        (EntryKind::Service, _) => None,
+    };
+    skip_reason(when, host)
+}
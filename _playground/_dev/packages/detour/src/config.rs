@@ -1,10 +1,21 @@
 // Configuration parsing and management
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::fs;
 
+/// The kinds of entries a config holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryKind {
+    Detour,
+    Injection,
+    Mirror,
+    Service,
+}
+
 // Runtime configuration (detours mapping from ~/.detour.yaml)
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
 pub struct DetourConfig {
     #[serde(default)]
     pub detours: Vec<DetourEntry>,
@@ -14,41 +25,235 @@ pub struct DetourConfig {
     pub mirrors: Vec<MirrorEntry>,
     #[serde(default)]
     pub services: Vec<ServiceEntry>,
+    /// File browser bookmarks
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bookmarks: Vec<String>,
+    #[serde(default, skip_serializing_if = "TemplateSettings::is_default")]
+    pub templates: TemplateSettings,
+    #[serde(default, skip_serializing_if = "HistorySettings::is_default")]
+    pub history: HistorySettings,
+    #[serde(default, skip_serializing_if = "SecretSettings::is_default")]
+    pub secrets: SecretSettings,
+}
+
+/// How new custom files are generated
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct TemplateSettings {
+    /// Prepend a comment noting origin path, date and checksum
+    #[serde(default)]
+    pub header: bool,
+    /// Directory holding user template files
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dir: Option<String>,
+    /// Custom files default to `<managed_dir>/<original path>` when left empty
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub managed_dir: Option<String>,
+}
+
+impl TemplateSettings {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Git history for files under `templates.managed_dir`
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct HistorySettings {
+    /// Auto-commit managed files on save and activate
+    #[serde(default)]
+    pub enabled: bool,
+}
+
+impl HistorySettings {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Where `{{secret:key}}` placeholders in injection templates are looked up
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct SecretSettings {
+    /// Flat `key: value` YAML, e.g. Home Assistant's secrets.yaml
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+}
+
+impl SecretSettings {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct DetourEntry {
+    /// Stable identifier - generated on load when missing
+    #[serde(default)]
+    pub id: String,
     pub original: String,
     pub custom: String,
     #[serde(default)]
     pub description: Option<String>,
+    /// Profile the entry is grouped under
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    /// Host predicates; the entry is skipped on hosts that don't match
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub when: Option<When>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hooks: Option<EntryHooks>,
+    /// Limit the overlay to one unit or command instead of a global bind mount
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<DetourScope>,
+    /// Remount read-only so `original` can't be written through
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub readonly: bool,
+    /// chown/chmod `custom` to the owner and mode of `original` on activate
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub match_permissions: bool,
+    /// Local .deb holding the packaged `original`, when the apt cache doesn't have it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deb: Option<String>,
+    /// Unit that reads `custom` - defaults to `scope.unit`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub service: Option<String>,
     #[serde(skip)]
     pub enabled: bool,
 }
 
+impl DetourEntry {
+    /// Unit whose user has to be able to read `custom`
+    pub fn consumer(&self) -> Option<&str> {
+        self.service.as_deref()
+            .or_else(|| self.scope.as_ref().and_then(|scope| scope.unit.as_deref()))
+    }
+}
+
+/// Shell commands run around activation (`sh -c`, output goes to the log)
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct EntryHooks {
+    /// Failure aborts activation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pre_apply: Option<String>,
+    /// Runs once applied, e.g. `nginx -t`; failure rolls the change back
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub validate: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_apply: Option<String>,
+    /// Failure aborts removal
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pre_remove: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_remove: Option<String>,
+    /// Seconds per hook (default 30)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u64>,
+}
+
+/// Host predicates for `when:` - every predicate that is set must match
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct When {
+    /// Glob, e.g. `pi-*`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hostname: Option<String>,
+    /// Glob against `uname -m`, e.g. `aarch64`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arch: Option<String>,
+    /// Glob against /proc/device-tree/model, e.g. `Raspberry Pi 4*`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_exists: Option<String>,
+    /// `NAME` (set and non-empty) or `NAME=glob`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub env: Option<String>,
+    /// `name` (installed) or `name>=version` (also <=, >, <, =)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub package: Option<String>,
+}
+
+/// Where a scoped detour is visible. Exactly one of `unit`/`command` is set.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct DetourScope {
+    /// systemd unit that gets a BindPaths= drop-in, e.g. klipper.service
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+    /// Command run in a private mount namespace with the overlay applied
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    /// Read-only overlay (BindReadOnlyPaths= for units)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub read_only: bool,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct InjectionEntry {
+    /// Stable identifier - generated on load when missing
+    #[serde(default)]
+    pub id: String,
     pub target: String,
     #[serde(rename = "include")]
     pub include_file: String,
     #[serde(default)]
     pub description: Option<String>,
+    /// Profile the entry is grouped under
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    /// Host predicates; the entry is skipped on hosts that don't match
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub when: Option<When>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hooks: Option<EntryHooks>,
+    /// `include` holds `{{env:..}}`/`{{secret:..}}`/`{{cmd:..}}` placeholders;
+    /// a rendered 0600 copy is injected instead
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub template: bool,
+    /// Rendered copy for templates - defaults to `<include stem>.rendered.<ext>` beside `target`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<String>,
+    /// Unit that reads the injected file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub service: Option<String>,
     #[serde(skip)]
     pub enabled: bool,
 }
 
+impl InjectionEntry {
+    /// File `target` references: the rendered copy for templates, else `include` itself
+    pub fn injected_file(&self) -> String {
+        match (&self.output, self.template) {
+            (_, false) => self.include_file.clone(),
+            (Some(output), true) => output.clone(),
+            (None, true) => crate::secrets::default_output(&self.target, &self.include_file),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct MirrorEntry {
+    /// Stable identifier - generated on load when missing
+    #[serde(default)]
+    pub id: String,
     pub source: String,
     pub target: String,
     #[serde(default)]
     pub description: Option<String>,
+    /// Profile the entry is grouped under
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    /// Host predicates; the entry is skipped on hosts that don't match
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub when: Option<When>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hooks: Option<EntryHooks>,
     #[serde(skip)]
     pub enabled: bool,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ServiceEntry {
+    /// Stable identifier - generated on load when missing
+    #[serde(default)]
+    pub id: String,
     pub name: String,
     pub action: String,
     #[serde(default)]
@@ -91,6 +296,83 @@ impl DetourConfig {
             "/etc/detour.yaml".to_string()
         }
     }
+    
+    /// Assign ids to entries that lack one. Returns true if anything changed
+    pub fn ensure_ids(&mut self) -> bool {
+        let mut taken: HashSet<String> = self.all_ids().into_iter().map(|(_, id)| id.to_string()).collect();
+        let mut changed = false;
+        
+        let mut assign = |id: &mut String, seed: String| {
+            if id.is_empty() {
+                *id = Self::generate_id(&seed, &taken);
+                taken.insert(id.clone());
+                changed = true;
+            }
+        };
+        for e in &mut self.detours {
+            assign(&mut e.id, format!("detour:{}:{}", e.original, e.custom));
+        }
+        for e in &mut self.injections {
+            assign(&mut e.id, format!("injection:{}:{}", e.target, e.include_file));
+        }
+        for e in &mut self.mirrors {
+            assign(&mut e.id, format!("mirror:{}:{}", e.source, e.target));
+        }
+        for e in &mut self.services {
+            assign(&mut e.id, format!("service:{}", e.name));
+        }
+        changed
+    }
+    
+    /// Short hex hash of the entry's content, salted until it is unused
+    ///
+    /// FNV-1a rather than `DefaultHasher`, whose algorithm may change between Rust
+    /// releases - ids that were never written back must come out the same after an upgrade.
+    fn generate_id(seed: &str, taken: &HashSet<String>) -> String {
+        (0u32..)
+            .map(|salt| {
+                let bytes = seed.bytes().chain(salt.to_le_bytes());
+                let hash = bytes.fold(0xcbf29ce484222325u64, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x100000001b3));
+                format!("{:08x}", hash as u32)
+            })
+            .find(|id| !taken.contains(id))
+            .unwrap()
+    }
+    
+    /// Every non-empty id with its kind, in config order
+    pub fn all_ids(&self) -> Vec<(EntryKind, &str)> {
+        let detours = self.detours.iter().map(|e| (EntryKind::Detour, e.id.as_str()));
+        let injections = self.injections.iter().map(|e| (EntryKind::Injection, e.id.as_str()));
+        let mirrors = self.mirrors.iter().map(|e| (EntryKind::Mirror, e.id.as_str()));
+        let services = self.services.iter().map(|e| (EntryKind::Service, e.id.as_str()));
+        detours.chain(injections).chain(mirrors).chain(services)
+            .filter(|(_, id)| !id.is_empty())
+            .collect()
+    }
+    
+    /// Ids used by more than one entry
+    pub fn duplicate_ids(&self) -> Vec<String> {
+        let mut seen = HashSet::new();
+        let mut duplicates = vec![];
+        for (_, id) in self.all_ids() {
+            if !seen.insert(id) && !duplicates.iter().any(|d| d == id) {
+                duplicates.push(id.to_string());
+            }
+        }
+        duplicates
+    }
+    
+    /// Locate an entry by id
+    pub fn find(&self, id: &str) -> Option<(EntryKind, usize)> {
+        if let Some(i) = self.detours.iter().position(|e| e.id == id) {
+            return Some((EntryKind::Detour, i));
+        }
+        if let Some(i) = self.injections.iter().position(|e| e.id == id) {
+            return Some((EntryKind::Injection, i));
+        }
+        if let Some(i) = self.mirrors.iter().position(|e| e.id == id) {
+            return Some((EntryKind::Mirror, i));
+        }
+        self.services.iter().position(|e| e.id == id).map(|i| (EntryKind::Service, i))
+    }
 }
-
-
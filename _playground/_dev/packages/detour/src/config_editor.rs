@@ -0,0 +1,432 @@
+// Embedded YAML editor for ~/.detour.yaml (ViewMode::ConfigEdit)
+
+use crate::config::DetourConfig;
+use std::fs;
+
+// Maximum number of undo snapshots kept in memory
+pub const MAX_UNDO: usize = 200;
+
+// Top-level keys understood by DetourConfig
+const KNOWN_SECTIONS: &[&str] = &["detours", "injections", "mirrors", "services"];
+
+#[derive(Debug, Clone)]
+pub struct EditorDiagnostic {
+    pub line: usize,      // 1-based
+    pub column: usize,    // 1-based
+    pub message: String,
+    pub blocking: bool,   // Blocking diagnostics prevent saving
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HighlightKind {
+    Key,
+    Dash,
+    String,
+    Number,
+    Comment,
+    Plain,
+}
+
+#[derive(Debug, Clone)]
+struct EditorSnapshot {
+    lines: Vec<String>,
+    cursor_row: usize,
+    cursor_col: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConfigEditor {
+    pub path: String,
+    pub lines: Vec<String>,
+    pub cursor_row: usize,
+    pub cursor_col: usize,   // Character (not byte) index
+    pub scroll_offset: usize,
+    pub diagnostics: Vec<EditorDiagnostic>,
+    pub dirty: bool,
+    saved_lines: Vec<String>,   // Buffer as last read from or written to disk
+    undo_stack: Vec<EditorSnapshot>,
+    redo_stack: Vec<EditorSnapshot>,
+}
+
+impl ConfigEditor {
+    pub fn open(path: &str) -> Self {
+        let content = fs::read_to_string(path).unwrap_or_default();
+        Self::from_content(path, &content)
+    }
+
+    pub fn from_content(path: &str, content: &str) -> Self {
+        let mut lines: Vec<String> = content.lines().map(|l| l.to_string()).collect();
+        if lines.is_empty() {
+            lines.push(String::new());
+        }
+        let mut editor = Self {
+            path: path.to_string(),
+            lines: lines.clone(),
+            cursor_row: 0,
+            cursor_col: 0,
+            scroll_offset: 0,
+            diagnostics: vec![],
+            dirty: false,
+            saved_lines: lines,
+            undo_stack: vec![],
+            redo_stack: vec![],
+        };
+        editor.validate();
+        editor
+    }
+
+    pub fn content(&self) -> String {
+        let mut content = self.lines.join("\n");
+        content.push('\n');
+        content
+    }
+
+    pub fn has_blocking_errors(&self) -> bool {
+        self.diagnostics.iter().any(|d| d.blocking)
+    }
+
+    /// Diagnostics attached to a given 1-based line
+    pub fn diagnostics_for_line(&self, line: usize) -> Vec<&EditorDiagnostic> {
+        self.diagnostics.iter().filter(|d| d.line == line).collect()
+    }
+
+    /// Re-parse the buffer against DetourConfig and refresh diagnostics
+    pub fn validate(&mut self) {
+        self.diagnostics.clear();
+        let content = self.content();
+
+        let config = match serde_yaml::from_str::<DetourConfig>(&content) {
+            Ok(config) => config,
+            Err(e) => {
+                let (line, column) = e.location()
+                    .map(|loc| (loc.line(), loc.column()))
+                    .unwrap_or((1, 1));
+                self.diagnostics.push(EditorDiagnostic {
+                    line,
+                    column,
+                    message: Self::strip_location(&e.to_string()),
+                    blocking: true,
+                });
+                return;
+            }
+        };
+
+        // Entries are addressed by id, so a reused id would make actions ambiguous
+        for id in config.duplicate_ids() {
+            let occurrences = self.lines.iter().enumerate()
+                .filter(|(_, line)| {
+                    let line = line.trim_start().trim_start_matches("- ");
+                    line.strip_prefix("id:").map(|v| v.trim().trim_matches(|c| c == '"' || c == '\'') == id).unwrap_or(false)
+                })
+                .map(|(idx, _)| idx + 1)
+                .skip(1);
+            for line in occurrences {
+                self.diagnostics.push(EditorDiagnostic {
+                    line,
+                    column: 1,
+                    message: format!("Duplicate id `{}`", id),
+                    blocking: true,
+                });
+            }
+        }
+
+        // Unknown top-level sections are ignored by serde, so flag them as warnings
+        for (idx, line) in self.lines.iter().enumerate() {
+            if line.starts_with(' ') || line.starts_with('#') || line.starts_with('-') {
+                continue;
+            }
+            if let Some((key, _)) = line.split_once(':') {
+                let key = key.trim();
+                if !key.is_empty() && !KNOWN_SECTIONS.contains(&key) {
+                    self.diagnostics.push(EditorDiagnostic {
+                        line: idx + 1,
+                        column: 1,
+                        message: format!("Unknown section `{}` will be ignored", key),
+                        blocking: false,
+                    });
+                }
+            }
+        }
+    }
+
+    // serde_yaml appends " at line X column Y" which we already show separately
+    fn strip_location(message: &str) -> String {
+        match message.find(" at line ") {
+            Some(pos) => message[..pos].to_string(),
+            None => message.to_string(),
+        }
+    }
+
+    fn snapshot(&self) -> EditorSnapshot {
+        EditorSnapshot {
+            lines: self.lines.clone(),
+            cursor_row: self.cursor_row,
+            cursor_col: self.cursor_col,
+        }
+    }
+
+    fn restore(&mut self, snapshot: EditorSnapshot) {
+        self.lines = snapshot.lines;
+        self.cursor_row = snapshot.cursor_row;
+        self.cursor_col = snapshot.cursor_col;
+    }
+
+    // Record state before an edit
+    fn begin_edit(&mut self) {
+        self.undo_stack.push(self.snapshot());
+        if self.undo_stack.len() > MAX_UNDO {
+            self.undo_stack.remove(0);
+        }
+        self.redo_stack.clear();
+    }
+
+    // Buffer is dirty while it differs from the saved text, so undoing back to it is clean
+    fn end_edit(&mut self) {
+        self.dirty = self.lines != self.saved_lines;
+        self.validate();
+    }
+
+    pub fn undo(&mut self) -> bool {
+        if let Some(snapshot) = self.undo_stack.pop() {
+            self.redo_stack.push(self.snapshot());
+            self.restore(snapshot);
+            self.end_edit();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn redo(&mut self) -> bool {
+        if let Some(snapshot) = self.redo_stack.pop() {
+            self.undo_stack.push(self.snapshot());
+            self.restore(snapshot);
+            self.end_edit();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn line_len(&self, row: usize) -> usize {
+        self.lines.get(row).map(|l| l.chars().count()).unwrap_or(0)
+    }
+
+    fn byte_index(line: &str, col: usize) -> usize {
+        line.char_indices().nth(col).map(|(i, _)| i).unwrap_or(line.len())
+    }
+
+    pub fn insert_char(&mut self, c: char) {
+        self.begin_edit();
+        let row = self.cursor_row;
+        let idx = Self::byte_index(&self.lines[row], self.cursor_col);
+        self.lines[row].insert(idx, c);
+        self.cursor_col += 1;
+        self.end_edit();
+    }
+
+    pub fn insert_tab(&mut self) {
+        // YAML forbids tabs for indentation
+        self.begin_edit();
+        let row = self.cursor_row;
+        let idx = Self::byte_index(&self.lines[row], self.cursor_col);
+        self.lines[row].insert_str(idx, "  ");
+        self.cursor_col += 2;
+        self.end_edit();
+    }
+
+    pub fn insert_newline(&mut self) {
+        self.begin_edit();
+        let row = self.cursor_row;
+        let idx = Self::byte_index(&self.lines[row], self.cursor_col);
+        let rest = self.lines[row].split_off(idx);
+
+        // Carry indentation over to the new line
+        let indent: String = self.lines[row].chars().take_while(|c| *c == ' ').collect();
+        self.lines.insert(row + 1, format!("{}{}", indent, rest));
+        self.cursor_row += 1;
+        self.cursor_col = indent.chars().count();
+        self.end_edit();
+    }
+
+    pub fn backspace(&mut self) {
+        if self.cursor_col > 0 {
+            self.begin_edit();
+            let row = self.cursor_row;
+            let idx = Self::byte_index(&self.lines[row], self.cursor_col - 1);
+            self.lines[row].remove(idx);
+            self.cursor_col -= 1;
+            self.end_edit();
+        } else if self.cursor_row > 0 {
+            self.begin_edit();
+            let line = self.lines.remove(self.cursor_row);
+            self.cursor_row -= 1;
+            self.cursor_col = self.line_len(self.cursor_row);
+            self.lines[self.cursor_row].push_str(&line);
+            self.end_edit();
+        }
+    }
+
+    pub fn delete(&mut self) {
+        let len = self.line_len(self.cursor_row);
+        if self.cursor_col < len {
+            self.begin_edit();
+            let row = self.cursor_row;
+            let idx = Self::byte_index(&self.lines[row], self.cursor_col);
+            self.lines[row].remove(idx);
+            self.end_edit();
+        } else if self.cursor_row + 1 < self.lines.len() {
+            self.begin_edit();
+            let next = self.lines.remove(self.cursor_row + 1);
+            self.lines[self.cursor_row].push_str(&next);
+            self.end_edit();
+        }
+    }
+
+    pub fn move_left(&mut self) {
+        if self.cursor_col > 0 {
+            self.cursor_col -= 1;
+        } else if self.cursor_row > 0 {
+            self.cursor_row -= 1;
+            self.cursor_col = self.line_len(self.cursor_row);
+        }
+    }
+
+    pub fn move_right(&mut self) {
+        if self.cursor_col < self.line_len(self.cursor_row) {
+            self.cursor_col += 1;
+        } else if self.cursor_row + 1 < self.lines.len() {
+            self.cursor_row += 1;
+            self.cursor_col = 0;
+        }
+    }
+
+    pub fn move_up(&mut self) {
+        if self.cursor_row > 0 {
+            self.cursor_row -= 1;
+            self.cursor_col = self.cursor_col.min(self.line_len(self.cursor_row));
+        }
+    }
+
+    pub fn move_down(&mut self) {
+        if self.cursor_row + 1 < self.lines.len() {
+            self.cursor_row += 1;
+            self.cursor_col = self.cursor_col.min(self.line_len(self.cursor_row));
+        }
+    }
+
+    pub fn move_home(&mut self) {
+        self.cursor_col = 0;
+    }
+
+    pub fn move_end(&mut self) {
+        self.cursor_col = self.line_len(self.cursor_row);
+    }
+
+    pub fn page_up(&mut self, page: usize) {
+        self.cursor_row = self.cursor_row.saturating_sub(page);
+        self.cursor_col = self.cursor_col.min(self.line_len(self.cursor_row));
+    }
+
+    pub fn page_down(&mut self, page: usize) {
+        self.cursor_row = (self.cursor_row + page).min(self.lines.len().saturating_sub(1));
+        self.cursor_col = self.cursor_col.min(self.line_len(self.cursor_row));
+    }
+
+    /// Jump the cursor to the first diagnostic, if any
+    pub fn goto_first_diagnostic(&mut self) {
+        if let Some(diag) = self.diagnostics.first() {
+            self.cursor_row = diag.line.saturating_sub(1).min(self.lines.len().saturating_sub(1));
+            self.cursor_col = diag.column.saturating_sub(1).min(self.line_len(self.cursor_row));
+        }
+    }
+
+    /// Keep the cursor inside the visible window
+    pub fn adjust_scroll(&mut self, visible_height: usize) {
+        if visible_height == 0 {
+            return;
+        }
+        if self.cursor_row < self.scroll_offset {
+            self.scroll_offset = self.cursor_row;
+        } else if self.cursor_row >= self.scroll_offset + visible_height {
+            self.scroll_offset = self.cursor_row + 1 - visible_height;
+        }
+    }
+
+    /// Write the buffer back to disk - refuses while the buffer doesn't parse
+    pub fn save(&mut self) -> Result<(), String> {
+        self.validate();
+        if let Some(diag) = self.diagnostics.iter().find(|d| d.blocking) {
+            return Err(format!(
+                "Cannot save while config is invalid:\nLine {}, column {}: {}",
+                diag.line, diag.column, diag.message
+            ));
+        }
+
+        fs::write(&self.path, self.content())
+            .map_err(|e| format!("Failed to write config: {}", e))?;
+        self.saved_lines = self.lines.clone();
+        self.dirty = false;
+        Ok(())
+    }
+}
+
+/// Split a YAML line into highlighted segments
+pub fn highlight_line(line: &str) -> Vec<(String, HighlightKind)> {
+    let mut segments = vec![];
+    let indent_len = line.len() - line.trim_start().len();
+    let (indent, mut rest) = line.split_at(indent_len);
+    if !indent.is_empty() {
+        segments.push((indent.to_string(), HighlightKind::Plain));
+    }
+
+    if rest.starts_with('#') {
+        segments.push((rest.to_string(), HighlightKind::Comment));
+        return segments;
+    }
+
+    // List item marker
+    if rest.starts_with("- ") || rest == "-" {
+        let (dash, tail) = rest.split_at(1);
+        segments.push((dash.to_string(), HighlightKind::Dash));
+        let spaces = tail.len() - tail.trim_start().len();
+        if spaces > 0 {
+            segments.push((tail[..spaces].to_string(), HighlightKind::Plain));
+        }
+        rest = &tail[spaces..];
+    }
+
+    // Split off trailing comment (only when preceded by whitespace)
+    let (body, comment) = match rest.find(" #") {
+        Some(pos) => (&rest[..pos], Some(&rest[pos..])),
+        None => (rest, None),
+    };
+
+    // key: value
+    let key_end = body.find(": ").or_else(|| if body.ends_with(':') { Some(body.len() - 1) } else { None });
+    let value = match key_end {
+        Some(pos) if !body.starts_with('"') && !body.starts_with('\'') => {
+            segments.push((body[..=pos].to_string(), HighlightKind::Key));
+            &body[pos + 1..]
+        }
+        _ => body,
+    };
+
+    if !value.is_empty() {
+        let trimmed = value.trim();
+        let kind = if trimmed.parse::<f64>().is_ok() || matches!(trimmed, "true" | "false" | "null" | "~") {
+            HighlightKind::Number
+        } else if trimmed.is_empty() {
+            HighlightKind::Plain
+        } else {
+            HighlightKind::String
+        };
+        segments.push((value.to_string(), kind));
+    }
+
+    if let Some(comment) = comment {
+        segments.push((comment.to_string(), HighlightKind::Comment));
+    }
+
+    segments
+}
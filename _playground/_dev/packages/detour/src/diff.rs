@@ -23,6 +23,8 @@ pub struct DiffViewer {
     pub left_content: Vec<String>,
     pub right_content: Vec<String>,
     pub scroll_offset: usize,
+    /// Set when the left side is a history revision of the right-hand file
+    pub revision: Option<String>,
 }
 
 impl DiffViewer {
@@ -45,6 +47,7 @@ impl DiffViewer {
             left_content,
             right_content,
             scroll_offset: 0,
+            revision: None,
         })
     }
     
@@ -124,7 +127,11 @@ pub fn draw_diff(f: &mut Frame, area: Rect, diff: &DiffViewer) {
         height: 1,
     };
     
-    let help_text = "[↑↓] Scroll  [PgUp/PgDn] Page  [Esc] Close";
+    let help_text = if diff.revision.is_some() {
+        "[↑↓] Scroll  [PgUp/PgDn] Page  [r] Restore this revision  [Esc] Close"
+    } else {
+        "[↑↓] Scroll  [PgUp/PgDn] Page  [Esc] Close"
+    };
     let help = Paragraph::new(help_text)
         .style(Style::default().fg(hex_color(0x666666)))
         .alignment(Alignment::Center);
@@ -0,0 +1,78 @@
+// External editor for custom/include files - the TUI suspends while it runs
+
+use std::path::Path;
+use std::process::Command;
+
+/// File handed to the editor once the main loop has left the alternate screen
+#[derive(Debug, Clone)]
+pub struct PendingEdit {
+    pub id: String,
+    pub path: String,
+    /// Inode before editing - editors that save by rename leave a bind mount on the old one
+    pub inode: Option<u64>,
+}
+
+impl PendingEdit {
+    pub fn new(id: String, path: String) -> Self {
+        let inode = inode(&path);
+        Self { id, path, inode }
+    }
+
+    /// Editor replaced the file instead of writing it in place
+    pub fn replaced(&self) -> bool {
+        self.inode.is_some() && inode(&self.path) != self.inode
+    }
+}
+
+pub fn inode(path: &str) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    std::fs::metadata(path).ok().map(|m| m.ino())
+}
+
+/// $VISUAL, then $EDITOR, then vi
+pub fn editor() -> String {
+    ["VISUAL", "EDITOR"].iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string())
+}
+
+/// Whether the current user can save `path` without sudo
+pub fn writable(path: &str) -> bool {
+    let path = Path::new(path);
+    if path.exists() {
+        return std::fs::OpenOptions::new().write(true).open(path).is_ok();
+    }
+    // New file: the directory must take it
+    let dir = path.parent().filter(|p| !p.as_os_str().is_empty()).unwrap_or(Path::new("."));
+    let probe = dir.join(format!(".detour-write-test-{}", std::process::id()));
+    let ok = std::fs::File::create(&probe).is_ok();
+    let _ = std::fs::remove_file(&probe);
+    ok
+}
+
+/// `$EDITOR path`, or `sudoedit path` when the file isn't writable.
+/// The editor string may carry arguments (e.g. `code --wait`), so it goes through sh.
+pub fn command(path: &str) -> Command {
+    if writable(path) {
+        let mut command = Command::new("sh");
+        command.arg("-c").arg(format!("{} \"$1\"", editor())).arg("sh").arg(path);
+        command
+    } else {
+        let mut command = Command::new("sudoedit");
+        command.arg(path);
+        command
+    }
+}
+
+/// Run the editor in the foreground; the terminal must already be restored
+pub fn run(path: &str) -> Result<(), String> {
+    let status = command(path)
+        .status()
+        .map_err(|e| format!("Failed to start editor: {}", e))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("Editor exited with {}", status))
+    }
+}
@@ -1,6 +1,6 @@
 // Event handling for detour TUI
 
-use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 use std::time::Duration;
 
 pub fn handle_events(app: &mut crate::app::App) -> std::io::Result<()> {
@@ -10,20 +10,25 @@ pub fn handle_events(app: &mut crate::app::App) -> std::io::Result<()> {
     });
     
     if event::poll(Duration::from_millis(100))? {
-        match event::read()? {
-            Event::Key(key) => {
-                handle_key_event(key, app);
-            }
-            Event::Mouse(mouse) => {
-                handle_mouse_event(mouse, app);
-            }
-            _ => {}
-        }
+        handle_event(event::read()?, app);
     }
     Ok(())
 }
 
-fn handle_key_event(key: KeyEvent, app: &mut crate::app::App) {
+/// Dispatch one terminal event - the main loop and the UI snapshot tests feed this
+pub fn handle_event(event: Event, app: &mut crate::app::App) {
+    match event {
+        Event::Key(key) => {
+            handle_key_event(key, app);
+        }
+        Event::Mouse(mouse) => {
+            handle_mouse_event(mouse, app);
+        }
+        _ => {}
+    }
+}
+
+pub fn handle_key_event(key: KeyEvent, app: &mut crate::app::App) {
     // If file browser is open, handle browser-specific keys
     if app.file_browser.is_some() {
         handle_file_browser_keys(key, app);
@@ -60,7 +65,58 @@ fn handle_key_event(key: KeyEvent, app: &mut crate::app::App) {
         return;
     }
     
+    // Embedded config editor captures all keys while focused
+    if app.view_mode == crate::app::ViewMode::ConfigEdit
+        && app.active_column == crate::app::ActiveColumn::Content
+        && app.config_editor.is_some() {
+        handle_config_editor_keys(key, app);
+        return;
+    }
+    
+    // `/` search input captures keys until Enter/Esc
+    if app.is_searching() && app.active_column == crate::app::ActiveColumn::Content {
+        handle_search_keys(key, app);
+        return;
+    }
+    
+    let in_list = app.active_column == crate::app::ActiveColumn::Content && app.current_filter().is_some();
+    
     match key.code {
+        // Esc clears marks, then an active list filter, before quitting
+        KeyCode::Esc if in_list && !app.marked_in_view().is_empty() => {
+            app.clear_marks();
+        }
+        KeyCode::Esc if in_list && app.current_filter().is_some_and(|f| f.is_active()) => {
+            app.update_filter(|f| f.clear());
+        }
+        KeyCode::Char('m') if in_list => {
+            app.toggle_mark();
+        }
+        KeyCode::Char('M') if in_list => {
+            app.mark_range();
+        }
+        KeyCode::Char('b') if in_list => {
+            app.open_bulk_menu();
+        }
+        KeyCode::Char('H') if in_list => {
+            app.open_history();
+        }
+        KeyCode::Char('E') if in_list => {
+            app.open_in_editor();
+        }
+        KeyCode::Char('P') if in_list && app.view_mode == crate::app::ViewMode::DetoursList => {
+            app.show_pristine_diff();
+        }
+        KeyCode::Char('/') if in_list => {
+            app.update_filter(|f| f.editing = true);
+        }
+        KeyCode::Char('f') if in_list && !key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.cycle_status_filter();
+        }
+        KeyCode::Char('o') if in_list => {
+            app.cycle_sort();
+        }
+        
         // Quit
         KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('Q') => {
             app.should_quit = true;
@@ -135,6 +191,10 @@ fn handle_key_event(key: KeyEvent, app: &mut crate::app::App) {
         KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
             app.reload_config();
         }
+        KeyCode::Char('r') if app.view_mode == crate::app::ViewMode::StatusOverview => {
+            app.refresh_status();
+            app.add_toast("Status refreshed".to_string(), crate::app::ToastType::Success);
+        }
         KeyCode::Char('v') => {
             // Verify - context aware based on column and selection
             match app.active_column {
@@ -169,6 +229,11 @@ fn handle_key_event(key: KeyEvent, app: &mut crate::app::App) {
                     } else if app.view_mode == crate::app::ViewMode::InjectionsList {
                         let idx = app.selected_injection;
                         app.validate_single_injection(idx);
+                    } else if app.view_mode == crate::app::ViewMode::MirrorsList {
+                        if let Some(mirror) = app.mirrors.get(app.selected_mirror) {
+                            let scope = crate::checks::Scope::entry(crate::status::EntryKind::Mirror, &mirror.id);
+                            app.run_checks(scope);
+                        }
                     }
                 }
             }
@@ -202,6 +267,8 @@ fn handle_key_event(key: KeyEvent, app: &mut crate::app::App) {
             }
         }
         KeyCode::Char('s') => {
+            app.selected_view = 4;
+            app.view_state.select(Some(4));
             app.view_mode = crate::app::ViewMode::StatusOverview;
         }
         KeyCode::Char('d') => {
@@ -214,6 +281,10 @@ fn handle_key_event(key: KeyEvent, app: &mut crate::app::App) {
                             let custom = detour.custom.clone();
                             app.show_diff(&original, &custom);
                         }
+                    } else if app.view_mode == crate::app::ViewMode::InjectionsList
+                        && app.injections.get(app.selected_injection).is_some_and(|i| i.template) {
+                        // Template vs rendered output, secrets redacted
+                        app.show_rendered_diff();
                     } else {
                         // Deactivate all for other lists from content
                         match app.view_mode {
@@ -240,6 +311,30 @@ fn handle_key_event(key: KeyEvent, app: &mut crate::app::App) {
     }
 }
 
+fn handle_search_keys(key: KeyEvent, app: &mut crate::app::App) {
+    match key.code {
+        KeyCode::Esc => {
+            app.update_filter(|f| {
+                f.query.clear();
+                f.editing = false;
+            });
+        }
+        KeyCode::Enter => {
+            app.update_filter(|f| f.editing = false);
+        }
+        KeyCode::Backspace => {
+            app.update_filter(|f| { f.query.pop(); });
+        }
+        // Arrows still move through the narrowed list while typing
+        KeyCode::Up => app.navigate_up(),
+        KeyCode::Down => app.navigate_down(),
+        KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.update_filter(|f| f.query.push(c));
+        }
+        _ => {}
+    }
+}
+
 fn handle_popup_keys(key: KeyEvent, app: &mut crate::app::App) {
     use crate::popup::Popup;
     
@@ -257,6 +352,14 @@ fn handle_popup_keys(key: KeyEvent, app: &mut crate::app::App) {
             app.handle_popup_right();
         }
         
+        // For select popups
+        KeyCode::Up | KeyCode::Char('k') if matches!(app.popup, Some(Popup::Select { .. })) => {
+            app.handle_popup_up();
+        }
+        KeyCode::Down | KeyCode::Char('j') if matches!(app.popup, Some(Popup::Select { .. })) => {
+            app.handle_popup_down();
+        }
+        
         // For input popups
         KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
             if matches!(app.popup, Some(Popup::Input { .. })) {
@@ -280,53 +383,88 @@ fn handle_popup_keys(key: KeyEvent, app: &mut crate::app::App) {
                             // Execute pending action if any
                             if let Some(pending) = action {
                                 match pending {
-                                    crate::app::PendingAction::CreateFileAndSaveDetour => {
-                                        app.create_custom_file_and_save();
+                                    crate::app::PendingAction::DeleteDetour(id) => {
+                                        app.confirm_delete_detour(&id);
                                     }
-                                    crate::app::PendingAction::DeleteDetour(index) => {
-                                        app.confirm_delete_detour(index);
+                                    crate::app::PendingAction::DeleteDetourAndFile(id, custom_path) => {
+                                        app.delete_detour_and_file(&id, custom_path, true);
                                     }
-                                    crate::app::PendingAction::DeleteDetourAndFile(index, custom_path) => {
-                                        app.delete_detour_and_file(index, custom_path, true);
+                                    crate::app::PendingAction::DeleteInjection(id) => {
+                                        app.confirm_delete_injection(&id);
                                     }
-                                    crate::app::PendingAction::DeleteInjection(index) => {
-                                        app.confirm_delete_injection(index);
-                                    }
-                                    crate::app::PendingAction::DeleteInjectionAndFile(index, include_file_path) => {
-                                        app.delete_injection_and_file(index, include_file_path, true);
+                                    crate::app::PendingAction::DeleteInjectionAndFile(id, include_file_path) => {
+                                        app.delete_injection_and_file(&id, include_file_path, true);
                                     }
                                     crate::app::PendingAction::CreateInjectionFileAndSave => {
                                         app.create_injection_file_and_save();
                                     }
-                                    crate::app::PendingAction::DeleteMirror(index) => {
-                                        app.confirm_delete_mirror(index);
+                                    crate::app::PendingAction::DeleteMirror(id) => {
+                                        app.confirm_delete_mirror(&id);
+                                    }
+                                    crate::app::PendingAction::Bulk(plan) => {
+                                        app.execute_bulk(plan);
+                                    }
+                                    crate::app::PendingAction::RestoreRevision(path, rev) => {
+                                        app.restore_revision(&path, &rev);
                                     }
+                                    crate::app::PendingAction::WriteMissingIds => {
+                                        app.write_missing_ids();
+                                    }
+                                    // Only issued by Select/Input popups
+                                    crate::app::PendingAction::BulkChoose(..)
+                                    | crate::app::PendingAction::BulkProfile(..)
+                                    | crate::app::PendingAction::HistoryPick(..)
+                                    | crate::app::PendingAction::CreateFileAndSaveDetour(..) => {}
                                 }
                             }
                         } else {
                             // User selected "No" - handle accordingly
                             match action {
-                                Some(crate::app::PendingAction::DeleteDetourAndFile(_index, _)) => {
+                                Some(crate::app::PendingAction::DeleteDetourAndFile(_id, _)) => {
                                     // Don't delete file, just reload config (detour already deleted from config)
+                                    // reload_config re-syncs the selection against the filtered list
                                     app.reload_config();
-                                    if app.selected_detour >= app.detours.len() && app.selected_detour > 0 {
-                                        app.selected_detour -= 1;
-                                        app.detour_state.select(Some(app.selected_detour));
-                                    }
                                 }
-                                Some(crate::app::PendingAction::DeleteInjectionAndFile(_index, _)) => {
+                                Some(crate::app::PendingAction::DeleteInjectionAndFile(_id, _)) => {
                                     // Don't delete file, just reload config (injection already deleted from config)
+                                    // reload_config re-syncs the selection against the filtered list
                                     app.reload_config();
-                                    if app.selected_injection >= app.injections.len() && app.selected_injection > 0 {
-                                        app.selected_injection -= 1;
-                                        app.injection_state.select(Some(app.selected_injection));
-                                    }
                                 }
                                 _ => {}
                             }
                         }
                     }
-                    Popup::Input { .. } | Popup::Error { .. } | Popup::Info { .. } => {
+                    Popup::Select { selected, .. } => {
+                        let choice = *selected;
+                        let action = app.pending_action.take();
+                        app.close_popup();
+                        match action {
+                            Some(crate::app::PendingAction::BulkChoose(kind, ids)) => {
+                                app.handle_bulk_choice(kind, ids, choice);
+                            }
+                            Some(crate::app::PendingAction::HistoryPick(path, hashes)) => {
+                                if let Some(rev) = hashes.get(choice) {
+                                    app.show_revision_diff(&path, rev);
+                                }
+                            }
+                            Some(crate::app::PendingAction::CreateFileAndSaveDetour(sources)) => {
+                                if let Some(source) = sources.get(choice).cloned() {
+                                    app.create_custom_file_and_save(source);
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                    Popup::Input { .. } => {
+                        let input = popup.get_input().unwrap_or_default();
+                        let action = app.pending_action.take();
+                        app.close_popup();
+                        if let Some(crate::app::PendingAction::BulkProfile(kind, ids)) = action {
+                            use crate::operations::bulk::{BulkOp, BulkPlan};
+                            app.confirm_bulk(BulkPlan { op: BulkOp::MoveToProfile(input), kind, ids });
+                        }
+                    }
+                    Popup::Error { .. } | Popup::Info { .. } => {
                         app.close_popup();
                     }
                 }
@@ -354,6 +492,9 @@ fn handle_diff_keys(key: KeyEvent, app: &mut crate::app::App) {
         KeyCode::PageDown => {
             app.scroll_diff_page_down();
         }
+        KeyCode::Char('r') => {
+            app.confirm_restore_revision();
+        }
         _ => {}
     }
 }
@@ -410,10 +551,88 @@ fn handle_unified_form_keys(key: KeyEvent, app: &mut crate::app::App) {
     }
 }
 
+fn handle_config_editor_keys(key: KeyEvent, app: &mut crate::app::App) {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    
+    match key.code {
+        KeyCode::Char('s') if ctrl => {
+            app.save_config_editor();
+            return;
+        }
+        KeyCode::Char('r') if ctrl => {
+            app.revert_config_editor();
+            return;
+        }
+        KeyCode::Esc => {
+            app.active_column = crate::app::ActiveColumn::Actions;
+            return;
+        }
+        _ => {}
+    }
+    
+    if let Some(editor) = &mut app.config_editor {
+        match key.code {
+            KeyCode::Char('z') if ctrl => { editor.undo(); }
+            KeyCode::Char('y') if ctrl => { editor.redo(); }
+            KeyCode::Char('g') if ctrl => editor.goto_first_diagnostic(),
+            KeyCode::Char(c) if !ctrl => editor.insert_char(c),
+            KeyCode::Tab => editor.insert_tab(),
+            KeyCode::Enter => editor.insert_newline(),
+            KeyCode::Backspace => editor.backspace(),
+            KeyCode::Delete => editor.delete(),
+            KeyCode::Left => editor.move_left(),
+            KeyCode::Right => editor.move_right(),
+            KeyCode::Up => editor.move_up(),
+            KeyCode::Down => editor.move_down(),
+            KeyCode::Home => editor.move_home(),
+            KeyCode::End => editor.move_end(),
+            KeyCode::PageUp => editor.page_up(10),
+            KeyCode::PageDown => editor.page_down(10),
+            _ => {}
+        }
+    }
+}
+
 fn handle_file_browser_keys(key: KeyEvent, app: &mut crate::app::App) {
+    use crate::filebrowser::BrowserMode;
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    
     if let Some(browser) = &mut app.file_browser {
+        // Filter input, jump box and places list capture keys until Esc/Enter
+        if browser.mode != BrowserMode::Browse {
+            match key.code {
+                KeyCode::Esc if browser.mode == BrowserMode::Filter => {
+                    browser.clear_filter();
+                    browser.mode = BrowserMode::Browse;
+                }
+                KeyCode::Esc => browser.mode = BrowserMode::Browse,
+                KeyCode::Enter if browser.mode == BrowserMode::Filter => browser.mode = BrowserMode::Browse,
+                KeyCode::Up if browser.mode == BrowserMode::Filter => browser.navigate_up(),
+                KeyCode::Down if browser.mode == BrowserMode::Filter => browser.navigate_down(),
+                KeyCode::Backspace if browser.mode == BrowserMode::Filter => browser.filter_pop(),
+                KeyCode::Char(c) if browser.mode == BrowserMode::Filter && !ctrl => browser.filter_push(c),
+                KeyCode::Enter => browser.accept_list(),
+                KeyCode::Up => browser.list_up(),
+                KeyCode::Down => browser.list_down(),
+                KeyCode::Delete if browser.mode == BrowserMode::Places => {
+                    match browser.remove_place() {
+                        Some(true) => app.save_browser_bookmarks(),
+                        Some(false) => app.save_recent_paths(),
+                        None => {}
+                    }
+                }
+                KeyCode::Backspace if browser.mode == BrowserMode::Jump => browser.jump_pop(),
+                KeyCode::Char(c) if browser.mode == BrowserMode::Jump && !ctrl => browser.jump_push(c),
+                _ => {}
+            }
+            return;
+        }
+        
         match key.code {
-            // Close browser without selection
+            // Clear the filter first, then close browser without selection
+            KeyCode::Esc if !browser.filter.is_empty() => {
+                browser.clear_filter();
+            }
             KeyCode::Esc => {
                 match app.view_mode {
                     crate::app::ViewMode::InjectionsAdd => {
@@ -437,6 +656,9 @@ fn handle_file_browser_keys(key: KeyEvent, app: &mut crate::app::App) {
                     } else {
                         // Select file
                         let path = browser.get_selected_path();
+                        if let Some(path) = &path {
+                            app.remember_browser_path(path);
+                        }
                         match app.view_mode {
                             crate::app::ViewMode::InjectionsAdd => {
                                 app.injection_form_close_file_browser(path);
@@ -474,6 +696,34 @@ fn handle_file_browser_keys(key: KeyEvent, app: &mut crate::app::App) {
                 browser.go_to_parent();
             }
             
+            KeyCode::Char('a') if ctrl => {
+                browser.toggle_hidden();
+            }
+            KeyCode::Char('b') if ctrl => {
+                let added = browser.toggle_bookmark();
+                let dir = browser.current_dir.display().to_string();
+                app.save_browser_bookmarks();
+                let message = if added { format!("Bookmarked {}", dir) } else { format!("Removed bookmark {}", dir) };
+                app.add_toast(message, crate::app::ToastType::Info);
+            }
+            KeyCode::Char('o') if ctrl => {
+                browser.open_places();
+            }
+            KeyCode::Char('g') if ctrl => {
+                browser.open_jump();
+            }
+            
+            KeyCode::Char('/') => {
+                browser.open_filter();
+            }
+            // Backspace trims a kept filter, on an empty filter it goes up a directory
+            KeyCode::Backspace if browser.filter.is_empty() => {
+                browser.go_to_parent();
+            }
+            KeyCode::Backspace => {
+                browser.filter_pop();
+            }
+            
             _ => {}
         }
     }
@@ -481,26 +731,123 @@ fn handle_file_browser_keys(key: KeyEvent, app: &mut crate::app::App) {
 
 fn handle_validation_report_keys(key: KeyEvent, app: &mut crate::app::App) {
     match key.code {
-        KeyCode::Enter | KeyCode::Esc | KeyCode::Char('q') => {
+        KeyCode::Esc | KeyCode::Char('q') => {
             app.close_validation_report();
         }
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.validation_select_prev();
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.validation_select_next();
+        }
+        KeyCode::Enter => {
+            app.validation_jump_to_entry();
+        }
+        KeyCode::Char('f') => {
+            app.validation_apply_fix();
+        }
         _ => {}
     }
 }
 
 fn handle_mouse_event(mouse: MouseEvent, app: &mut crate::app::App) {
-    // Only handle mouse events in file browser for now
+    use crate::app::ActiveColumn;
+    
+    let scroll_up = match mouse.kind {
+        MouseEventKind::ScrollUp => Some(true),
+        MouseEventKind::ScrollDown => Some(false),
+        _ => None,
+    };
+    let clicked = matches!(mouse.kind, MouseEventKind::Down(MouseButton::Left));
+    let (x, y) = (mouse.column, mouse.row);
+    
+    // Overlays take the mouse in the same order they take keys
     if let Some(browser) = &mut app.file_browser {
-        match mouse.kind {
-            MouseEventKind::ScrollUp => {
-                browser.scroll_up();
-            }
-            MouseEventKind::ScrollDown => {
-                browser.scroll_down();
-            }
+        match scroll_up {
+            Some(true) => browser.scroll_up(),
+            Some(false) => browser.scroll_down(),
+            None => {}
+        }
+        return;
+    }
+    
+    if app.popup.is_some() {
+        if clicked {
+            click_popup(app, x, y);
+        }
+        return;
+    }
+    
+    if app.validation_report.is_some() {
+        match scroll_up {
+            Some(true) => app.validation_select_prev(),
+            Some(false) => app.validation_select_next(),
+            None => {}
+        }
+        return;
+    }
+    
+    if app.diff_viewer.is_some() {
+        match scroll_up {
+            Some(true) => app.scroll_diff_up(),
+            Some(false) => app.scroll_diff_down(),
+            None => {}
+        }
+        return;
+    }
+    
+    let areas = &app.hit_areas;
+    let column = if hit(areas.views, x, y) {
+        ActiveColumn::Views
+    } else if hit(areas.actions, x, y) {
+        ActiveColumn::Actions
+    } else if hit(areas.content, x, y) {
+        ActiveColumn::Content
+    } else {
+        return;
+    };
+    let rows = match column {
+        ActiveColumn::Views => &areas.view_rows,
+        ActiveColumn::Actions => &areas.action_rows,
+        ActiveColumn::Content => &areas.content_rows,
+    };
+    let row = rows.iter().find(|(_, rect)| hit(*rect, x, y)).map(|(position, _)| *position);
+    
+    if let Some(up) = scroll_up {
+        // Scroll the list under the pointer
+        app.focus_column(column);
+        if up {
+            app.navigate_up();
+        } else {
+            app.navigate_down();
+        }
+    } else if clicked {
+        app.focus_column(column);
+        match (column, row) {
+            (ActiveColumn::Views, Some(row)) => app.set_selected_view(row),
+            (ActiveColumn::Actions, Some(row)) => app.set_selected_action(row),
+            (ActiveColumn::Content, Some(row)) => app.select_content_row(row),
             _ => {}
         }
     }
 }
 
+fn hit(rect: ratatui::layout::Rect, x: u16, y: u16) -> bool {
+    x >= rect.x && x < rect.x + rect.width && y >= rect.y && y < rect.y + rect.height
+}
+
+/// Pick the clicked button or option, then act as if Enter was pressed
+fn click_popup(app: &mut crate::app::App, x: u16, y: u16) {
+    use crate::popup::Popup;
+    
+    let Some(choice) = app.hit_areas.popup.iter().find(|(_, rect)| hit(*rect, x, y)).map(|(choice, _)| *choice) else {
+        return;
+    };
+    match &mut app.popup {
+        Some(Popup::Confirm { selected, .. }) | Some(Popup::Select { selected, .. }) => *selected = choice,
+        _ => {}
+    }
+    handle_popup_keys(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE), app);
+}
+
 
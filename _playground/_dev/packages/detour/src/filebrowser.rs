@@ -1,9 +1,11 @@
 // File browser for selecting files in the TUI
 
 use std::fs;
+use std::io::Read;
+use std::os::unix::fs::{MetadataExt, PermissionsExt};
 use std::path::{Path, PathBuf};
 use ratatui::{
-    layout::Rect,
+    layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, BorderType, Clear, Paragraph},
@@ -18,13 +20,52 @@ fn hex_color(hex: u32) -> Color {
     )
 }
 
+/// Recent paths kept in the state file
+pub const RECENT_LIMIT: usize = 10;
+/// Bytes read when previewing a file
+const PREVIEW_BYTES: u64 = 16 * 1024;
+/// Jump box candidates shown at most
+const JUMP_LIMIT: usize = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BrowserMode {
+    Browse,
+    /// Typing narrows the entries (opened with `/`)
+    Filter,
+    /// Shorthand path input (`b/f/c` -> `/boot/firmware/config.txt`)
+    Jump,
+    /// Bookmarks followed by recent paths
+    Places,
+}
+
+#[derive(Debug, Clone)]
+pub enum Preview {
+    Text(Vec<String>),
+    /// Metadata lines for files that are not text
+    Binary(Vec<String>),
+    Directory(Vec<String>),
+    Unreadable(String),
+}
+
 #[derive(Debug, Clone)]
 pub struct FileBrowser {
     pub current_dir: PathBuf,
+    /// Entries after the hidden toggle and filter are applied
     pub entries: Vec<FileEntry>,
     pub selected_index: usize,
     pub scroll_offset: usize,
     pub visible_height: usize,
+    all_entries: Vec<FileEntry>,
+    pub show_hidden: bool,
+    pub filter: String,
+    pub mode: BrowserMode,
+    pub jump_input: String,
+    pub jump_matches: Vec<String>,
+    pub bookmarks: Vec<String>,
+    pub recent: Vec<String>,
+    /// Selection in the jump/places list
+    pub list_selected: usize,
+    preview: Option<(PathBuf, Preview)>,
 }
 
 #[derive(Debug, Clone)]
@@ -37,27 +78,40 @@ pub struct FileEntry {
 
 impl FileBrowser {
     pub fn new(start_path: &str) -> Self {
+        Self::with_places(start_path, vec![], vec![])
+    }
+    
+    pub fn with_places(start_path: &str, bookmarks: Vec<String>, recent: Vec<String>) -> Self {
         let mut browser = Self {
             current_dir: PathBuf::from(start_path),
             entries: vec![],
             selected_index: 0,
             scroll_offset: 0,
             visible_height: 20, // Default, will be updated on render
+            all_entries: vec![],
+            show_hidden: false,
+            filter: String::new(),
+            mode: BrowserMode::Browse,
+            jump_input: String::new(),
+            jump_matches: vec![],
+            bookmarks,
+            recent,
+            list_selected: 0,
+            preview: None,
         };
         browser.load_entries();
         browser
     }
     
     pub fn load_entries(&mut self) {
-        self.entries.clear();
-        self.selected_index = 0;
-        self.scroll_offset = 0;
+        self.all_entries.clear();
+        self.filter.clear();
         
         // Add parent directory entry
-        if self.current_dir.parent().is_some() {
-            self.entries.push(FileEntry {
+        if let Some(parent) = self.current_dir.parent() {
+            self.all_entries.push(FileEntry {
                 name: "..".to_string(),
-                path: self.current_dir.parent().unwrap().to_path_buf(),
+                path: parent.to_path_buf(),
                 is_dir: true,
                 size: 0,
             });
@@ -92,8 +146,184 @@ impl FileBrowser {
             files.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
             
             // Add sorted entries
-            self.entries.extend(dirs);
-            self.entries.extend(files);
+            self.all_entries.extend(dirs);
+            self.all_entries.extend(files);
+        }
+        
+        self.apply_filter();
+    }
+    
+    /// Rebuild `entries` from the hidden toggle and type-to-filter text
+    fn apply_filter(&mut self) {
+        let query = self.filter.clone();
+        self.entries = self.all_entries.iter()
+            .filter(|e| e.name == ".." || self.show_hidden || !e.name.starts_with('.'))
+            .filter(|e| query.is_empty() || (e.name != ".." && crate::search::fuzzy_match(&query, &e.name).is_some()))
+            .cloned()
+            .collect();
+        self.selected_index = 0;
+        self.scroll_offset = 0;
+    }
+    
+    pub fn toggle_hidden(&mut self) {
+        let selected = self.get_selected_path();
+        self.show_hidden = !self.show_hidden;
+        self.apply_filter();
+        if let Some(path) = selected {
+            self.select_path(Path::new(&path));
+        }
+    }
+    
+    pub fn filter_push(&mut self, c: char) {
+        self.filter.push(c);
+        self.apply_filter();
+    }
+    
+    pub fn filter_pop(&mut self) {
+        if self.filter.pop().is_some() {
+            self.apply_filter();
+        }
+    }
+    
+    pub fn clear_filter(&mut self) {
+        self.filter.clear();
+        self.apply_filter();
+    }
+    
+    fn select_path(&mut self, path: &Path) {
+        if let Some(i) = self.entries.iter().position(|e| e.path == path) {
+            self.selected_index = i;
+            self.adjust_scroll_to_selection();
+        }
+    }
+    
+    /// Show `path`: a directory is opened, a file is selected in its parent
+    pub fn go_to(&mut self, path: &str) {
+        let path = Path::new(path);
+        self.mode = BrowserMode::Browse;
+        if path.is_dir() {
+            self.current_dir = path.to_path_buf();
+            self.load_entries();
+        } else if let Some(parent) = path.parent() {
+            self.current_dir = parent.to_path_buf();
+            self.load_entries();
+            if path.file_name().is_some_and(|n| n.to_string_lossy().starts_with('.')) && !self.show_hidden {
+                self.show_hidden = true;
+                self.apply_filter();
+            }
+            self.select_path(path);
+        }
+    }
+    
+    pub fn is_bookmarked(&self) -> bool {
+        let dir = self.current_dir.to_string_lossy();
+        self.bookmarks.iter().any(|b| *b == dir)
+    }
+    
+    /// Bookmark or un-bookmark the current directory. Returns true if added
+    pub fn toggle_bookmark(&mut self) -> bool {
+        let dir = self.current_dir.to_string_lossy().to_string();
+        if let Some(i) = self.bookmarks.iter().position(|b| *b == dir) {
+            self.bookmarks.remove(i);
+            false
+        } else {
+            self.bookmarks.push(dir);
+            true
+        }
+    }
+    
+    /// Bookmarks then recent paths, without duplicates
+    pub fn places(&self) -> Vec<(bool, String)> {
+        let mut places: Vec<(bool, String)> = self.bookmarks.iter().map(|b| (true, b.clone())).collect();
+        for path in &self.recent {
+            if !self.bookmarks.contains(path) {
+                places.push((false, path.clone()));
+            }
+        }
+        places
+    }
+    
+    /// Drop the selected bookmark or recent path from the places list,
+    /// returns whether it was a bookmark
+    pub fn remove_place(&mut self) -> Option<bool> {
+        let (is_bookmark, path) = self.places().get(self.list_selected).cloned()?;
+        if is_bookmark {
+            self.bookmarks.retain(|b| *b != path);
+        } else {
+            self.recent.retain(|r| *r != path);
+        }
+        self.list_selected = self.list_selected.min(self.places().len().saturating_sub(1));
+        Some(is_bookmark)
+    }
+    
+    pub fn open_filter(&mut self) {
+        self.mode = BrowserMode::Filter;
+    }
+    
+    pub fn open_places(&mut self) {
+        self.mode = BrowserMode::Places;
+        self.list_selected = 0;
+    }
+    
+    pub fn open_jump(&mut self) {
+        self.mode = BrowserMode::Jump;
+        self.jump_input.clear();
+        self.jump_matches.clear();
+        self.list_selected = 0;
+    }
+    
+    pub fn jump_push(&mut self, c: char) {
+        self.jump_input.push(c);
+        self.update_jump_matches();
+    }
+    
+    pub fn jump_pop(&mut self) {
+        self.jump_input.pop();
+        self.update_jump_matches();
+    }
+    
+    fn update_jump_matches(&mut self) {
+        // Shorthand is always resolved from / (or ~)
+        let input = match self.jump_input.strip_prefix('~') {
+            Some(rest) => format!("{}{}", std::env::var("HOME").unwrap_or_default(), rest),
+            None if self.jump_input.starts_with('/') => self.jump_input.clone(),
+            None => format!("/{}", self.jump_input),
+        };
+        let mut matches = expand_path_shorthand(&input).unwrap_or_default();
+        matches.sort();
+        matches.truncate(JUMP_LIMIT);
+        self.jump_matches = matches;
+        self.list_selected = 0;
+    }
+    
+    fn list_len(&self) -> usize {
+        match self.mode {
+            BrowserMode::Jump => self.jump_matches.len(),
+            BrowserMode::Places => self.places().len(),
+            BrowserMode::Browse | BrowserMode::Filter => 0,
+        }
+    }
+    
+    pub fn list_up(&mut self) {
+        self.list_selected = self.list_selected.saturating_sub(1);
+    }
+    
+    pub fn list_down(&mut self) {
+        if self.list_selected + 1 < self.list_len() {
+            self.list_selected += 1;
+        }
+    }
+    
+    /// Go to the path chosen in the jump/places list
+    pub fn accept_list(&mut self) {
+        let path = match self.mode {
+            BrowserMode::Jump => self.jump_matches.get(self.list_selected).cloned(),
+            BrowserMode::Places => self.places().get(self.list_selected).map(|(_, p)| p.clone()),
+            BrowserMode::Browse | BrowserMode::Filter => None,
+        };
+        match path {
+            Some(path) => self.go_to(&path),
+            None => self.mode = BrowserMode::Browse,
         }
     }
     
@@ -178,13 +408,29 @@ impl FileBrowser {
         })
     }
     
+    /// Preview of the selected entry, cached until the selection changes
+    fn selected_preview(&mut self) -> Option<&Preview> {
+        let entry = self.entries.get(self.selected_index)?;
+        if self.preview.as_ref().is_none_or(|(path, _)| *path != entry.path) {
+            self.preview = Some((entry.path.clone(), build_preview(entry)));
+        }
+        self.preview.as_ref().map(|(_, preview)| preview)
+    }
+    
     pub fn render(&mut self, f: &mut Frame, _full_area: Rect, area: Rect) {
         // No dimming overlay needed - UI elements handle dimming themselves
         // Just clear the browser area to ensure solid background
         f.render_widget(Clear, area);
         
+        let mut title = format!(" File Browser - {} ", self.current_dir.display());
+        if self.is_bookmarked() {
+            title.push_str("★ ");
+        }
+        if self.show_hidden {
+            title.push_str("[hidden shown] ");
+        }
         let block = Block::default()
-            .title(format!(" File Browser - {} ", self.current_dir.display()))
+            .title(title)
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
             .border_style(Style::default().fg(Color::Cyan))
@@ -199,9 +445,57 @@ impl FileBrowser {
         
         f.render_widget(block, area);
         
+        // Entries on the left, preview on the right
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+            .split(Rect { height: inner_area.height.saturating_sub(2), ..inner_area });
+        let list_area = columns[0];
+        
+        match self.mode {
+            BrowserMode::Browse | BrowserMode::Filter => self.render_entries(f, list_area),
+            BrowserMode::Jump | BrowserMode::Places => self.render_pick_list(f, list_area),
+        }
+        self.render_preview(f, columns[1]);
+        
+        // Status line: active filter or jump input
+        let status = match self.mode {
+            BrowserMode::Browse if !self.filter.is_empty() => {
+                format!("Filter: {}  ({} matches)", self.filter, self.entries.iter().filter(|e| e.name != "..").count())
+            }
+            BrowserMode::Browse => String::new(),
+            BrowserMode::Filter => format!("Filter: {}▏  ({} matches)", self.filter, self.entries.iter().filter(|e| e.name != "..").count()),
+            BrowserMode::Jump => format!("Jump: {}▏", self.jump_input),
+            BrowserMode::Places => "Bookmarks & recent paths".to_string(),
+        };
+        f.render_widget(
+            Paragraph::new(Span::styled(status, Style::default().fg(Color::Yellow))),
+            Rect { x: inner_area.x + 1, y: inner_area.y + inner_area.height.saturating_sub(2), width: inner_area.width.saturating_sub(2), height: 1 },
+        );
+        
+        // Help text at bottom
+        let help = match self.mode {
+            BrowserMode::Browse => "[↑↓/jk] Navigate  [Enter] Select/Open  [/] Filter  [Ctrl+A] Hidden  [Ctrl+B] Bookmark  [Ctrl+O] Places  [Ctrl+G] Jump  [Esc] Cancel",
+            BrowserMode::Filter => "[type] Filter  [↑↓] Navigate  [Enter] Keep filter  [Esc] Clear",
+            BrowserMode::Jump => "[type] Shorthand e.g. b/f/c  [↑↓] Choose  [Enter] Go  [Esc] Back",
+            BrowserMode::Places => "[↑↓] Choose  [Enter] Go  [Del] Remove  [Esc] Back",
+        };
+        let help_text = Line::from(Span::styled(help, Style::default().fg(Color::Gray)));
+        
+        let help_area = Rect {
+            x: inner_area.x + 1,
+            y: inner_area.y + inner_area.height.saturating_sub(1),
+            width: inner_area.width.saturating_sub(2),
+            height: 1,
+        };
+        
+        f.render_widget(ratatui::widgets::Paragraph::new(help_text), help_area);
+    }
+    
+    fn render_entries(&mut self, f: &mut Frame, list_area: Rect) {
         // Calculate visible entries
-        let visible_height = inner_area.height.saturating_sub(2) as usize; // Reserve space for help
-        self.visible_height = visible_height;
+        let visible_height = list_area.height as usize;
+        self.visible_height = visible_height.max(1);
         let visible_entries: Vec<ListItem> = self.entries
             .iter()
             .skip(self.scroll_offset)
@@ -249,17 +543,10 @@ impl FileBrowser {
         
         let list = List::new(visible_entries);
         
-        let list_area = Rect {
-            x: inner_area.x,
-            y: inner_area.y,
-            width: inner_area.width,
-            height: inner_area.height.saturating_sub(2),
-        };
-        
         f.render_widget(list, list_area);
         
         // Draw scrollbar if needed
-        if self.entries.len() > visible_height {
+        if self.entries.len() > visible_height && visible_height > 0 {
             let scrollbar_height = list_area.height as usize;
             let total_items = self.entries.len();
             let scrollbar_position = if total_items > scrollbar_height {
@@ -284,23 +571,136 @@ impl FileBrowser {
                 );
             }
         }
-        
-        // Help text at bottom
-        let help_text = Line::from(vec![
-            Span::styled("[↑↓] Navigate  ", Style::default().fg(Color::Gray)),
-            Span::styled("[Enter] Select/Open  ", Style::default().fg(Color::Gray)),
-            Span::styled("[Esc] Cancel", Style::default().fg(Color::Gray)),
-        ]);
-        
-        let help_area = Rect {
-            x: inner_area.x + 1,
-            y: inner_area.y + inner_area.height.saturating_sub(1),
-            width: inner_area.width.saturating_sub(2),
-            height: 1,
+    }
+    
+    /// Jump candidates or places, in place of the directory listing
+    fn render_pick_list(&self, f: &mut Frame, list_area: Rect) {
+        let rows: Vec<(String, String)> = match self.mode {
+            BrowserMode::Jump => self.jump_matches.iter()
+                .map(|p| (if Path::new(p).is_dir() { "📁" } else { "📄" }.to_string(), p.clone()))
+                .collect(),
+            _ => self.places().into_iter()
+                .map(|(is_bookmark, p)| (if is_bookmark { "★" } else { "🕘" }.to_string(), p))
+                .collect(),
         };
         
-        f.render_widget(ratatui::widgets::Paragraph::new(help_text), help_area);
+        let height = list_area.height as usize;
+        let skip = (self.list_selected + 1).saturating_sub(height);
+        let items: Vec<ListItem> = if rows.is_empty() {
+            let empty = if self.mode == BrowserMode::Jump { " No matches" } else { " No bookmarks or recent paths" };
+            vec![ListItem::new(Span::styled(empty, Style::default().fg(Color::DarkGray)))]
+        } else {
+            rows.iter().enumerate().skip(skip).take(height).map(|(i, (icon, path))| {
+                let style = if i == self.list_selected {
+                    Style::default().fg(Color::Black).bg(Color::Cyan)
+                } else {
+                    Style::default().fg(Color::White)
+                };
+                ListItem::new(Line::from(Span::styled(format!(" {} {}", icon, path), style)))
+            }).collect()
+        };
+        f.render_widget(List::new(items), list_area);
+    }
+    
+    fn render_preview(&mut self, f: &mut Frame, area: Rect) {
+        let height = area.height.saturating_sub(2) as usize;
+        let (title, lines, color) = match self.selected_preview() {
+            Some(Preview::Text(lines)) => (" Preview ", lines.iter().take(height).cloned().collect(), Color::Gray),
+            Some(Preview::Binary(lines)) => (" Binary file ", lines.clone(), Color::Yellow),
+            Some(Preview::Directory(names)) => (" Directory ", names.iter().take(height).cloned().collect(), Color::Cyan),
+            Some(Preview::Unreadable(e)) => (" Preview ", vec![e.clone()], Color::Red),
+            None => (" Preview ", vec![], Color::Gray),
+        };
+        let lines: Vec<Line> = lines.into_iter().map(|l| Line::from(Span::styled(l, Style::default().fg(color)))).collect();
+        let block = Block::default()
+            .title(title)
+            .borders(Borders::LEFT)
+            .border_style(Style::default().fg(Color::DarkGray));
+        f.render_widget(Paragraph::new(lines).block(block), area);
+    }
+}
+
+/// Build the preview: text lines, directory listing or metadata
+fn build_preview(entry: &FileEntry) -> Preview {
+    if entry.is_dir {
+        return match fs::read_dir(&entry.path) {
+            Ok(entries) => {
+                let mut names: Vec<String> = entries.flatten().map(|e| {
+                    let name = e.file_name().to_string_lossy().to_string();
+                    if e.path().is_dir() { format!("{}/", name) } else { name }
+                }).collect();
+                names.sort_by_key(|n| n.to_lowercase());
+                Preview::Directory(names)
+            }
+            Err(e) => Preview::Unreadable(format!("Cannot read directory: {}", e)),
+        };
+    }
+    
+    let mut buf = vec![];
+    if let Err(e) = fs::File::open(&entry.path).and_then(|file| file.take(PREVIEW_BYTES).read_to_end(&mut buf)) {
+        return Preview::Unreadable(format!("Cannot read file: {}", e));
+    }
+    
+    // A truncated multi-byte char at the cut is still text
+    let text = match std::str::from_utf8(&buf) {
+        Ok(text) => Some(text),
+        Err(e) if e.error_len().is_none() => std::str::from_utf8(&buf[..e.valid_up_to()]).ok(),
+        Err(_) => None,
+    };
+    match text {
+        Some(text) if !text.contains('\0') => {
+            Preview::Text(text.lines().map(|l| l.replace('\t', "    ")).collect())
+        }
+        _ => Preview::Binary(metadata_lines(&entry.path)),
+    }
+}
+
+fn metadata_lines(path: &Path) -> Vec<String> {
+    let Ok(meta) = fs::metadata(path) else {
+        return vec!["No metadata available".to_string()];
+    };
+    let modified = meta.modified().ok()
+        .map(|t| chrono::DateTime::<chrono::Local>::from(t).format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+    vec![
+        format!("Size:     {} bytes", meta.len()),
+        format!("Mode:     {:o}", meta.permissions().mode() & 0o7777),
+        format!("Owner:    uid {} / gid {}", meta.uid(), meta.gid()),
+        format!("Modified: {}", modified),
+    ]
+}
+
+/// Add `path` to the front of a recent-paths list
+pub fn push_recent(recent: &mut Vec<String>, path: &str) {
+    recent.retain(|p| p != path);
+    recent.insert(0, path.to_string());
+    recent.truncate(RECENT_LIMIT);
+}
+
+/// Recent paths live outside the user-edited config:
+/// `$XDG_STATE_HOME/detour/recent-paths`, falling back to `~/.local/state`
+pub fn recent_paths_file() -> PathBuf {
+    let state_dir = std::env::var_os("XDG_STATE_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(std::env::var("HOME").unwrap_or_default()).join(".local/state"));
+    state_dir.join("detour").join("recent-paths")
+}
+
+/// One path per line, newest first - a missing file is an empty list
+pub fn load_recent(file: &Path) -> Vec<String> {
+    fs::read_to_string(file)
+        .map(|content| content.lines().filter(|l| !l.is_empty()).take(RECENT_LIMIT).map(String::from).collect())
+        .unwrap_or_default()
+}
+
+pub fn save_recent(file: &Path, recent: &[String]) -> std::io::Result<()> {
+    if let Some(dir) = file.parent() {
+        fs::create_dir_all(dir)?;
     }
+    let mut content = recent.join("\n");
+    content.push('\n');
+    fs::write(file, content)
 }
 
 // Path completion utilities
@@ -341,8 +741,9 @@ pub fn expand_path_shorthand(input: &str) -> Option<Vec<String>> {
     let parts: Vec<&str> = input.split('/').collect();
     let mut current_paths = vec![String::from("")];
     
-    for (idx, part) in parts.iter().enumerate() {
-        if part.is_empty() && idx > 0 {
+    for part in &parts {
+        // Leading `/` anchors at root rather than matching every top-level entry
+        if part.is_empty() {
             continue;
         }
         
@@ -379,6 +780,8 @@ pub fn expand_path_shorthand(input: &str) -> Option<Vec<String>> {
         current_paths = next_paths;
     }
     
+    // Input with no components (e.g. "/") matched nothing
+    current_paths.retain(|p| !p.is_empty());
     if current_paths.is_empty() {
         None
     } else {
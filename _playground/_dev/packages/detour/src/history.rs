@@ -0,0 +1,161 @@
+// Git history for custom files in the managed overlay directory
+
+use crate::config::{DetourConfig, EntryKind};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// One commit touching a file
+#[derive(Debug, Clone)]
+pub struct Revision {
+    pub hash: String,
+    pub date: String,
+    pub subject: String,
+}
+
+impl Revision {
+    pub fn short(&self) -> &str {
+        &self.hash[..self.hash.len().min(7)]
+    }
+
+    pub fn label(&self) -> String {
+        format!("{}  {}  {}", self.short(), self.date, self.subject)
+    }
+}
+
+/// Repository rooted at `templates.managed_dir`
+pub struct History {
+    root: PathBuf,
+}
+
+impl History {
+    /// None unless `history.enabled` is set and a managed dir is configured
+    pub fn open(config: &DetourConfig) -> Option<Self> {
+        if !config.history.enabled {
+            return None;
+        }
+        crate::template::managed_dir(&config.templates).map(|root| Self { root })
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn git(&self, args: &[&str]) -> Result<String, String> {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(&self.root)
+            .args(args)
+            .output()
+            .map_err(|e| format!("Failed to execute git: {}", e))?;
+        if output.status.success() {
+            Ok(String::from_utf8_lossy(&output.stdout).to_string())
+        } else {
+            Err(format!("git {} failed: {}", args.first().unwrap_or(&""), String::from_utf8_lossy(&output.stderr).trim()))
+        }
+    }
+
+    fn ensure_repo(&self) -> Result<(), String> {
+        if self.root.join(".git").exists() {
+            return Ok(());
+        }
+        fs::create_dir_all(&self.root)
+            .map_err(|e| format!("Failed to create {}: {}", self.root.display(), e))?;
+        self.git(&["init", "--quiet"]).map(|_| ())
+    }
+
+    /// Path relative to the repo root, or None when `path` lives elsewhere
+    fn relative(&self, path: &Path) -> Option<String> {
+        path.strip_prefix(&self.root).ok()
+            .filter(|rel| !rel.as_os_str().is_empty())
+            .map(|rel| rel.to_string_lossy().to_string())
+    }
+
+    pub fn tracks(&self, path: &str) -> bool {
+        self.relative(Path::new(path)).is_some()
+    }
+
+    /// Commit the current state of `path`. Ok(false) when nothing changed
+    /// or the file is outside the managed dir.
+    pub fn record(&self, path: &str, message: &str) -> Result<bool, String> {
+        let Some(rel) = self.relative(Path::new(path)) else {
+            return Ok(false);
+        };
+        self.ensure_repo()?;
+        self.git(&["add", "--all", "--", &rel])?;
+        if self.git(&["diff", "--cached", "--quiet", "--", &rel]).is_ok() {
+            return Ok(false);
+        }
+        let email = format!("user.email=detour@{}", crate::template::hostname());
+        self.git(&["-c", "user.name=detour", "-c", &email, "commit", "--quiet", "-m", message, "--", &rel])?;
+        Ok(true)
+    }
+
+    /// Commits touching `path`, newest first
+    pub fn log(&self, path: &str) -> Result<Vec<Revision>, String> {
+        let rel = self.relative(Path::new(path))
+            .ok_or_else(|| format!("{} is not inside {}", path, self.root.display()))?;
+        if !self.root.join(".git").exists() {
+            return Ok(vec![]);
+        }
+        // An empty repo has no HEAD to log
+        if self.git(&["rev-parse", "--verify", "--quiet", "HEAD"]).is_err() {
+            return Ok(vec![]);
+        }
+        let out = self.git(&["log", "--format=%H%x09%ad%x09%s", "--date=format:%Y-%m-%d %H:%M", "--", &rel])?;
+        Ok(out.lines()
+            .filter_map(|line| {
+                let mut parts = line.splitn(3, '\t');
+                Some(Revision {
+                    hash: parts.next()?.to_string(),
+                    date: parts.next()?.to_string(),
+                    subject: parts.next().unwrap_or_default().to_string(),
+                })
+            })
+            .collect())
+    }
+
+    /// Contents of `path` at `rev`
+    pub fn show(&self, path: &str, rev: &str) -> Result<String, String> {
+        let rel = self.relative(Path::new(path))
+            .ok_or_else(|| format!("{} is not inside {}", path, self.root.display()))?;
+        self.git(&["show", &format!("{}:{}", rev, rel)])
+    }
+
+    /// Write `path` back to its contents at `rev` and commit the restore
+    pub fn restore(&self, path: &str, rev: &str) -> Result<(), String> {
+        let contents = self.show(path, rev)?;
+        fs::write(path, contents).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+        let short = &rev[..rev.len().min(7)];
+        self.record(path, &format!("Restore {} to {}", path, short)).map(|_| ())
+    }
+}
+
+/// File whose history belongs to the entry: custom file, include file or mirror source
+pub fn entry_path(config: &DetourConfig, id: &str) -> Option<(EntryKind, String)> {
+    match config.find(id)? {
+        (EntryKind::Detour, i) => Some((EntryKind::Detour, config.detours[i].custom.clone())),
+        (EntryKind::Injection, i) => Some((EntryKind::Injection, config.injections[i].include_file.clone())),
+        (EntryKind::Mirror, i) => Some((EntryKind::Mirror, config.mirrors[i].source.clone())),
+        (EntryKind::Service, _) => None,
+    }
+}
+
+/// Commit the entry's file with a message like "Activate detour 1a2b3c4d (/etc/hosts)".
+/// Ok(false) when history is off, the file is unmanaged or unchanged.
+pub fn record_entry(config: &DetourConfig, id: &str, action: &str) -> Result<bool, String> {
+    let Some(history) = History::open(config) else {
+        return Ok(false);
+    };
+    let Some((kind, path)) = entry_path(config, id) else {
+        return Ok(false);
+    };
+    let target = match config.find(id) {
+        Some((EntryKind::Detour, i)) => config.detours[i].original.clone(),
+        Some((EntryKind::Injection, i)) => config.injections[i].target.clone(),
+        Some((EntryKind::Mirror, i)) => config.mirrors[i].target.clone(),
+        _ => String::new(),
+    };
+    let kind = format!("{:?}", kind).to_lowercase();
+    history.record(&path, &format!("{} {} {} ({})", action, kind, id, target))
+}
@@ -0,0 +1,227 @@
+// Per-entry hook commands run around activation and removal
+
+use crate::config::EntryHooks;
+use std::io::Read;
+use std::os::unix::process::CommandExt;
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver};
+use std::time::{Duration, Instant};
+
+pub const DEFAULT_TIMEOUT_SECS: u64 = 30;
+
+/// Lines of hook output kept in the log
+const OUTPUT_LINES: usize = 20;
+
+/// How long to wait for the pipes to drain once the hook has exited or been killed
+const OUTPUT_GRACE: Duration = Duration::from_millis(200);
+
+#[derive(Debug, Clone)]
+pub struct HookRun {
+    pub stage: &'static str,
+    pub command: String,
+    pub success: bool,
+    pub timed_out: bool,
+    /// Combined stdout/stderr, trimmed to the last `OUTPUT_LINES`
+    pub output: String,
+}
+
+impl HookRun {
+    /// Log lines: a summary followed by the indented output
+    pub fn log_lines(&self) -> Vec<String> {
+        let outcome = if self.timed_out {
+            "timed out"
+        } else if self.success {
+            "ok"
+        } else {
+            "failed"
+        };
+        let mut lines = vec![format!("[{}] `{}` {}", self.stage, self.command, outcome)];
+        lines.extend(self.output.lines().map(|l| format!("    {}", l)));
+        lines
+    }
+}
+
+/// Output arrives in chunks until the pipe closes - which a background process
+/// started by the hook can delay indefinitely, so `collect` stops at a deadline
+fn read_pipe(pipe: Option<impl Read + Send + 'static>) -> Receiver<Vec<u8>> {
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let Some(mut pipe) = pipe else { return };
+        let mut buf = [0u8; 4096];
+        while let Ok(n) = pipe.read(&mut buf) {
+            if n == 0 || tx.send(buf[..n].to_vec()).is_err() {
+                break;
+            }
+        }
+    });
+    rx
+}
+
+/// Output received by `deadline`, or until the pipe closed if that came first
+fn collect(pipe: &Receiver<Vec<u8>>, deadline: Instant) -> String {
+    let mut out = Vec::new();
+    while let Ok(chunk) = pipe.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+        out.extend(chunk);
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// SIGKILL every process in the hook's group, not just `sh`
+fn kill_group(child: &mut std::process::Child) {
+    // The group id is the child's pid, see process_group(0) in `run`
+    let pgid = child.id() as libc::pid_t;
+    unsafe {
+        libc::kill(-pgid, libc::SIGKILL);
+    }
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+/// Run `command` with `sh -c` in its own process group, killing the group after `timeout`
+pub fn run(stage: &'static str, command: &str, timeout: Duration) -> HookRun {
+    let mut run = HookRun { stage, command: command.to_string(), success: false, timed_out: false, output: String::new() };
+    let mut child = match Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .process_group(0)
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => {
+            run.output = format!("Failed to execute hook: {}", e);
+            return run;
+        }
+    };
+
+    // Drain pipes on threads so a chatty hook can't block on a full pipe
+    let stdout = read_pipe(child.stdout.take());
+    let stderr = read_pipe(child.stderr.take());
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break Some(status),
+            Ok(None) if Instant::now() >= deadline => {
+                kill_group(&mut child);
+                run.timed_out = true;
+                break None;
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(50)),
+            Err(_) => break None,
+        }
+    };
+
+    // Background processes left by the hook may keep the pipes open - don't wait for them
+    let output_deadline = Instant::now() + OUTPUT_GRACE;
+    let combined = format!("{}{}", collect(&stdout, output_deadline), collect(&stderr, output_deadline));
+    let lines: Vec<&str> = combined.trim_end().lines().collect();
+    run.output = lines[lines.len().saturating_sub(OUTPUT_LINES)..].join("\n");
+    run.success = status.is_some_and(|s| s.success());
+    run
+}
+
+impl EntryHooks {
+    fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout.unwrap_or(DEFAULT_TIMEOUT_SECS))
+    }
+}
+
+/// Append hook log lines to an activation message
+fn with_log(message: String, runs: &[HookRun]) -> String {
+    let mut lines = vec![message];
+    lines.extend(runs.iter().flat_map(|r| r.log_lines()));
+    lines.join("\n")
+}
+
+/// pre_apply → apply → validate → post_apply. A failing pre_apply aborts;
+/// a failing validate rolls the change back.
+pub fn apply(
+    hooks: Option<&EntryHooks>,
+    apply: impl FnOnce() -> Result<String, String>,
+    rollback: impl FnOnce() -> Result<String, String>,
+) -> Result<String, String> {
+    let Some(hooks) = hooks else {
+        return apply();
+    };
+    let mut runs = vec![];
+
+    if let Some(command) = &hooks.pre_apply {
+        let run = run("pre_apply", command, hooks.timeout());
+        let ok = run.success;
+        runs.push(run);
+        if !ok {
+            return Err(with_log("pre_apply hook failed, not applied".to_string(), &runs));
+        }
+    }
+
+    let message = apply().map_err(|e| with_log(e, &runs))?;
+
+    if let Some(command) = &hooks.validate {
+        let run = run("validate", command, hooks.timeout());
+        let ok = run.success;
+        runs.push(run);
+        if !ok {
+            let undo = match rollback() {
+                Ok(_) => "rolled back".to_string(),
+                Err(e) => format!("rollback failed: {}", e),
+            };
+            return Err(with_log(format!("validate hook failed, {}", undo), &runs));
+        }
+    }
+
+    // The change stays in place even if post_apply fails; it's only logged
+    if let Some(command) = &hooks.post_apply {
+        runs.push(run("post_apply", command, hooks.timeout()));
+    }
+    Ok(with_log(message, &runs))
+}
+
+/// validate → post_apply for an applied entry whose file was just edited.
+/// Nothing is rolled back; a failing validate skips post_apply.
+pub fn changed(hooks: Option<&EntryHooks>, message: String) -> Result<String, String> {
+    let Some(hooks) = hooks else {
+        return Ok(message);
+    };
+    let mut runs = vec![];
+
+    if let Some(command) = &hooks.validate {
+        let run = run("validate", command, hooks.timeout());
+        let ok = run.success;
+        runs.push(run);
+        if !ok {
+            return Err(with_log("validate hook failed after edit - the change is live".to_string(), &runs));
+        }
+    }
+
+    if let Some(command) = &hooks.post_apply {
+        runs.push(run("post_apply", command, hooks.timeout()));
+    }
+    Ok(with_log(message, &runs))
+}
+
+/// pre_remove → remove → post_remove. A failing pre_remove aborts.
+pub fn remove(hooks: Option<&EntryHooks>, remove: impl FnOnce() -> Result<String, String>) -> Result<String, String> {
+    let Some(hooks) = hooks else {
+        return remove();
+    };
+    let mut runs = vec![];
+
+    if let Some(command) = &hooks.pre_remove {
+        let run = run("pre_remove", command, hooks.timeout());
+        let ok = run.success;
+        runs.push(run);
+        if !ok {
+            return Err(with_log("pre_remove hook failed, not removed".to_string(), &runs));
+        }
+    }
+
+    let message = remove().map_err(|e| with_log(e, &runs))?;
+
+    if let Some(command) = &hooks.post_remove {
+        runs.push(run("post_remove", command, hooks.timeout()));
+    }
+    Ok(with_log(message, &runs))
+}
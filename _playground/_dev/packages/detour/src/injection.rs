@@ -1,10 +1,11 @@
 // Injection operations - file content injection
 
+use crate::backend::Backend;
 use std::path::Path;
-use std::fs;
-use std::process::Command;
 
-pub struct InjectionManager;
+pub struct InjectionManager {
+    backend: Backend,
+}
 
 #[derive(Debug, Clone, Copy)]
 enum FileType {
@@ -18,49 +19,29 @@ enum FileType {
 
 impl InjectionManager {
     pub fn new() -> Self {
-        Self
+        Self::with_backend(Backend::system())
+    }
+
+    pub fn with_backend(backend: Backend) -> Self {
+        Self { backend }
     }
 
     // Write file with automatic sudo fallback on permission denied
-    fn write_file_with_sudo(target: &Path, contents: &str) -> Result<(), String> {
+    fn write_file_with_sudo(&self, target: &Path, contents: &str) -> Result<(), String> {
         // Try normal write first
-        match fs::write(target, contents) {
+        match self.backend.fs.write(target, contents) {
             Ok(_) => Ok(()),
             Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
-                // Permission denied - write to temp file, then use sudo to copy it
-                let target_str = target.to_string_lossy().to_string();
-                let temp_file = format!("/tmp/detour_include_{}", std::process::id());
-                
-                // Write to temp file
-                fs::write(&temp_file, contents)
-                    .map_err(|e| format!("Failed to write temp file: {}", e))?;
-                
-                // Use sudo cp to copy temp file to target
-                let output = Command::new("sudo")
-                    .arg("cp")
-                    .arg(&temp_file)
-                    .arg(&target_str)
-                    .output()
-                    .map_err(|e| format!("Failed to execute sudo cp: {}", e))?;
-
-                // Clean up temp file
-                let _ = fs::remove_file(&temp_file);
-
-                if !output.status.success() {
-                    let stderr = String::from_utf8_lossy(&output.stderr);
-                    return Err(format!("Failed to write file with sudo: {}", stderr));
-                }
-
-                Ok(())
+                self.backend.privileged.write(target, contents)
             }
             Err(e) => Err(format!("Failed to write target file: {}", e))
         }
     }
 
-    fn get_file_type(target: &Path) -> FileType {
+    fn get_file_type(&self, target: &Path) -> FileType {
         // First, try to detect by existing include directives in the file
-        if target.exists() {
-            if let Ok(content) = fs::read_to_string(target) {
+        if self.backend.fs.exists(target) {
+            if let Ok(content) = self.backend.fs.read_to_string(target) {
                 // Check for existing include patterns in the file
                 for line in content.lines().take(100) {
                     let trimmed = line.trim();
@@ -100,8 +81,8 @@ impl InjectionManager {
         }
 
         // Last resort: content-based heuristics (only if file exists and no includes found)
-        if target.exists() {
-            if let Ok(content) = fs::read_to_string(target) {
+        if self.backend.fs.exists(target) {
+            if let Ok(content) = self.backend.fs.read_to_string(target) {
                 let sample = content.lines().take(20).collect::<Vec<_>>().join("\n").to_lowercase();
                 
                 // Raspberry Pi config.txt heuristics
@@ -128,11 +109,11 @@ impl InjectionManager {
 
     pub fn apply(&self, target: &Path, include: &Path) -> Result<(), String> {
         // Verify include file exists
-        if !include.exists() {
+        if !self.backend.fs.exists(include) {
             return Err(format!("Include file does not exist: {}", include.display()));
         }
 
-        let file_type = Self::get_file_type(target);
+        let file_type = self.get_file_type(target);
         let include_relative = self.get_relative_path(target, include)?;
 
         match file_type {
@@ -148,11 +129,11 @@ impl InjectionManager {
     }
 
     pub fn remove(&self, target: &Path, include: &Path) -> Result<(), String> {
-        if !target.exists() {
+        if !self.backend.fs.exists(target) {
             return Ok(()); // Nothing to remove
         }
 
-        let file_type = Self::get_file_type(target);
+        let file_type = self.get_file_type(target);
         let include_relative = self.get_relative_path(target, include)?;
 
         match file_type {
@@ -166,16 +147,28 @@ impl InjectionManager {
         }
     }
 
+    /// Check whether the target currently references the include file
+    pub fn is_applied(&self, target: &Path, include: &Path) -> bool {
+        let content = match self.backend.fs.read_to_string(target) {
+            Ok(content) => content,
+            Err(_) => return false,
+        };
+        match self.get_relative_path(target, include) {
+            Ok(include_relative) => content.contains(&include_relative),
+            Err(_) => content.contains(&include.to_string_lossy().to_string()),
+        }
+    }
+
     fn get_relative_path(&self, target: &Path, include: &Path) -> Result<String, String> {
         // Try to get canonical paths
         let target_dir = target.parent()
-            .and_then(|p| p.canonicalize().ok())
+            .and_then(|p| self.backend.fs.canonicalize(p).ok())
             .ok_or_else(|| "Cannot determine target directory".to_string())?;
         
-        let include_path = include.canonicalize().or_else(|_| {
+        let include_path = self.backend.fs.canonicalize(include).or_else(|_| {
             include.file_name()
                 .and_then(|name| include.parent().map(|p| p.join(name)))
-                .and_then(|p| p.canonicalize().ok())
+                .and_then(|p| self.backend.fs.canonicalize(&p).ok())
                 .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "Include file not found"))
         })
         .map_err(|_| format!("Cannot resolve include file: {}", include.display()))?;
@@ -186,7 +179,7 @@ impl InjectionManager {
             Ok(relative.to_string_lossy().to_string())
         } else {
             // Fall back to just the file name if in same directory, or absolute path
-            if target_dir == include_path.parent().and_then(|p| p.canonicalize().ok()).unwrap_or_default() {
+            if target_dir == include_path.parent().and_then(|p| self.backend.fs.canonicalize(p).ok()).unwrap_or_default() {
                 Ok(include.file_name()
                     .and_then(|n| n.to_str())
                     .unwrap_or("")
@@ -203,13 +196,13 @@ impl InjectionManager {
         use serde_yaml::{Value, Mapping};
         
         // Read target file or create empty YAML
-        let target_content = if target.exists() {
-            fs::read_to_string(target)
+        let target_content = if self.backend.fs.exists(target) {
+            self.backend.fs.read_to_string(target)
                 .map_err(|e| format!("Failed to read target file: {}", e))?
         } else {
             // Create directory if needed
             if let Some(parent) = target.parent() {
-                fs::create_dir_all(parent)
+                self.backend.fs.create_dir_all(parent)
                     .map_err(|e| format!("Failed to create target directory: {}", e))?;
             }
             String::new()
@@ -235,7 +228,7 @@ impl InjectionManager {
         let updated_content = serde_yaml::to_string(&config)
             .map_err(|e| format!("Failed to serialize YAML: {}", e))?;
         
-        Self::write_file_with_sudo(target, &updated_content)?;
+        self.write_file_with_sudo(target, &updated_content)?;
 
         Ok(())
     }
@@ -243,7 +236,7 @@ impl InjectionManager {
     fn remove_yaml(&self, target: &Path, _include: &Path, include_path: &str) -> Result<(), String> {
         use serde_yaml::Value;
         
-        let target_content = fs::read_to_string(target)
+        let target_content = self.backend.fs.read_to_string(target)
             .map_err(|e| format!("Failed to read target file: {}", e))?;
 
         if target_content.trim().is_empty() {
@@ -262,7 +255,7 @@ impl InjectionManager {
         let updated_content = serde_yaml::to_string(&config)
             .map_err(|e| format!("Failed to serialize YAML: {}", e))?;
         
-        Self::write_file_with_sudo(target, &updated_content)?;
+        self.write_file_with_sudo(target, &updated_content)?;
 
         Ok(())
     }
@@ -359,12 +352,12 @@ impl InjectionManager {
 
     // C/C++ header file handlers
     fn apply_cheader(&self, target: &Path, _include: &Path, include_path: &str) -> Result<(), String> {
-        let target_content = if target.exists() {
-            fs::read_to_string(target)
+        let target_content = if self.backend.fs.exists(target) {
+            self.backend.fs.read_to_string(target)
                 .map_err(|e| format!("Failed to read target file: {}", e))?
         } else {
             if let Some(parent) = target.parent() {
-                fs::create_dir_all(parent)
+                self.backend.fs.create_dir_all(parent)
                     .map_err(|e| format!("Failed to create target directory: {}", e))?;
             }
             String::new()
@@ -390,13 +383,13 @@ impl InjectionManager {
 
         lines.insert(insert_pos, &include_line);
         
-        Self::write_file_with_sudo(target, &lines.join("\n"))?;
+        self.write_file_with_sudo(target, &lines.join("\n"))?;
 
         Ok(())
     }
 
     fn remove_cheader(&self, target: &Path, _include: &Path, include_path: &str) -> Result<(), String> {
-        let target_content = fs::read_to_string(target)
+        let target_content = self.backend.fs.read_to_string(target)
             .map_err(|e| format!("Failed to read target file: {}", e))?;
 
         let include_line = format!("#include \"{}\"", include_path);
@@ -405,19 +398,19 @@ impl InjectionManager {
             .filter(|line| line.trim() != include_line)
             .collect();
 
-        Self::write_file_with_sudo(target, &lines.join("\n"))?;
+        self.write_file_with_sudo(target, &lines.join("\n"))?;
 
         Ok(())
     }
 
     // Shell script handlers
     fn apply_shell(&self, target: &Path, _include: &Path, include_path: &str) -> Result<(), String> {
-        let target_content = if target.exists() {
-            fs::read_to_string(target)
+        let target_content = if self.backend.fs.exists(target) {
+            self.backend.fs.read_to_string(target)
                 .map_err(|e| format!("Failed to read target file: {}", e))?
         } else {
             if let Some(parent) = target.parent() {
-                fs::create_dir_all(parent)
+                self.backend.fs.create_dir_all(parent)
                     .map_err(|e| format!("Failed to create target directory: {}", e))?;
             }
             String::new()
@@ -445,13 +438,13 @@ impl InjectionManager {
 
         lines.insert(insert_pos, &source_line);
         
-        Self::write_file_with_sudo(target, &lines.join("\n"))?;
+        self.write_file_with_sudo(target, &lines.join("\n"))?;
 
         Ok(())
     }
 
     fn remove_shell(&self, target: &Path, _include: &Path, include_path: &str) -> Result<(), String> {
-        let target_content = fs::read_to_string(target)
+        let target_content = self.backend.fs.read_to_string(target)
             .map_err(|e| format!("Failed to read target file: {}", e))?;
 
         let source_line = format!("source \"{}\"", include_path);
@@ -465,19 +458,19 @@ impl InjectionManager {
             })
             .collect();
 
-        Self::write_file_with_sudo(target, &lines.join("\n"))?;
+        self.write_file_with_sudo(target, &lines.join("\n"))?;
 
         Ok(())
     }
 
     // Raspberry Pi config.txt handlers
     fn apply_rpi_config(&self, target: &Path, _include: &Path, include_path: &str) -> Result<(), String> {
-        let target_content = if target.exists() {
-            fs::read_to_string(target)
+        let target_content = if self.backend.fs.exists(target) {
+            self.backend.fs.read_to_string(target)
                 .map_err(|e| format!("Failed to read target file: {}", e))?
         } else {
             if let Some(parent) = target.parent() {
-                fs::create_dir_all(parent)
+                self.backend.fs.create_dir_all(parent)
                     .map_err(|e| format!("Failed to create target directory: {}", e))?;
             }
             String::new()
@@ -501,13 +494,13 @@ impl InjectionManager {
             format!("{}\n{}", target_content, wrapper_block)
         };
 
-        Self::write_file_with_sudo(target, &new_content)?;
+        self.write_file_with_sudo(target, &new_content)?;
 
         Ok(())
     }
 
     fn remove_rpi_config(&self, target: &Path, _include: &Path, include_path: &str) -> Result<(), String> {
-        let target_content = fs::read_to_string(target)
+        let target_content = self.backend.fs.read_to_string(target)
             .map_err(|e| format!("Failed to read target file: {}", e))?;
 
         // Build the wrapper pattern to find
@@ -549,7 +542,7 @@ impl InjectionManager {
             filtered_lines.join("\n") + "\n"
         };
 
-        Self::write_file_with_sudo(target, &new_content)?;
+        self.write_file_with_sudo(target, &new_content)?;
 
         Ok(())
     }
@@ -557,12 +550,12 @@ impl InjectionManager {
     // Generic/config file handlers
     fn apply_generic(&self, target: &Path, _include: &Path, include_path: &str) -> Result<(), String> {
         // For unknown files, try to append a comment-based include
-        let target_content = if target.exists() {
-            fs::read_to_string(target)
+        let target_content = if self.backend.fs.exists(target) {
+            self.backend.fs.read_to_string(target)
                 .map_err(|e| format!("Failed to read target file: {}", e))?
         } else {
             if let Some(parent) = target.parent() {
-                fs::create_dir_all(parent)
+                self.backend.fs.create_dir_all(parent)
                     .map_err(|e| format!("Failed to create target directory: {}", e))?;
             }
             String::new()
@@ -580,13 +573,13 @@ impl InjectionManager {
             format!("{}\n# Include: {}\n", target_content, include_path)
         };
 
-        Self::write_file_with_sudo(target, &new_content)?;
+        self.write_file_with_sudo(target, &new_content)?;
 
         Ok(())
     }
 
     fn remove_generic(&self, target: &Path, _include: &Path, include_path: &str) -> Result<(), String> {
-        let target_content = fs::read_to_string(target)
+        let target_content = self.backend.fs.read_to_string(target)
             .map_err(|e| format!("Failed to read target file: {}", e))?;
 
         // Remove lines containing the include path in comments
@@ -595,7 +588,7 @@ impl InjectionManager {
             .filter(|line| !line.contains(&include_path))
             .collect();
 
-        Self::write_file_with_sudo(target, &lines.join("\n"))?;
+        self.write_file_with_sudo(target, &lines.join("\n"))?;
 
         Ok(())
     }
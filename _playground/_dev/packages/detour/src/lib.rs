@@ -8,6 +8,7 @@ pub mod ui;
 pub mod popup;
 pub mod diff;
 pub mod filebrowser;
+pub mod config_editor;
 
 // Core functionality modules
 pub mod config;
@@ -18,5 +19,18 @@ pub mod components;
 pub mod forms;
 pub mod operations;
 pub mod validation;
+pub mod status;
+pub mod checks;
+pub mod search;
+pub mod template;
+pub mod bundle;
+pub mod history;
+pub mod scope;
+pub mod conditions;
+pub mod hooks;
+pub mod package;
+pub mod editor;
+pub mod secrets;
 // pub mod service;  // TODO: Implement
 
+pub mod backend;
@@ -11,7 +11,7 @@ use ratatui::{backend::CrosstermBackend, Terminal};
 use std::io;
 use std::process::Command;
 
-use detour::{app, events, ui};
+use detour::{app, bundle, checks, conditions, config, editor, events, injection, manager, mirror, operations, status, ui};
 
 #[derive(Parser)]
 #[command(name = "detour")]
@@ -38,6 +38,59 @@ enum Commands {
     /// Run build (release) then start TUI
     #[command(alias = "rbr")]
     RunBuildRelease,
+    
+    /// Show system status (exits non-zero when anything needs attention)
+    #[command(alias = "st")]
+    Status {
+        /// Emit machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
+    
+    /// List entries with their ids
+    #[command(alias = "ls")]
+    List,
+    
+    /// Activate an entry by id
+    Activate {
+        id: String,
+    },
+    
+    /// Deactivate an entry by id
+    Deactivate {
+        id: String,
+    },
+    
+    /// Run all validation checks (exits non-zero on errors)
+    #[command(alias = "v")]
+    Validate {
+        /// Emit machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
+    
+    /// Export config and all referenced files to a portable bundle
+    Export {
+        /// Output path (default: detour-bundle-<date>.tar.gz)
+        output: Option<String>,
+    },
+    
+    /// Import a bundle, previewing every change before writing
+    Import {
+        bundle: String,
+        /// Rewrite a path prefix, e.g. --map /home/alice=/home/bob (repeatable)
+        #[arg(long = "map", value_name = "FROM=TO")]
+        maps: Vec<String>,
+        /// Show the preview and exit
+        #[arg(long)]
+        dry_run: bool,
+        /// Replace conflicting files and entries instead of skipping them
+        #[arg(long)]
+        overwrite: bool,
+        /// Apply without asking
+        #[arg(short, long)]
+        yes: bool,
+    },
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -96,6 +149,73 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             // Launch TUI
             run_tui()
         }
+        Some(Commands::Status { json }) => {
+            let report = status::StatusReport::collect(&config::DetourConfig::get_config_path());
+            if json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else {
+                println!("{}", report.to_text());
+            }
+            
+            std::process::exit(if report.is_healthy() { 0 } else { 1 });
+        }
+        Some(Commands::List) => {
+            let config = operations::config_ops::load_config(&config::DetourConfig::get_config_path());
+            let detour_manager = manager::DetourManager::new();
+            let injection_manager = injection::InjectionManager::new();
+            let mirror_manager = mirror::MirrorManager::new();
+            let mark = |active: bool| if active { "✓" } else { "○" };
+            let host = conditions::Host::detect();
+            let skipped = |when: Option<&config::When>| conditions::skip_reason(when, &host)
+                .map(|r| format!("  (skipped: {})", r))
+                .unwrap_or_default();
+            
+            for e in &config.detours {
+                let scope = e.scope.as_ref().map(|s| format!("  [{}]", s.label()))
+                    .unwrap_or_else(|| if e.readonly { "  [ro]".to_string() } else { String::new() });
+                println!("{}  detour     {} {} ← {}{}{}", e.id, mark(detour_manager.is_entry_active(&e.id, &e.original, e.scope.as_ref())), e.original, e.custom, scope, skipped(e.when.as_ref()));
+            }
+            for e in &config.injections {
+                let applied = injection_manager.is_applied(std::path::Path::new(&e.target), std::path::Path::new(&e.injected_file()));
+                println!("{}  injection  {} {} ← {}{}", e.id, mark(applied), e.target, e.include_file, skipped(e.when.as_ref()));
+            }
+            for e in &config.mirrors {
+                println!("{}  mirror     {} {} → {}{}", e.id, mark(mirror_manager.is_active(&e.source, &e.target)), e.source, e.target, skipped(e.when.as_ref()));
+            }
+            for e in &config.services {
+                println!("{}  service      {} ({})", e.id, e.name, e.action);
+            }
+            Ok(())
+        }
+        Some(Commands::Activate { id }) => run_activation(&id, true),
+        Some(Commands::Deactivate { id }) => run_activation(&id, false),
+        Some(Commands::Validate { json }) => {
+            let config_path = config::DetourConfig::get_config_path();
+            let config = config::DetourConfig::parse(&config_path)?;
+            let report = checks::CheckReport::run(&config, &checks::Scope::all());
+            
+            if json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else {
+                println!("{}", report.to_text());
+            }
+            
+            std::process::exit(if report.has_errors() { 1 } else { 0 });
+        }
+        Some(Commands::Export { output }) => {
+            let output = output.unwrap_or_else(|| {
+                format!("detour-bundle-{}.tar.gz", chrono::Local::now().format("%Y%m%d-%H%M%S"))
+            });
+            let summary = bundle::export(&config::DetourConfig::get_config_path(), std::path::Path::new(&output))?;
+            println!("Exported {} files ({} bytes) to {}", summary.files, summary.bytes, output);
+            for path in &summary.missing {
+                eprintln!("Warning: {} does not exist, not bundled", path);
+            }
+            Ok(())
+        }
+        Some(Commands::Import { bundle: path, maps, dry_run, overwrite, yes }) => {
+            run_import(&path, &maps, dry_run, overwrite, yes)
+        }
         None => {
             // No subcommand - launch TUI
             run_tui()
@@ -103,6 +223,72 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
 }
 
+fn run_activation(id: &str, activate: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let config = operations::config_ops::load_config(&config::DetourConfig::get_config_path());
+    let result = if activate {
+        operations::activation::activate(&config, id)
+    } else {
+        operations::activation::deactivate(&config, id)
+    };
+    
+    match result {
+        Ok(msg) => {
+            println!("{}", msg);
+            Ok(())
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn run_import(path: &str, maps: &[String], dry_run: bool, overwrite: bool, yes: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let remaps = maps.iter().map(|m| bundle::Remap::parse(m)).collect::<Result<Vec<_>, _>>()?;
+    let config_path = config::DetourConfig::get_config_path();
+    let plan = bundle::ImportPlan::prepare(std::path::Path::new(path), &remaps, &config_path)?;
+    
+    println!("{}", plan.preview());
+    if plan.has_conflicts() && !overwrite {
+        println!("\nConflicts will be skipped (use --overwrite to replace them)");
+    }
+    if dry_run {
+        return Ok(());
+    }
+    
+    if !yes {
+        print!("\nApply? [y/N] ");
+        io::Write::flush(&mut io::stdout())?;
+        let mut answer = String::new();
+        io::stdin().read_line(&mut answer)?;
+        if !matches!(answer.trim(), "y" | "Y" | "yes") {
+            println!("Aborted");
+            return Ok(());
+        }
+    }
+    
+    let summary = plan.apply(&config_path, overwrite)?;
+    println!("Wrote {} files, merged {} entries", summary.files_written, summary.entries_added);
+    for skipped in &summary.skipped {
+        println!("Skipped: {}", skipped);
+    }
+    Ok(())
+}
+
+/// Leave the alternate screen and raw mode while the editor owns the terminal
+fn run_editor(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, path: &str) -> io::Result<Result<(), String>> {
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen, DisableMouseCapture)?;
+    terminal.show_cursor()?;
+    
+    let result = editor::run(path);
+    
+    enable_raw_mode()?;
+    execute!(terminal.backend_mut(), EnterAlternateScreen, EnableMouseCapture)?;
+    terminal.clear()?;
+    Ok(result)
+}
+
 fn run_tui() -> Result<(), Box<dyn std::error::Error>> {
     // Setup terminal
     enable_raw_mode()?;
@@ -120,6 +306,11 @@ fn run_tui() -> Result<(), Box<dyn std::error::Error>> {
         
         events::handle_events(&mut app)?;
         
+        if let Some(edit) = app.pending_editor.take() {
+            let result = run_editor(&mut terminal, &edit.path)?;
+            app.finish_edit(edit, result);
+        }
+        
         if app.should_quit {
             break;
         }
@@ -1,62 +1,100 @@
 // Detour operations - bind mount management
 
-use std::process::Command;
+use crate::backend::Backend;
+use crate::config::{DetourEntry, DetourScope};
 use std::fs;
+use std::path::Path;
 
-pub struct DetourManager;
+pub struct DetourManager {
+    backend: Backend,
+}
 
 impl DetourManager {
     pub fn new() -> Self {
-        Self
+        Self::with_backend(Backend::system())
+    }
+    
+    pub fn with_backend(backend: Backend) -> Self {
+        Self { backend }
     }
     
-    pub fn apply_detour(&self, original: &str, custom: &str) -> Result<String, String> {
+    pub fn apply_detour(&self, original: &str, custom: &str, readonly: bool) -> Result<String, String> {
         // Create a bind mount for a specific detour
-        let output = Command::new("sudo")
-            .arg("mount")
-            .arg("--bind")
-            .arg(custom)
-            .arg(original)
-            .output()
-            .map_err(|e| format!("Failed to mount detour: {}", e))?;
+        self.backend.mounts.bind(custom, original)?;
+        if !readonly {
+            return Ok(format!("Mounted {} → {}", custom, original));
+        }
         
-        if output.status.success() {
-            Ok(format!("Mounted {} → {}", custom, original))
-        } else {
-            Err(String::from_utf8_lossy(&output.stderr).to_string())
+        if let Err(e) = self.backend.mounts.remount_read_only(original) {
+            let _ = self.remove_detour(original);
+            return Err(format!("Read-only remount failed: {}", e.trim()));
         }
+        Ok(format!("Mounted {} → {} (read-only)", custom, original))
     }
     
     pub fn remove_detour(&self, original: &str) -> Result<String, String> {
         // Remove a bind mount for a specific detour
-        let output = Command::new("sudo")
-            .arg("umount")
-            .arg(original)
-            .output()
-            .map_err(|e| format!("Failed to unmount detour: {}", e))?;
-        
-        if output.status.success() {
-            Ok(format!("Unmounted {}", original))
-        } else {
-            Err(String::from_utf8_lossy(&output.stderr).to_string())
-        }
+        self.backend.mounts.unmount(original)?;
+        Ok(format!("Unmounted {}", original))
     }
     
     
     pub fn is_active(&self, original: &str) -> bool {
         // Check if specific detour is mounted
-        let output = Command::new("mount")
-            .output()
-            .ok();
+        self.backend.mounts.is_mounted(original)
+    }
+    
+    /// Global bind mount, or a unit/namespace overlay when `scope` is set
+    pub fn apply_entry(&self, entry: &DetourEntry) -> Result<String, String> {
+        let mut notes = vec![];
+        if let Some(mismatch) = self.permission_mismatch(&entry.original, &entry.custom) {
+            if entry.match_permissions {
+                self.backend.privileged.copy_owner_mode(&entry.original, &entry.custom)?;
+                notes.push(format!("Matched {} to {} ({})", entry.custom, entry.original, mismatch));
+            } else {
+                notes.push(format!("⚠ {}", mismatch));
+            }
+        }
         
-        if let Some(output) = output {
-            let mount_output = String::from_utf8_lossy(&output.stdout);
-            mount_output.contains(original)
-        } else {
-            false
+        let message = match &entry.scope {
+            Some(scope) => {
+                let scope = DetourScope { read_only: scope.read_only || entry.readonly, ..scope.clone() };
+                crate::scope::apply(&entry.id, &entry.original, &entry.custom, &scope)?
+            }
+            None => self.apply_detour(&entry.original, &entry.custom, entry.readonly)?,
+        };
+        notes.insert(0, message);
+        Ok(notes.join("\n"))
+    }
+    
+    pub fn remove_entry(&self, id: &str, original: &str, scope: Option<&DetourScope>) -> Result<String, String> {
+        match scope {
+            Some(scope) => crate::scope::remove(id, scope),
+            None => self.remove_detour(original),
+        }
+    }
+    
+    /// Scoped detours count as active once their drop-in/namespace exists
+    pub fn is_entry_active(&self, id: &str, original: &str, scope: Option<&DetourScope>) -> bool {
+        match scope {
+            Some(scope) => crate::scope::state(id, original, scope) != crate::scope::ScopeState::Inactive,
+            None => self.is_active(original),
         }
     }
     
+    /// "original is 0:0 0644, custom is 1000:1000 0600" when `custom` differs from `original`
+    pub fn permission_mismatch(&self, original: &str, custom: &str) -> Option<String> {
+        let describe = |(uid, gid, mode): (u32, u32, u32)| format!("{}:{} {:04o}", uid, gid, mode);
+        let expected = describe(self.backend.fs.owner_mode(Path::new(original))?);
+        let actual = describe(self.backend.fs.owner_mode(Path::new(custom))?);
+        (expected != actual).then(|| format!("original is {}, custom is {}", expected, actual))
+    }
+    
+    /// Give `custom` the owner, group and mode of `original`
+    pub fn match_permissions(&self, original: &str, custom: &str) -> Result<(), String> {
+        self.backend.privileged.copy_owner_mode(original, custom)
+    }
+    
     pub fn get_file_info(&self, path: &str) -> Option<FileInfo> {
         let metadata = fs::metadata(path).ok()?;
         let size = metadata.len();
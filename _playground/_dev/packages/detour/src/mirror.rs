@@ -1,51 +1,56 @@
 // Mirror operations - symlink management
 
-use std::fs;
+use crate::backend::Backend;
 use std::path::Path;
 
-pub struct MirrorManager;
+pub struct MirrorManager {
+    backend: Backend,
+}
 
 impl MirrorManager {
     pub fn new() -> Self {
-        Self
+        Self::with_backend(Backend::system())
+    }
+    
+    pub fn with_backend(backend: Backend) -> Self {
+        Self { backend }
     }
     
     /// Create a symlink from source to target
     pub fn apply_mirror(&self, source: &str, target: &str) -> Result<String, String> {
+        let fs = &self.backend.fs;
         let source_path = Path::new(source);
         let target_path = Path::new(target);
         
         // Check if source exists
-        if !source_path.exists() {
+        if !fs.exists(source_path) {
             return Err(format!("Source path does not exist: {}", source));
         }
         
-        // Check if target already exists
-        if target_path.exists() {
+        // Check if target already exists (a dangling symlink counts)
+        if fs.is_symlink(target_path) {
             // If it's already a symlink pointing to the same source, we're done
-            if target_path.is_symlink() {
-                if let Ok(target_link) = fs::read_link(target_path) {
-                    if target_link == source_path {
-                        return Ok(format!("Symlink already exists: {} → {}", target, source));
-                    }
+            if let Ok(target_link) = fs.read_link(target_path) {
+                if target_link == source_path {
+                    return Ok(format!("Symlink already exists: {} → {}", target, source));
                 }
-                // Remove existing symlink if it points elsewhere
-                fs::remove_file(target_path)
-                    .map_err(|e| format!("Failed to remove existing symlink: {}", e))?;
-            } else {
-                // Target exists but is not a symlink
-                return Err(format!("Target path exists and is not a symlink: {}", target));
             }
+            // Remove existing symlink if it points elsewhere
+            fs.remove_file(target_path)
+                .map_err(|e| format!("Failed to remove existing symlink: {}", e))?;
+        } else if fs.exists(target_path) {
+            // Target exists but is not a symlink
+            return Err(format!("Target path exists and is not a symlink: {}", target));
         }
         
         // Create parent directory if needed
         if let Some(parent) = target_path.parent() {
-            fs::create_dir_all(parent)
+            fs.create_dir_all(parent)
                 .map_err(|e| format!("Failed to create parent directory: {}", e))?;
         }
         
         // Create symlink
-        std::os::unix::fs::symlink(source_path, target_path)
+        fs.symlink(source_path, target_path)
             .map_err(|e| format!("Failed to create symlink: {}", e))?;
         
         Ok(format!("Created symlink: {} → {}", target, source))
@@ -53,17 +58,17 @@ impl MirrorManager {
     
     /// Remove a symlink
     pub fn remove_mirror(&self, target: &str) -> Result<String, String> {
+        let fs = &self.backend.fs;
         let target_path = Path::new(target);
         
-        if !target_path.exists() {
-            return Ok(format!("Symlink does not exist: {}", target));
-        }
-        
-        if !target_path.is_symlink() {
+        if !fs.is_symlink(target_path) {
+            if !fs.exists(target_path) {
+                return Ok(format!("Symlink does not exist: {}", target));
+            }
             return Err(format!("Target is not a symlink: {}", target));
         }
         
-        fs::remove_file(target_path)
+        fs.remove_file(target_path)
             .map_err(|e| format!("Failed to remove symlink: {}", e))?;
         
         Ok(format!("Removed symlink: {}", target))
@@ -71,18 +76,14 @@ impl MirrorManager {
     
     /// Check if a mirror is active (symlink exists and points to source)
     pub fn is_active(&self, source: &str, target: &str) -> bool {
+        let fs = &self.backend.fs;
         let target_path = Path::new(target);
-        let source_path = Path::new(source);
         
-        if !target_path.exists() || !target_path.is_symlink() {
+        if !fs.exists(target_path) || !fs.is_symlink(target_path) {
             return false;
         }
         
-        if let Ok(link_target) = fs::read_link(target_path) {
-            return link_target == source_path;
-        }
-        
-        false
+        fs.read_link(target_path).is_ok_and(|link_target| link_target == Path::new(source))
     }
     
     /// Get file info for a path (used for size/modified time)
@@ -1,13 +1,134 @@
-// Shared activation/deactivation logic for detours and injections
+// Shared activation/deactivation logic for detours, injections and mirrors
 // Note: Activation logic is tightly coupled with App state, so this module
 // provides simple helpers rather than full abstraction
 
 /// Helper to update config entry enabled state after activation toggle
 pub fn update_entry_enabled(
     config_path: &str,
-    update_fn: impl FnOnce(&mut crate::config::DetourConfig) -> Result<(), String>,
+    update_fn: impl FnMut(&mut crate::config::DetourConfig) -> Result<(), String>,
 ) -> Result<(), String> {
     use crate::operations::config_ops;
     config_ops::with_config_mut(config_path, update_fn)
 }
 
+
+/// Apply the entry with `id` (detour mount, injection include or mirror symlink)
+pub fn activate(config: &crate::config::DetourConfig, id: &str) -> Result<String, String> {
+    let message = apply(config, id)?;
+    match crate::history::record_entry(config, id, "Activate") {
+        Err(e) => Ok(format!("{} (history commit failed: {})", message, e)),
+        _ => Ok(message),
+    }
+}
+
+/// Hooks configured on the entry with `id`
+pub fn entry_hooks<'a>(config: &'a crate::config::DetourConfig, id: &str) -> Option<&'a crate::config::EntryHooks> {
+    use crate::config::EntryKind;
+    
+    match config.find(id)? {
+        (EntryKind::Detour, i) => config.detours[i].hooks.as_ref(),
+        (EntryKind::Injection, i) => config.injections[i].hooks.as_ref(),
+        (EntryKind::Mirror, i) => config.mirrors[i].hooks.as_ref(),
+        (EntryKind::Service, _) => None,
+    }
+}
+
+fn apply(config: &crate::config::DetourConfig, id: &str) -> Result<String, String> {
+    crate::hooks::apply(entry_hooks(config, id), || apply_entry(config, id), || remove_entry(config, id))
+}
+
+fn apply_entry(config: &crate::config::DetourConfig, id: &str) -> Result<String, String> {
+    use crate::config::EntryKind;
+    use std::path::Path;
+    
+    match config.find(id) {
+        Some((EntryKind::Detour, i)) => {
+            crate::manager::DetourManager::new().apply_entry(&config.detours[i])
+        }
+        Some((EntryKind::Injection, i)) => {
+            let entry = &config.injections[i];
+            let injected = entry.injected_file();
+            if entry.template {
+                render_injection(config, entry)?;
+            }
+            crate::injection::InjectionManager::new()
+                .apply(Path::new(&entry.target), Path::new(&injected))
+                .map(|_| format!("Injected {} into {}", injected, entry.target))
+        }
+        Some((EntryKind::Mirror, i)) => {
+            let entry = &config.mirrors[i];
+            crate::mirror::MirrorManager::new().apply_mirror(&entry.source, &entry.target)
+        }
+        Some((EntryKind::Service, _)) => Err(format!("{} is a service entry and cannot be activated", id)),
+        None => Err(format!("No entry with id {}", id)),
+    }
+}
+
+/// Render a template injection's include into its 0600 output file
+pub fn render_injection(config: &crate::config::DetourConfig, entry: &crate::config::InjectionEntry) -> Result<crate::secrets::Rendered, String> {
+    let template = std::fs::read_to_string(&entry.include_file)
+        .map_err(|e| format!("Failed to read template {}: {}", entry.include_file, e))?;
+    let rendered = crate::secrets::render(&template, &config.secrets)?;
+    crate::secrets::write_private(&entry.injected_file(), &rendered.contents)?;
+    Ok(rendered)
+}
+
+/// Undo `activate` for the entry with `id`
+pub fn deactivate(config: &crate::config::DetourConfig, id: &str) -> Result<String, String> {
+    crate::hooks::remove(entry_hooks(config, id), || remove_entry(config, id))
+}
+
+fn remove_entry(config: &crate::config::DetourConfig, id: &str) -> Result<String, String> {
+    use crate::config::EntryKind;
+    use std::path::Path;
+    
+    match config.find(id) {
+        Some((EntryKind::Detour, i)) => {
+            let entry = &config.detours[i];
+            crate::manager::DetourManager::new().remove_entry(&entry.id, &entry.original, entry.scope.as_ref())
+        }
+        Some((EntryKind::Injection, i)) => {
+            let entry = &config.injections[i];
+            let injected = entry.injected_file();
+            crate::injection::InjectionManager::new()
+                .remove(Path::new(&entry.target), Path::new(&injected))?;
+            // The rendered copy holds secrets - don't leave it behind
+            if entry.template {
+                match std::fs::remove_file(&injected) {
+                    Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                        return Err(format!("Removed from {} but failed to delete {}: {}", entry.target, injected, e));
+                    }
+                    _ => {}
+                }
+            }
+            Ok(format!("Removed {} from {}", injected, entry.target))
+        }
+        Some((EntryKind::Mirror, i)) => {
+            crate::mirror::MirrorManager::new().remove_mirror(&config.mirrors[i].target)
+        }
+        Some((EntryKind::Service, _)) => Err(format!("{} is a service entry and cannot be deactivated", id)),
+        None => Err(format!("No entry with id {}", id)),
+    }
+}
+
+/// Whether the entry with `id` is currently applied on the system
+pub fn is_active(config: &crate::config::DetourConfig, id: &str) -> bool {
+    use crate::config::EntryKind;
+    use std::path::Path;
+    
+    match config.find(id) {
+        Some((EntryKind::Detour, i)) => {
+            let entry = &config.detours[i];
+            crate::manager::DetourManager::new().is_entry_active(&entry.id, &entry.original, entry.scope.as_ref())
+        }
+        Some((EntryKind::Injection, i)) => {
+            let entry = &config.injections[i];
+            crate::injection::InjectionManager::new().is_applied(Path::new(&entry.target), Path::new(&entry.injected_file()))
+        }
+        Some((EntryKind::Mirror, i)) => {
+            let entry = &config.mirrors[i];
+            crate::mirror::MirrorManager::new().is_active(&entry.source, &entry.target)
+        }
+        _ => false,
+    }
+}
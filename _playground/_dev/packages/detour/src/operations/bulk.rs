@@ -0,0 +1,170 @@
+// Bulk operations over a set of marked entries
+
+use crate::config::{DetourConfig, EntryKind};
+use crate::operations::{activation, config_ops};
+
+/// Entries listed in a plan summary before it is truncated
+const SUMMARY_LIMIT: usize = 10;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BulkOp {
+    Apply,
+    Remove,
+    /// Deactivate and drop from config (custom files are kept)
+    Delete,
+    Validate,
+    MoveToProfile(String),
+}
+
+impl BulkOp {
+    /// Choices offered by the bulk menu, in order
+    pub const MENU: [&'static str; 5] = ["Apply", "Remove", "Delete", "Validate", "Move to profile…"];
+
+    pub fn verb(&self) -> String {
+        match self {
+            BulkOp::Apply => "Apply".to_string(),
+            BulkOp::Remove => "Remove".to_string(),
+            BulkOp::Delete => "Delete".to_string(),
+            BulkOp::Validate => "Validate".to_string(),
+            BulkOp::MoveToProfile(profile) => format!("Move to profile '{}'", profile),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BulkPlan {
+    pub op: BulkOp,
+    pub kind: EntryKind,
+    pub ids: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct BulkOutcome {
+    pub id: String,
+    pub label: String,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BulkReport {
+    pub outcomes: Vec<BulkOutcome>,
+}
+
+impl BulkReport {
+    fn record(&mut self, config: &DetourConfig, id: &str, result: Result<(), String>) {
+        self.outcomes.push(BulkOutcome {
+            id: id.to_string(),
+            label: entry_label(config, id),
+            error: result.err(),
+        });
+    }
+
+    pub fn failed(&self) -> impl Iterator<Item = &BulkOutcome> {
+        self.outcomes.iter().filter(|o| o.error.is_some())
+    }
+
+    pub fn to_text(&self) -> String {
+        let failed = self.failed().count();
+        let mut out = vec![format!("{} succeeded, {} failed", self.outcomes.len() - failed, failed)];
+        for outcome in self.failed() {
+            out.push(format!("✗ {}: {}", outcome.label, outcome.error.as_deref().unwrap_or_default()));
+        }
+        out.join("\n")
+    }
+}
+
+fn entry_label(config: &DetourConfig, id: &str) -> String {
+    match config.find(id) {
+        Some((EntryKind::Detour, i)) => format!("{} ← {}", config.detours[i].original, config.detours[i].custom),
+        Some((EntryKind::Injection, i)) => format!("{} ← {}", config.injections[i].target, config.injections[i].include_file),
+        Some((EntryKind::Mirror, i)) => format!("{} → {}", config.mirrors[i].source, config.mirrors[i].target),
+        Some((EntryKind::Service, i)) => config.services[i].name.clone(),
+        None => id.to_string(),
+    }
+}
+
+impl BulkPlan {
+    /// Confirmation text listing what will happen to each entry
+    pub fn describe(&self, config: &DetourConfig) -> String {
+        let mut out = vec![format!("{} {} {:?} entries:", self.op.verb(), self.ids.len(), self.kind), String::new()];
+        for id in self.ids.iter().take(SUMMARY_LIMIT) {
+            out.push(format!("• {}", entry_label(config, id)));
+        }
+        if self.ids.len() > SUMMARY_LIMIT {
+            out.push(format!("…and {} more", self.ids.len() - SUMMARY_LIMIT));
+        }
+        if self.op == BulkOp::Delete {
+            out.push(String::new());
+            out.push("Active entries are deactivated first; custom files are kept.".to_string());
+        }
+        out.join("\n")
+    }
+
+    /// Run the plan. Config changes are written in a single save.
+    pub fn execute(&self, config_path: &str) -> BulkReport {
+        let config = config_ops::load_config(config_path);
+        let mut report = BulkReport::default();
+
+        match &self.op {
+            BulkOp::Apply | BulkOp::Remove => {
+                let host = crate::conditions::Host::detect();
+                for id in &self.ids {
+                    let skipped = crate::conditions::entry_skip_reason(&config, id, &host)
+                        .filter(|_| self.op == BulkOp::Apply);
+                    if let Some(reason) = skipped {
+                        report.record(&config, id, Err(format!("skipped, when: {}", reason)));
+                        continue;
+                    }
+                    let result = if self.op == BulkOp::Apply {
+                        activation::activate(&config, id)
+                    } else {
+                        activation::deactivate(&config, id)
+                    };
+                    report.record(&config, id, result.map(|_| ()));
+                }
+            }
+            BulkOp::Delete => {
+                let mut removable = vec![];
+                for id in &self.ids {
+                    if activation::is_active(&config, id) {
+                        if let Err(e) = activation::deactivate(&config, id) {
+                            report.record(&config, id, Err(format!("not deleted, deactivation failed: {}", e)));
+                            continue;
+                        }
+                    }
+                    removable.push(id.clone());
+                }
+                let result = config_ops::with_config_mut(config_path, |config| {
+                    config.detours.retain(|e| !removable.contains(&e.id));
+                    config.injections.retain(|e| !removable.contains(&e.id));
+                    config.mirrors.retain(|e| !removable.contains(&e.id));
+                    Ok(())
+                });
+                for id in &removable {
+                    report.record(&config, id, result.clone());
+                }
+            }
+            BulkOp::MoveToProfile(profile) => {
+                let profile = if profile.trim().is_empty() { None } else { Some(profile.trim().to_string()) };
+                let result = config_ops::with_config_mut(config_path, |config| {
+                    for e in config.detours.iter_mut().filter(|e| self.ids.contains(&e.id)) {
+                        e.profile = profile.clone();
+                    }
+                    for e in config.injections.iter_mut().filter(|e| self.ids.contains(&e.id)) {
+                        e.profile = profile.clone();
+                    }
+                    for e in config.mirrors.iter_mut().filter(|e| self.ids.contains(&e.id)) {
+                        e.profile = profile.clone();
+                    }
+                    Ok(())
+                });
+                for id in &self.ids {
+                    report.record(&config, id, result.clone());
+                }
+            }
+            // Validation has its own report view
+            BulkOp::Validate => {}
+        }
+        report
+    }
+}
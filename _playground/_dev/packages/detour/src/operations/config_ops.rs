@@ -3,13 +3,33 @@
 use crate::config::DetourConfig;
 
 /// Load config from file, or return empty config if file doesn't exist or is invalid
+/// Entries missing an id get one in memory only - ids are derived from the entry, so
+/// they come out the same on every load until `write_missing_ids` or a save persists them
 pub fn load_config(config_path: &str) -> DetourConfig {
-    DetourConfig::parse(config_path).unwrap_or_else(|_| DetourConfig {
-        detours: vec![],
-        injections: vec![],
-        mirrors: vec![],
-        services: vec![],
-    })
+    match DetourConfig::parse(config_path) {
+        Ok(mut config) => {
+            config.ensure_ids();
+            config
+        }
+        Err(_) => DetourConfig::default(),
+    }
+}
+
+/// Whether any entry in the file on disk has no id yet
+pub fn has_missing_ids(config_path: &str) -> bool {
+    DetourConfig::parse(config_path)
+        .map(|mut config| config.ensure_ids())
+        .unwrap_or(false)
+}
+
+/// Write generated ids back to the config file - returns false when nothing was missing
+pub fn write_missing_ids(config_path: &str) -> Result<bool, String> {
+    let mut config = DetourConfig::parse(config_path)?;
+    if !config.ensure_ids() {
+        return Ok(false);
+    }
+    save_config(config_path, &config)?;
+    Ok(true)
 }
 
 /// Save config to file - returns Ok(()) on success, Err with message on failure
@@ -34,6 +54,8 @@ where
 {
     let mut config = load_config(config_path);
     let result = f(&mut config)?;
+    // Newly added entries get their id here
+    config.ensure_ids();
     save_config(config_path, &config)?;
     Ok(result)
 }
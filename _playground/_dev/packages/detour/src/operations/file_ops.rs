@@ -1,7 +1,10 @@
 // Shared file operations - duplication, deletion, directory creation
 
 use std::fs;
-use std::path::Path;
+use std::io::Write;
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
 
 /// Create parent directories for a file path if they don't exist
 pub fn ensure_parent_dirs(file_path: &Path) -> Result<(), String> {
@@ -33,6 +36,39 @@ pub fn duplicate_file(source: &Path, dest: &Path) -> Result<String, String> {
     Ok(file_contents)
 }
 
+/// Write `contents` to a new file, creating parent directories if needed
+pub fn write_file(dest: &Path, contents: &str) -> Result<(), String> {
+    ensure_parent_dirs(dest)?;
+    fs::write(dest, contents)
+        .map_err(|e| format!("Failed to write destination file: {}", e))
+}
+
+/// Write `contents` to a fresh 0600 file in the temp dir, named after `name`
+///
+/// The file is created exclusively, so a planted file or symlink at a guessed path is never written through.
+pub fn write_private_temp(name: &str, contents: &str) -> Result<PathBuf, String> {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+    for attempt in 0..16 {
+        let path = std::env::temp_dir().join(format!("detour-{}-{}-{}-{}", std::process::id(), nanos, attempt, name));
+        match fs::OpenOptions::new().write(true).create_new(true).mode(0o600).open(&path) {
+            Ok(mut file) => {
+                return file.write_all(contents.as_bytes())
+                    .map(|_| path.clone())
+                    .map_err(|e| {
+                        let _ = fs::remove_file(&path);
+                        format!("Failed to write {}: {}", path.display(), e)
+                    });
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(format!("Failed to create {}: {}", path.display(), e)),
+        }
+    }
+    Err(format!("Failed to create a temp file for {}", name))
+}
+
 /// Delete a file if it exists
 pub fn delete_file(file_path: &Path) -> Result<(), String> {
     if file_path.exists() {
@@ -50,3 +86,12 @@ pub fn file_exists(file_path: &Path) -> bool {
         file_path.canonicalize().is_ok() || file_path.exists()
     }
 }
+
+/// Hex sha256 of a file (via sha256sum), None if it cannot be read
+pub fn sha256(path: &Path) -> Option<String> {
+    let output = Command::new("sha256sum").arg(path).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8_lossy(&output.stdout).split_whitespace().next().map(|s| s.to_string())
+}
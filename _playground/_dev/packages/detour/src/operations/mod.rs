@@ -1,3 +1,4 @@
 pub mod config_ops;
 pub mod file_ops;
-
+pub mod activation;
+pub mod bulk;
@@ -0,0 +1,149 @@
+// Debian package lookups - owning package, pristine file from the .deb, conffile md5s
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+pub const APT_ARCHIVES: &str = "/var/cache/apt/archives";
+pub const DPKG_STATUS: &str = "/var/lib/dpkg/status";
+
+/// md5 dpkg recorded for a conffile at install time
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conffile {
+    pub package: String,
+    pub md5: String,
+    pub obsolete: bool,
+}
+
+/// Packaged version of a file, extracted from its .deb
+#[derive(Debug, Clone)]
+pub struct Pristine {
+    pub package: String,
+    pub deb: PathBuf,
+    pub contents: String,
+}
+
+fn run(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    output.status.success().then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Package owning `path` according to `dpkg -S`
+pub fn owner(path: &str) -> Option<String> {
+    run("dpkg", &["-S", path]).and_then(|out| parse_owner(&out, path))
+}
+
+/// "pkg1, pkg2: /path" → pkg1, skipping diversion lines
+pub fn parse_owner(output: &str, path: &str) -> Option<String> {
+    output.lines()
+        .filter(|line| !line.starts_with("diversion by"))
+        .filter_map(|line| line.rsplit_once(": "))
+        .find(|(_, owned)| *owned == path)
+        .and_then(|(packages, _)| packages.split(',').next())
+        .map(|package| package.trim().to_string())
+}
+
+/// Name of the cached archive for a version, without the architecture
+///
+/// apt escapes the epoch colon: `1:2.0-1` is stored as `pkg_1%3a2.0-1_arm64.deb`
+pub fn deb_prefix(package: &str, version: &str) -> String {
+    let name = package.split(':').next().unwrap_or(package);
+    format!("{}_{}_", name, version.replace(':', "%3a"))
+}
+
+/// Cached .deb of the installed version of `package`
+pub fn cached_deb(package: &str) -> Option<PathBuf> {
+    let version = run("dpkg-query", &["-W", "-f=${Version}", package])?;
+    let prefix = deb_prefix(package, &version);
+    std::fs::read_dir(APT_ARCHIVES).ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .find(|path| path.file_name()
+            .map(|name| name.to_string_lossy())
+            .is_some_and(|name| name.starts_with(&prefix) && name.ends_with(".deb")))
+}
+
+/// Contents of `path` inside `deb` (`dpkg-deb --fsys-tarfile | tar -xO`)
+pub fn extract(deb: &Path, path: &str) -> Result<String, String> {
+    let mut fsys = Command::new("dpkg-deb")
+        .arg("--fsys-tarfile")
+        .arg(deb)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Failed to execute dpkg-deb: {}", e))?;
+    let tarball = fsys.stdout.take().ok_or("dpkg-deb produced no output")?;
+    let output = Command::new("tar")
+        .args(["-xO", "-f", "-"])
+        .arg(format!(".{}", path))
+        .stdin(Stdio::from(tarball))
+        .output()
+        .map_err(|e| format!("Failed to execute tar: {}", e))?;
+    let _ = fsys.wait();
+
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    } else {
+        Err(format!("{} is not in {}", path, deb.display()))
+    }
+}
+
+/// Packaged `original`, from `local_deb` when given, else from the apt cache
+pub fn pristine(original: &str, local_deb: Option<&str>) -> Result<Pristine, String> {
+    let (package, deb) = match local_deb {
+        Some(deb) => {
+            let package = run("dpkg-deb", &["--field", deb, "Package"])
+                .ok_or_else(|| format!("{} is not a readable .deb", deb))?;
+            (package, PathBuf::from(deb))
+        }
+        None => {
+            let package = owner(original)
+                .ok_or_else(|| format!("{} does not belong to any installed package", original))?;
+            let deb = cached_deb(&package)
+                .ok_or_else(|| format!("No cached .deb for {} in {} - set `deb:` on the entry or run `apt-get download {}`",
+                    package, APT_ARCHIVES, package))?;
+            (package, deb)
+        }
+    };
+    let contents = extract(&deb, original)?;
+    Ok(Pristine { package, deb, contents })
+}
+
+/// Conffiles recorded in a dpkg status file, keyed by path
+pub fn parse_conffiles(status: &str) -> HashMap<String, Conffile> {
+    let mut conffiles = HashMap::new();
+    for paragraph in status.split("\n\n") {
+        let mut package = "";
+        let mut in_conffiles = false;
+        for line in paragraph.lines() {
+            if let Some(continuation) = line.strip_prefix(' ') {
+                if !in_conffiles {
+                    continue;
+                }
+                let mut fields = continuation.split_whitespace();
+                if let (Some(path), Some(md5)) = (fields.next(), fields.next()) {
+                    conffiles.insert(path.to_string(), Conffile {
+                        package: package.to_string(),
+                        md5: md5.to_string(),
+                        obsolete: fields.any(|flag| flag == "obsolete"),
+                    });
+                }
+            } else {
+                in_conffiles = line.starts_with("Conffiles:");
+                if let Some(name) = line.strip_prefix("Package:") {
+                    package = name.trim();
+                }
+            }
+        }
+    }
+    conffiles
+}
+
+/// Recorded conffile for `path`, when its contents on disk no longer match it
+pub fn modified_conffile(path: &str) -> Option<Conffile> {
+    let status = std::fs::read_to_string(DPKG_STATUS).ok()?;
+    let conffile = parse_conffiles(&status).remove(path)?;
+    let md5 = run("md5sum", &[path])?;
+    let md5 = md5.split_whitespace().next()?;
+    (!conffile.obsolete && md5 != conffile.md5).then_some(conffile)
+}
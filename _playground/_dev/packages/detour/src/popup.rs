@@ -8,6 +8,12 @@ use ratatui::{
     Frame,
 };
 
+/// Confirm popup button line: `    [ Yes ]    [ No ]`
+const BUTTON_GAP: &str = "    ";
+const YES_BUTTON: &str = "[ Yes ]";
+const NO_BUTTON: &str = "[ No ]";
+const BUTTON_LINE_WIDTH: u16 = (2 * BUTTON_GAP.len() + YES_BUTTON.len() + NO_BUTTON.len()) as u16;
+
 fn hex_color(hex: u32) -> Color {
     Color::Rgb(
         ((hex >> 16) & 0xFF) as u8,
@@ -71,6 +77,11 @@ pub enum Popup {
         message: String,
         shown_at: std::time::Instant,  // For auto-dismiss
     },
+    Select {
+        title: String,
+        options: Vec<String>,
+        selected: usize,
+    },
 }
 
 impl Popup {
@@ -106,6 +117,28 @@ impl Popup {
         }
     }
     
+    pub fn select(title: impl Into<String>, options: Vec<String>) -> Self {
+        Popup::Select {
+            title: title.into(),
+            options,
+            selected: 0,
+        }
+    }
+    
+    pub fn handle_up(&mut self) {
+        if let Popup::Select { selected, .. } = self {
+            *selected = selected.saturating_sub(1);
+        }
+    }
+    
+    pub fn handle_down(&mut self) {
+        if let Popup::Select { options, selected, .. } = self {
+            if *selected + 1 < options.len() {
+                *selected += 1;
+            }
+        }
+    }
+    
     pub fn handle_left(&mut self) {
         if let Popup::Confirm { selected, .. } = self {
             *selected = (*selected + 1) % 2;
@@ -175,26 +208,31 @@ impl Popup {
     }
 }
 
-pub fn draw_popup(f: &mut Frame, area: Rect, popup: &Popup) {
+/// Draws the popup and returns its click targets as (choice, rect)
+pub fn draw_popup(f: &mut Frame, area: Rect, popup: &Popup) -> Vec<(usize, Rect)> {
     // Background dimming handled by global overlay in ui.rs
     
     match popup {
         Popup::Confirm { title, message, selected } => {
-            draw_confirm_popup(f, area, title, message, *selected);
+            draw_confirm_popup(f, area, title, message, *selected)
         }
         Popup::Input { title, prompt, input, cursor_pos } => {
             draw_input_popup(f, area, title, prompt, input, *cursor_pos);
+            vec![]
         }
         Popup::Error { title, message } => {
-            draw_message_popup(f, area, title, message, Color::Red);
+            draw_message_popup(f, area, title, message, Color::Red)
         }
         Popup::Info { title, message, .. } => {
-            draw_message_popup(f, area, title, message, Color::Cyan);
+            draw_message_popup(f, area, title, message, Color::Cyan)
+        }
+        Popup::Select { title, options, selected } => {
+            draw_select_popup(f, area, title, options, *selected)
         }
     }
 }
 
-fn draw_confirm_popup(f: &mut Frame, area: Rect, title: &str, message: &str, selected: usize) {
+fn draw_confirm_popup(f: &mut Frame, area: Rect, title: &str, message: &str, selected: usize) -> Vec<(usize, Rect)> {
     // Calculate width based on longest line + buttons width, with reasonable limits
     let max_line_len = message.lines().map(|l| l.len()).max().unwrap_or(30);
     let min_width_for_buttons = 30; // "    [ Yes ]    [ No ]"
@@ -266,15 +304,28 @@ fn draw_confirm_popup(f: &mut Frame, area: Rect, title: &str, message: &str, sel
         lines.push(Line::from(Span::styled(wrapped_line, Style::default().fg(Color::White))));
     }
     lines.push(Line::from("")); // Spacing before buttons
+    let buttons_y = content_area.y + lines.len() as u16;
     lines.push(Line::from(vec![
-        Span::raw("    "),
-        Span::styled("[ Yes ]", yes_style),
-        Span::raw("    "),
-        Span::styled("[ No ]", no_style),
+        Span::raw(BUTTON_GAP),
+        Span::styled(YES_BUTTON, yes_style),
+        Span::raw(BUTTON_GAP),
+        Span::styled(NO_BUTTON, no_style),
     ]));
     
     let paragraph = Paragraph::new(lines).alignment(Alignment::Center);
     f.render_widget(paragraph, content_area);
+    
+    // Paragraph centers with `width / 2 - line / 2`, halving each side separately
+    let buttons_x = content_area.x + (content_area.width / 2).saturating_sub(BUTTON_LINE_WIDTH / 2);
+    if buttons_y >= content_area.y + content_area.height {
+        return vec![];
+    }
+    let gap = BUTTON_GAP.len() as u16;
+    let yes_width = YES_BUTTON.len() as u16;
+    vec![
+        (0, Rect { x: buttons_x + gap, y: buttons_y, width: yes_width, height: 1 }),
+        (1, Rect { x: buttons_x + 2 * gap + yes_width, y: buttons_y, width: NO_BUTTON.len() as u16, height: 1 }),
+    ]
 }
 
 fn draw_input_popup(f: &mut Frame, area: Rect, title: &str, prompt: &str, input: &str, cursor_pos: usize) {
@@ -337,7 +388,56 @@ fn draw_input_popup(f: &mut Frame, area: Rect, title: &str, prompt: &str, input:
     f.render_widget(paragraph, content_area);
 }
 
-fn draw_message_popup(f: &mut Frame, area: Rect, title: &str, message: &str, color: Color) {
+fn draw_select_popup(f: &mut Frame, area: Rect, title: &str, options: &[String], selected: usize) -> Vec<(usize, Rect)> {
+    let max_len = options.iter().map(|o| o.chars().count()).max().unwrap_or(20);
+    let popup_width = (max_len as u16 + 12).max(36).min(area.width - 4);
+    // borders (2) + padding (2) + options + spacing (1) + help (1)
+    let popup_height = (options.len() as u16 + 6).min(area.height - 4);
+    
+    let popup_area = Rect {
+        x: (area.width.saturating_sub(popup_width)) / 2,
+        y: (area.height.saturating_sub(popup_height)) / 2,
+        width: popup_width,
+        height: popup_height,
+    };
+    
+    f.render_widget(Clear, popup_area);
+    
+    let block = Block::default()
+        .title(format!(" {} ", title))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double)
+        .border_style(Style::default().fg(Color::White))
+        .style(Style::default().bg(hex_color(0x0A0A0A)));
+    
+    f.render_widget(block, popup_area);
+    
+    let content_area = Rect {
+        x: popup_area.x + 2,
+        y: popup_area.y + 2,
+        width: popup_area.width.saturating_sub(4),
+        height: popup_area.height.saturating_sub(3),
+    };
+    
+    let mut lines: Vec<Line> = options.iter().enumerate().map(|(i, option)| {
+        if i == selected {
+            Line::from(Span::styled(format!("▶ {}", option), Style::default().fg(Color::Cyan).bg(hex_color(0x1A2A2A))))
+        } else {
+            Line::from(Span::styled(format!("  {}", option), Style::default().fg(Color::White)))
+        }
+    }).collect();
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled("[↑↓] Select  [Enter] Choose  [Esc] Cancel", Style::default().fg(hex_color(0x666666)))));
+    
+    f.render_widget(Paragraph::new(lines), content_area);
+    
+    (0..options.len())
+        .map(|i| (i, Rect { y: content_area.y + i as u16, height: 1, ..content_area }))
+        .filter(|(_, rect)| rect.y < content_area.y + content_area.height)
+        .collect()
+}
+
+fn draw_message_popup(f: &mut Frame, area: Rect, title: &str, message: &str, color: Color) -> Vec<(usize, Rect)> {
     // Calculate width based on content, with reasonable limits
     let max_line_len = message.lines().map(|l| l.len()).max().unwrap_or(30);
     let popup_width = (max_line_len as u16 + 8).max(40).min((area.width as f32 * 0.60) as u16).min(area.width - 4);
@@ -390,6 +490,9 @@ fn draw_message_popup(f: &mut Frame, area: Rect, title: &str, message: &str, col
     
     let paragraph = Paragraph::new(lines).alignment(Alignment::Center);
     f.render_widget(paragraph, content_area);
+    
+    // Clicking anywhere on the box closes it
+    vec![(0, popup_area)]
 }
 
 
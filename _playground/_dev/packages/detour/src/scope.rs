@@ -0,0 +1,225 @@
+// Scoped detours - overlay visible to one systemd unit or one command only
+
+use crate::config::DetourScope;
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+const DROPIN_DIR: &str = "/etc/systemd/system";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScopeState {
+    Inactive,
+    /// Drop-in installed but the unit has not been (re)started with it
+    Pending(String),
+    Active,
+}
+
+impl DetourScope {
+    /// Short form for lists and status, e.g. "unit klipper.service (ro)"
+    pub fn label(&self) -> String {
+        match (&self.unit, &self.command) {
+            (Some(unit), _) => format!("unit {}{}", unit_name(unit), if self.read_only { " (ro)" } else { "" }),
+            (None, Some(command)) => format!("namespace `{}`{}", command, if self.read_only { " (ro)" } else { "" }),
+            (None, None) => "invalid scope".to_string(),
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        match (&self.unit, &self.command) {
+            (Some(_), Some(_)) => Err("scope sets both unit and command".to_string()),
+            (None, None) => Err("scope needs a unit or a command".to_string()),
+            (Some(unit), None) if unit.contains('/') => Err(format!("invalid unit name '{}'", unit)),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// `klipper` → `klipper.service`
+fn unit_name(unit: &str) -> String {
+    if unit.contains('.') { unit.to_string() } else { format!("{}.service", unit) }
+}
+
+pub fn dropin_path(id: &str, unit: &str) -> PathBuf {
+    PathBuf::from(DROPIN_DIR).join(format!("{}.d", unit_name(unit))).join(format!("detour-{}.conf", id))
+}
+
+pub fn dropin_contents(id: &str, original: &str, custom: &str, read_only: bool) -> String {
+    let key = if read_only { "BindReadOnlyPaths" } else { "BindPaths" };
+    format!("# Managed by detour (entry {})\n[Service]\n{}={}:{}\n", id, key, custom, original)
+}
+
+/// pid file of the namespace process for command scopes
+fn pid_file(id: &str) -> PathBuf {
+    let dir = std::env::var("XDG_RUNTIME_DIR").map(PathBuf::from).unwrap_or_else(|_| std::env::temp_dir());
+    dir.join(format!("detour-scope-{}.pid", id))
+}
+
+/// stderr of the namespace process - a file, so it outlives a detour that exits first
+fn stderr_file(id: &str) -> PathBuf {
+    pid_file(id).with_extension("err")
+}
+
+/// stderr left by a command scope that is no longer running, e.g. a failed mount
+pub fn last_failure(id: &str) -> Option<String> {
+    if namespace_pid(id).is_some() {
+        return None;
+    }
+    fs::read_to_string(stderr_file(id)).ok().map(|e| e.trim().to_string()).filter(|e| !e.is_empty())
+}
+
+fn namespace_pid(id: &str) -> Option<u32> {
+    let pid = fs::read_to_string(pid_file(id)).ok()?.trim().parse().ok()?;
+    PathBuf::from(format!("/proc/{}", pid)).exists().then_some(pid)
+}
+
+fn sudo(args: &[&str]) -> Result<(), String> {
+    let output = Command::new("sudo")
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to execute {}: {}", args[0], e))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+fn systemctl_show(unit: &str, property: &str) -> String {
+    Command::new("systemctl")
+        .args(["show", "-p", property, "--value", unit])
+        .output()
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .unwrap_or_default()
+}
+
+/// Runs as root inside the new mount namespace: bind the overlay, record the pid
+/// once the mount is in place, then drop back to the calling user for the command.
+/// `$1` custom, `$2` original, `$3` command, `$4` pid file, `$5`/`$6` uid/gid.
+pub fn namespace_script(read_only: bool) -> String {
+    let remount = if read_only { " && mount -o remount,bind,ro \"$2\"" } else { "" };
+    format!(
+        "mount --bind \"$1\" \"$2\"{} && echo $$ > \"$4\" && exec setpriv --reuid=\"$5\" --regid=\"$6\" --init-groups sh -c \"$3\"",
+        remount
+    )
+}
+
+pub fn apply(id: &str, original: &str, custom: &str, scope: &DetourScope) -> Result<String, String> {
+    scope.validate()?;
+    if let Some(unit) = &scope.unit {
+        let unit = unit_name(unit);
+        let path = dropin_path(id, &unit);
+        let dir = path.parent().map(|p| p.to_string_lossy().to_string()).unwrap_or_default();
+        sudo(&["mkdir", "-p", &dir])?;
+
+        // Write through sudo tee - the drop-in dir is root-owned
+        let mut child = Command::new("sudo")
+            .args(["tee", &path.to_string_lossy()])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .spawn()
+            .map_err(|e| format!("Failed to execute tee: {}", e))?;
+        if let Some(stdin) = child.stdin.as_mut() {
+            use std::io::Write;
+            stdin.write_all(dropin_contents(id, original, custom, scope.read_only).as_bytes())
+                .map_err(|e| format!("Failed to write drop-in: {}", e))?;
+        }
+        let status = child.wait().map_err(|e| format!("Failed to write drop-in: {}", e))?;
+        if !status.success() {
+            return Err(format!("Failed to write {}", path.display()));
+        }
+
+        sudo(&["systemctl", "daemon-reload"])?;
+        // Bind mounts are set up when the unit's namespace is created
+        sudo(&["systemctl", "try-restart", &unit])?;
+        return Ok(format!("Scoped {} → {} for {}", custom, original, unit));
+    }
+
+    let command = scope.command.as_deref().unwrap_or_default();
+    if namespace_pid(id).is_some() {
+        return Err(format!("Namespace for {} is already running", id));
+    }
+    // -n: there is no terminal to prompt on once detached, so ask up front
+    sudo(&["-n", "true"]).map_err(|e| format!("sudo needs a password for scoped commands: {}", e))?;
+    let stderr = fs::File::create(stderr_file(id))
+        .map_err(|e| format!("Failed to create {}: {}", stderr_file(id).display(), e))?;
+
+    // Only the mount needs root - the command runs as whoever started detour
+    let (uid, gid) = unsafe { (libc::getuid(), libc::getgid()) };
+    let pid_path = pid_file(id);
+    let mut child = Command::new("sudo")
+        .args(["-n", "unshare", "--mount", "--propagation", "private", "sh", "-c",
+            &namespace_script(scope.read_only), "detour-scope", custom, original, command,
+            &pid_path.to_string_lossy(), &uid.to_string(), &gid.to_string()])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(stderr)
+        .spawn()
+        .map_err(|e| format!("Failed to execute unshare: {}", e))?;
+    if let Ok(Some(status)) = child.try_wait() {
+        return Err(format!("Scoped command exited at once ({})", status));
+    }
+
+    // Reap in the background; a failure keeps its stderr for `last_failure`
+    let stderr_path = stderr_file(id);
+    std::thread::spawn(move || {
+        if child.wait().is_ok_and(|status| status.success()) {
+            let _ = fs::remove_file(&stderr_path);
+        }
+        let _ = fs::remove_file(&pid_path);
+    });
+    Ok(format!("Starting `{}` with {} → {}", command, custom, original))
+}
+
+pub fn remove(id: &str, scope: &DetourScope) -> Result<String, String> {
+    if let Some(unit) = &scope.unit {
+        let unit = unit_name(unit);
+        let path = dropin_path(id, &unit);
+        sudo(&["rm", "-f", &path.to_string_lossy()])?;
+        sudo(&["systemctl", "daemon-reload"])?;
+        sudo(&["systemctl", "try-restart", &unit])?;
+        return Ok(format!("Removed scoped detour from {}", unit));
+    }
+
+    let pid = namespace_pid(id);
+    let _ = fs::remove_file(pid_file(id));
+    let _ = fs::remove_file(stderr_file(id));
+    match pid {
+        Some(pid) => {
+            sudo(&["kill", &pid.to_string()])?;
+            Ok(format!("Stopped scoped command (pid {})", pid))
+        }
+        None => Ok("Scoped command was not running".to_string()),
+    }
+}
+
+/// Whether `original` is a mount point in the mount namespace of `pid`
+fn mounted_in(pid: &str, original: &str) -> bool {
+    fs::read_to_string(format!("/proc/{}/mountinfo", pid))
+        .map(|info| info.lines().any(|line| line.split_whitespace().nth(4) == Some(original)))
+        .unwrap_or(false)
+}
+
+/// Checked against the unit's own mount namespace, not the global mount table.
+/// A command scope is active once its pid file exists - it is written after the mount.
+pub fn state(id: &str, original: &str, scope: &DetourScope) -> ScopeState {
+    if let Some(unit) = &scope.unit {
+        let unit = unit_name(unit);
+        if !dropin_path(id, &unit).exists() {
+            return ScopeState::Inactive;
+        }
+        let pid = systemctl_show(&unit, "MainPID");
+        if pid.is_empty() || pid == "0" {
+            return ScopeState::Pending(format!("{} is not running", unit));
+        }
+        if mounted_in(&pid, original) {
+            ScopeState::Active
+        } else {
+            ScopeState::Pending(format!("restart {} to apply", unit))
+        }
+    } else if namespace_pid(id).is_some() {
+        ScopeState::Active
+    } else {
+        ScopeState::Inactive
+    }
+}
@@ -0,0 +1,194 @@
+// List search, filtering and sorting for the Detours/Injections/Mirrors views
+
+/// Entries that can be searched and filtered in a list view
+pub trait Searchable {
+    /// Text shown on the first list line; match positions index into it
+    fn search_text(&self) -> String;
+    fn description(&self) -> Option<&str>;
+    fn is_active(&self) -> bool;
+    fn is_broken(&self) -> bool;
+    fn modified_secs(&self) -> u64;
+    fn size(&self) -> u64;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum StatusFilter {
+    #[default]
+    All,
+    Active,
+    Inactive,
+    Broken,
+}
+
+impl StatusFilter {
+    pub fn next(self) -> Self {
+        match self {
+            StatusFilter::All => StatusFilter::Active,
+            StatusFilter::Active => StatusFilter::Inactive,
+            StatusFilter::Inactive => StatusFilter::Broken,
+            StatusFilter::Broken => StatusFilter::All,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            StatusFilter::All => "all",
+            StatusFilter::Active => "active",
+            StatusFilter::Inactive => "inactive",
+            StatusFilter::Broken => "broken",
+        }
+    }
+
+    fn allows<T: Searchable>(&self, item: &T) -> bool {
+        match self {
+            StatusFilter::All => true,
+            StatusFilter::Active => item.is_active(),
+            StatusFilter::Inactive => !item.is_active(),
+            StatusFilter::Broken => item.is_broken(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SortKey {
+    /// Config file order (or best match first while searching)
+    #[default]
+    Config,
+    Path,
+    Modified,
+    Size,
+}
+
+impl SortKey {
+    pub fn next(self) -> Self {
+        match self {
+            SortKey::Config => SortKey::Path,
+            SortKey::Path => SortKey::Modified,
+            SortKey::Modified => SortKey::Size,
+            SortKey::Size => SortKey::Config,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SortKey::Config => "config",
+            SortKey::Path => "path",
+            SortKey::Modified => "modified",
+            SortKey::Size => "size",
+        }
+    }
+}
+
+/// A visible row: index into the full list plus highlighted char positions
+#[derive(Debug, Clone)]
+pub struct Match {
+    pub index: usize,
+    pub positions: Vec<usize>,
+}
+
+/// Search/filter/sort state for one list view
+#[derive(Debug, Clone, Default)]
+pub struct ListFilter {
+    pub query: String,
+    /// True while `/` search input is capturing keys
+    pub editing: bool,
+    pub status: StatusFilter,
+    pub sort: SortKey,
+}
+
+impl ListFilter {
+    pub fn is_active(&self) -> bool {
+        !self.query.is_empty() || self.status != StatusFilter::All || self.sort != SortKey::Config
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Visible entries in display order
+    pub fn apply<T: Searchable>(&self, items: &[T]) -> Vec<Match> {
+        let mut scored: Vec<(i64, Match)> = items.iter().enumerate()
+            .filter(|(_, item)| self.status.allows(*item))
+            .filter_map(|(index, item)| {
+                if self.query.is_empty() {
+                    return Some((0, Match { index, positions: vec![] }));
+                }
+                let on_text = fuzzy_match(&self.query, &item.search_text());
+                // Description matches count, but there is nothing on screen to highlight
+                let on_desc = item.description()
+                    .and_then(|d| fuzzy_match(&self.query, d))
+                    .map(|(score, _)| (score, vec![]));
+                let (score, positions) = match (on_text, on_desc) {
+                    (Some(a), Some(b)) => if b.0 > a.0 { b } else { a },
+                    (a, b) => a.or(b)?,
+                };
+                Some((score, Match { index, positions }))
+            })
+            .collect();
+
+        match self.sort {
+            SortKey::Config => {
+                if !self.query.is_empty() {
+                    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+                }
+            }
+            SortKey::Path => scored.sort_by_cached_key(|(_, m)| items[m.index].search_text()),
+            SortKey::Modified => scored.sort_by_key(|(_, m)| std::cmp::Reverse(items[m.index].modified_secs())),
+            SortKey::Size => scored.sort_by_key(|(_, m)| std::cmp::Reverse(items[m.index].size())),
+        }
+        scored.into_iter().map(|(_, m)| m).collect()
+    }
+
+    /// Short description for list titles, e.g. "/nginx · active · by size"
+    pub fn summary(&self) -> String {
+        let mut parts = vec![];
+        if !self.query.is_empty() || self.editing {
+            parts.push(format!("/{}{}", self.query, if self.editing { "▏" } else { "" }));
+        }
+        if self.status != StatusFilter::All {
+            parts.push(self.status.label().to_string());
+        }
+        if self.sort != SortKey::Config {
+            parts.push(format!("by {}", self.sort.label()));
+        }
+        parts.join(" · ")
+    }
+}
+
+/// Case-insensitive subsequence match. Returns a score (higher is better)
+/// and the char positions in `text` that matched.
+pub fn fuzzy_match(query: &str, text: &str) -> Option<(i64, Vec<usize>)> {
+    let query: Vec<char> = query.chars().flat_map(char::to_lowercase).collect();
+    if query.is_empty() {
+        return Some((0, vec![]));
+    }
+
+    let chars: Vec<char> = text.chars().collect();
+    let mut positions = Vec::with_capacity(query.len());
+    let mut score = 0i64;
+    let mut q = 0;
+    for (i, c) in chars.iter().enumerate() {
+        if q == query.len() {
+            break;
+        }
+        if !c.to_lowercase().eq(std::iter::once(query[q])) {
+            continue;
+        }
+        score += 1;
+        if positions.last().is_some_and(|&last| last + 1 == i) {
+            score += 5;
+        }
+        if i == 0 || matches!(chars[i - 1], '/' | '.' | '_' | '-' | ' ') {
+            score += 3;
+        }
+        positions.push(i);
+        q += 1;
+    }
+
+    if q < query.len() {
+        return None;
+    }
+    // Prefer tighter matches
+    let span = positions.last().unwrap() - positions[0];
+    Some((score - span as i64 / 4, positions))
+}
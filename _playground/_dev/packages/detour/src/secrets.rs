@@ -0,0 +1,196 @@
+// Secret placeholders in injection templates - rendered at apply time, redacted in diffs
+
+use crate::config::SecretSettings;
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Command;
+
+/// Shown in place of secret values
+pub const REDACTED: &str = "••••••";
+
+#[derive(Debug, Clone)]
+pub struct Rendered {
+    pub contents: String,
+}
+
+/// A template split at its secret placeholders, always ending with a literal
+#[derive(Debug, Clone, PartialEq)]
+enum Piece<'a> {
+    /// Copied to the output as is, other `{{...}}` included
+    Literal(&'a str),
+    /// `env`, `secret` or `cmd` with its trimmed argument
+    Secret(&'a str, &'a str),
+}
+
+fn pieces(template: &str) -> Vec<Piece<'_>> {
+    let mut pieces = vec![];
+    let mut literal_start = 0;
+    let mut pos = 0;
+
+    while let Some(found) = template[pos..].find("{{") {
+        let start = pos + found;
+        let Some(len) = template[start..].find("}}") else { break };
+        let end = start + len + 2;
+        if let Some((kind @ ("env" | "secret" | "cmd"), arg)) = template[start + 2..start + len].split_once(':') {
+            pieces.push(Piece::Literal(&template[literal_start..start]));
+            pieces.push(Piece::Secret(kind, arg.trim()));
+            literal_start = end;
+        }
+        pos = end;
+    }
+    pieces.push(Piece::Literal(&template[literal_start..]));
+    pieces
+}
+
+/// Replace `{{env:NAME}}`, `{{secret:key}}` and `{{cmd:shell command}}`.
+/// Other `{{...}}` are kept. Any placeholder that can't be resolved is an
+/// error, so a half-rendered file never gets written.
+pub fn render(template: &str, settings: &SecretSettings) -> Result<Rendered, String> {
+    let mut secrets: Option<HashMap<String, String>> = None;
+    let mut contents = String::new();
+
+    for piece in pieces(template) {
+        let value = match piece {
+            Piece::Literal(text) => {
+                contents.push_str(text);
+                continue;
+            }
+            Piece::Secret("env", name) => std::env::var(name)
+                .map_err(|_| format!("Environment variable {} is not set", name))?,
+            Piece::Secret("secret", key) => {
+                if secrets.is_none() {
+                    secrets = Some(load_secrets(settings)?);
+                }
+                secrets.as_ref().and_then(|s| s.get(key)).cloned()
+                    .ok_or_else(|| format!("Secret {} not found in {}", key, settings.file.as_deref().unwrap_or("")))?
+            }
+            Piece::Secret(_, command) => run_command(command)?,
+        };
+        contents.push_str(&value);
+    }
+
+    Ok(Rendered { contents })
+}
+
+/// Flat `key: value` YAML, the same shape as Home Assistant's secrets.yaml
+fn load_secrets(settings: &SecretSettings) -> Result<HashMap<String, String>, String> {
+    let file = settings.file.as_deref()
+        .ok_or("Template uses {{secret:...}} but no `secrets.file` is configured")?;
+    let path = crate::template::expand_home(file);
+    let text = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read secrets file {}: {}", path, e))?;
+    let map: HashMap<String, serde_yaml::Value> = serde_yaml::from_str(&text)
+        .map_err(|e| format!("Failed to parse secrets file {}: {}", path, e))?;
+
+    map.into_iter().map(|(key, value)| {
+        let value = match value {
+            serde_yaml::Value::String(s) => s,
+            serde_yaml::Value::Number(n) => n.to_string(),
+            serde_yaml::Value::Bool(b) => b.to_string(),
+            _ => return Err(format!("Secret {} in {} is not a scalar", key, path)),
+        };
+        Ok((key, value))
+    }).collect()
+}
+
+/// stdout of `sh -c command`, trailing newline dropped (e.g. `pass show ha/token`)
+fn run_command(command: &str) -> Result<String, String> {
+    let output = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .output()
+        .map_err(|e| format!("Failed to execute `{}`: {}", command, e))?;
+    if !output.status.success() {
+        return Err(format!("`{}` failed: {}", command, String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(stdout.strip_suffix('\n').unwrap_or(&stdout).to_string())
+}
+
+/// Mask whatever in `rendered` stands where `template` has a secret placeholder.
+///
+/// Works from the template alone, so nothing is resolved or run and a value that
+/// has since rotated is still hidden. Each placeholder's span ends at the next
+/// literal text. If the output no longer lines up with the template, only lines
+/// that appear verbatim in the template are shown.
+pub fn redact_rendered(template: &str, rendered: &str) -> String {
+    let pieces = pieces(template);
+    align(&pieces, rendered).unwrap_or_else(|| {
+        let literal_lines: std::collections::HashSet<&str> = pieces.iter()
+            .filter_map(|piece| match piece {
+                Piece::Literal(text) => Some(text),
+                Piece::Secret(..) => None,
+            })
+            .flat_map(|text| text.lines())
+            .collect();
+        rendered.lines()
+            .map(|line| if literal_lines.contains(line) { line } else { REDACTED })
+            .collect::<Vec<_>>()
+            .join("\n")
+    })
+}
+
+fn align(pieces: &[Piece], rendered: &str) -> Option<String> {
+    let mut out = String::new();
+    let mut rest = rendered;
+    let mut in_secret = false;
+
+    for (i, piece) in pieces.iter().enumerate() {
+        let Piece::Literal(text) = piece else {
+            in_secret = true;
+            continue;
+        };
+        let is_last = i + 1 == pieces.len();
+        let at = if !in_secret {
+            rest.strip_prefix(text).map(|_| 0)?
+        } else if is_last {
+            rest.ends_with(text).then(|| rest.len() - text.len())?
+        } else if text.is_empty() {
+            continue;
+        } else {
+            rest.find(text)?
+        };
+        if in_secret {
+            out.push_str(REDACTED);
+        }
+        out.push_str(text);
+        rest = &rest[at + text.len()..];
+        in_secret = false;
+    }
+    // The last piece is always a literal, so everything has been consumed
+    rest.is_empty().then_some(out)
+}
+
+/// `<target dir>/<include stem>.rendered.<ext>`, next to the file that references it
+pub fn default_output(target: &str, include: &str) -> String {
+    let include = Path::new(include);
+    let stem = include.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match include.extension() {
+        Some(ext) => format!("{}.rendered.{}", stem, ext.to_string_lossy()),
+        None => format!("{}.rendered", stem),
+    };
+    Path::new(target).parent().unwrap_or(Path::new("/")).join(name).to_string_lossy().to_string()
+}
+
+/// Write `contents` readable by the owner only
+pub fn write_private(path: &str, contents: &str) -> Result<(), String> {
+    use std::io::Write;
+    use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+    if let Some(parent) = Path::new(path).parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)
+        .map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    // `mode` only applies on create
+    file.set_permissions(std::fs::Permissions::from_mode(0o600))
+        .map_err(|e| format!("Failed to chmod {}: {}", path, e))?;
+    file.write_all(contents.as_bytes())
+        .map_err(|e| format!("Failed to write {}: {}", path, e))
+}
@@ -0,0 +1,345 @@
+// System status collection - shared by the StatusOverview view and `detour status`
+
+use crate::config::DetourConfig;
+use crate::injection::InjectionManager;
+use crate::manager::DetourManager;
+use crate::mirror::MirrorManager;
+use crate::scope::ScopeState;
+use serde::Serialize;
+use std::os::unix::fs::MetadataExt;
+use std::path::Path;
+use std::process::Command;
+
+pub use crate::config::EntryKind;
+
+/// A single entry that needs attention
+#[derive(Debug, Clone, Serialize)]
+pub struct StatusEntry {
+    pub kind: EntryKind,
+    pub id: String,
+    pub label: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CategoryStatus {
+    pub total: usize,
+    pub active: usize,
+    pub inactive: usize,
+    pub broken: usize,
+    pub failing: Vec<StatusEntry>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ServiceState {
+    pub id: String,
+    pub name: String,
+    pub action: String,
+    pub state: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigStatus {
+    pub path: String,
+    pub exists: bool,
+    pub parsed: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PrivilegeStatus {
+    pub is_root: bool,
+    pub sudo_usable: bool,
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StatusReport {
+    pub generated_at: String,
+    pub config: ConfigStatus,
+    pub privilege: PrivilegeStatus,
+    pub detours: CategoryStatus,
+    pub injections: CategoryStatus,
+    pub mirrors: CategoryStatus,
+    pub services: Vec<ServiceState>,
+    pub drift: Vec<StatusEntry>,
+}
+
+impl StatusReport {
+    pub fn collect(config_path: &str) -> Self {
+        let exists = Path::new(config_path).exists();
+        let (config, parse_error) = match DetourConfig::parse(config_path) {
+            Ok(config) => (config, None),
+            Err(e) => (DetourConfig::default(), Some(e)),
+        };
+
+        let mut drift = vec![];
+        let detours = Self::collect_detours(&config, &mut drift);
+        let injections = Self::collect_injections(&config, &mut drift);
+        let mirrors = Self::collect_mirrors(&config, &mut drift);
+        let services = Self::collect_services(&config);
+
+        StatusReport {
+            generated_at: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            config: ConfigStatus {
+                path: config_path.to_string(),
+                exists,
+                parsed: parse_error.is_none() && exists,
+                error: if exists { parse_error } else { Some("Config file does not exist".to_string()) },
+            },
+            privilege: Self::check_privilege(),
+            detours,
+            injections,
+            mirrors,
+            services,
+            drift,
+        }
+    }
+
+    fn collect_detours(config: &DetourConfig, drift: &mut Vec<StatusEntry>) -> CategoryStatus {
+        let manager = DetourManager::new();
+        let mut status = CategoryStatus { total: config.detours.len(), ..Default::default() };
+
+        for entry in &config.detours {
+            let label = match &entry.scope {
+                Some(scope) => format!("{} ← {} [{}]", entry.original, entry.custom, scope.label()),
+                None => format!("{} ← {}", entry.original, entry.custom),
+            };
+            let active = manager.is_entry_active(&entry.id, &entry.original, entry.scope.as_ref());
+            if active { status.active += 1 } else { status.inactive += 1 }
+
+            let reason = if !Path::new(&entry.custom).exists() {
+                Some("custom file missing".to_string())
+            } else if !Path::new(&entry.original).exists() {
+                Some("original file missing".to_string())
+            } else {
+                entry.scope.as_ref().and_then(|scope| scope.validate().err())
+            };
+            if let Some(reason) = reason {
+                status.broken += 1;
+                status.failing.push(StatusEntry { kind: EntryKind::Detour, id: entry.id.clone(), label: label.clone(), reason });
+                continue;
+            }
+
+            // Scoped detours live in the unit's namespace, not the global mount table
+            if let Some(scope) = &entry.scope {
+                match crate::scope::state(&entry.id, &entry.original, scope) {
+                    ScopeState::Pending(reason) => {
+                        drift.push(StatusEntry { kind: EntryKind::Detour, id: entry.id.clone(), label, reason });
+                    }
+                    ScopeState::Inactive => {
+                        if let Some(error) = crate::scope::last_failure(&entry.id) {
+                            let reason = format!("scoped command failed: {}", error);
+                            drift.push(StatusEntry { kind: EntryKind::Detour, id: entry.id.clone(), label, reason });
+                        }
+                    }
+                    ScopeState::Active => {}
+                }
+                continue;
+            }
+
+            // Editors that replace the file leave the mount pinned to the old inode
+            if active {
+                let inode = |p: &str| std::fs::metadata(p).ok().map(|m| (m.dev(), m.ino()));
+                if inode(&entry.original) != inode(&entry.custom) {
+                    drift.push(StatusEntry {
+                        kind: EntryKind::Detour,
+                        id: entry.id.clone(),
+                        label,
+                        reason: "mount points at a stale copy of the custom file (re-apply)".to_string(),
+                    });
+                }
+            }
+        }
+        status
+    }
+
+    fn collect_injections(config: &DetourConfig, drift: &mut Vec<StatusEntry>) -> CategoryStatus {
+        let manager = InjectionManager::new();
+        let mut status = CategoryStatus { total: config.injections.len(), ..Default::default() };
+
+        for entry in &config.injections {
+            let label = format!("{} ← {}", entry.target, entry.include_file);
+            let target = Path::new(&entry.target);
+            let include = Path::new(&entry.include_file);
+            let applied = manager.is_applied(target, Path::new(&entry.injected_file()));
+            if applied { status.active += 1 } else { status.inactive += 1 }
+
+            let reason = if !target.exists() {
+                Some("target file missing")
+            } else if !include.exists() {
+                Some("include file missing")
+            } else {
+                None
+            };
+            if let Some(reason) = reason {
+                status.broken += 1;
+                status.failing.push(StatusEntry { kind: EntryKind::Injection, id: entry.id.clone(), label: label.clone(), reason: reason.to_string() });
+                if applied {
+                    drift.push(StatusEntry {
+                        kind: EntryKind::Injection,
+                        id: entry.id.clone(),
+                        label,
+                        reason: "target still references a missing include".to_string(),
+                    });
+                }
+            }
+        }
+        status
+    }
+
+    fn collect_mirrors(config: &DetourConfig, drift: &mut Vec<StatusEntry>) -> CategoryStatus {
+        let manager = MirrorManager::new();
+        let mut status = CategoryStatus { total: config.mirrors.len(), ..Default::default() };
+
+        for entry in &config.mirrors {
+            let label = format!("{} → {}", entry.source, entry.target);
+            let active = manager.is_active(&entry.source, &entry.target);
+            if active { status.active += 1 } else { status.inactive += 1 }
+
+            if !Path::new(&entry.source).exists() {
+                status.broken += 1;
+                status.failing.push(StatusEntry { kind: EntryKind::Mirror, id: entry.id.clone(), label, reason: "source path missing".to_string() });
+                continue;
+            }
+
+            let target = Path::new(&entry.target);
+            if target.is_symlink() && !active {
+                let points_to = std::fs::read_link(target)
+                    .map(|p| p.display().to_string())
+                    .unwrap_or_default();
+                drift.push(StatusEntry {
+                    kind: EntryKind::Mirror,
+                    id: entry.id.clone(),
+                    label,
+                    reason: format!("target symlink points to {}", points_to),
+                });
+            }
+        }
+        status
+    }
+
+    fn collect_services(config: &DetourConfig) -> Vec<ServiceState> {
+        config.services.iter().map(|entry| {
+            let state = Command::new("systemctl")
+                .arg("is-active")
+                .arg(&entry.name)
+                .output()
+                .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+                .unwrap_or_else(|_| "unknown".to_string());
+            ServiceState {
+                id: entry.id.clone(),
+                name: entry.name.clone(),
+                action: entry.action.clone(),
+                state: if state.is_empty() { "unknown".to_string() } else { state },
+            }
+        }).collect()
+    }
+
+    fn check_privilege() -> PrivilegeStatus {
+        let is_root = Command::new("id")
+            .arg("-u")
+            .output()
+            .map(|o| String::from_utf8_lossy(&o.stdout).trim() == "0")
+            .unwrap_or(false);
+
+        // -n: never prompt, fail instead
+        match Command::new("sudo").arg("-n").arg("true").output() {
+            Ok(output) if output.status.success() => PrivilegeStatus {
+                is_root,
+                sudo_usable: true,
+                detail: "sudo available without password".to_string(),
+            },
+            Ok(output) => PrivilegeStatus {
+                is_root,
+                sudo_usable: false,
+                detail: {
+                    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+                    if stderr.is_empty() { "sudo requires a password".to_string() } else { stderr }
+                },
+            },
+            Err(e) => PrivilegeStatus {
+                is_root,
+                sudo_usable: false,
+                detail: format!("sudo not available: {}", e),
+            },
+        }
+    }
+
+    pub fn failed_services(&self) -> Vec<&ServiceState> {
+        self.services.iter().filter(|s| s.state == "failed").collect()
+    }
+
+    /// True when nothing needs attention
+    pub fn is_healthy(&self) -> bool {
+        self.config.parsed
+            && (self.privilege.sudo_usable || self.privilege.is_root)
+            && self.detours.broken == 0
+            && self.injections.broken == 0
+            && self.mirrors.broken == 0
+            && self.drift.is_empty()
+            && self.failed_services().is_empty()
+    }
+
+    /// Plain-text summary for the CLI
+    pub fn to_text(&self) -> String {
+        let mut out = vec![];
+        out.push(format!("Config:     {} ({})", self.config.path,
+            if self.config.parsed { "ok".to_string() } else { self.config.error.clone().unwrap_or_default() }));
+        out.push(format!("Privilege:  {}", self.privilege.detail));
+        for (name, cat) in [("Detours", &self.detours), ("Injections", &self.injections), ("Mirrors", &self.mirrors)] {
+            out.push(format!("{:<11} {} total, {} active, {} inactive, {} broken",
+                format!("{}:", name), cat.total, cat.active, cat.inactive, cat.broken));
+            for entry in &cat.failing {
+                out.push(format!("  ✗ {} ({})", entry.label, entry.reason));
+            }
+        }
+        out.push(format!("Services:   {}", self.services.len()));
+        for service in &self.services {
+            out.push(format!("  {} {} [{}]", if service.state == "active" { "✓" } else { "○" }, service.name, service.state));
+        }
+        if !self.drift.is_empty() {
+            out.push("Drift:".to_string());
+            for entry in &self.drift {
+                out.push(format!("  ⚠ {} ({})", entry.label, entry.reason));
+            }
+        }
+        out.join("\n")
+    }
+}
+
+/// Status tiles shown in the StatusOverview view, in display order
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StatusTile {
+    Config,
+    Privilege,
+    Detours,
+    Injections,
+    Mirrors,
+    Services,
+    Drift,
+}
+
+impl StatusTile {
+    pub const ALL: [StatusTile; 7] = [
+        StatusTile::Config,
+        StatusTile::Privilege,
+        StatusTile::Detours,
+        StatusTile::Injections,
+        StatusTile::Mirrors,
+        StatusTile::Services,
+        StatusTile::Drift,
+    ];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            StatusTile::Config => "Config",
+            StatusTile::Privilege => "Privilege",
+            StatusTile::Detours => "Detours",
+            StatusTile::Injections => "Injections",
+            StatusTile::Mirrors => "Mirrors",
+            StatusTile::Services => "Services",
+            StatusTile::Drift => "Drift",
+        }
+    }
+}
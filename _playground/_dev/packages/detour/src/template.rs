@@ -0,0 +1,152 @@
+// Custom file generation - origin headers, user templates and managed paths
+
+use crate::config::TemplateSettings;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Where the initial contents of a new custom file come from
+#[derive(Debug, Clone, PartialEq)]
+pub enum FileSource {
+    /// Verbatim copy of the original
+    Original,
+    /// User template file, rendered with `TemplateVars`
+    Template(PathBuf),
+    Empty,
+}
+
+impl FileSource {
+    pub fn label(&self) -> String {
+        match self {
+            FileSource::Original => "Copy original".to_string(),
+            FileSource::Template(path) => format!("Template: {}", path.file_name().unwrap_or_default().to_string_lossy()),
+            FileSource::Empty => "Empty file".to_string(),
+        }
+    }
+}
+
+/// Values substituted for `{{name}}` placeholders
+#[derive(Debug, Clone)]
+pub struct TemplateVars {
+    pub hostname: String,
+    pub original: String,
+    pub custom: String,
+    pub date: String,
+    pub checksum: String,
+    /// Contents of the original file, for templates that wrap it
+    pub original_contents: String,
+}
+
+impl TemplateVars {
+    pub fn collect(original: &str, custom: &str) -> Self {
+        Self {
+            hostname: hostname(),
+            original: original.to_string(),
+            custom: custom.to_string(),
+            date: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            checksum: crate::operations::file_ops::sha256(Path::new(original)).unwrap_or_else(|| "unavailable".to_string()),
+            original_contents: fs::read_to_string(original).unwrap_or_default(),
+        }
+    }
+}
+
+/// Substitute `{{hostname}}`, `{{original}}`, `{{custom}}`, `{{date}}`,
+/// `{{checksum}}` and `{{original_contents}}`. Unknown placeholders are kept.
+pub fn render(template: &str, vars: &TemplateVars) -> String {
+    template
+        .replace("{{hostname}}", &vars.hostname)
+        .replace("{{original}}", &vars.original)
+        .replace("{{custom}}", &vars.custom)
+        .replace("{{date}}", &vars.date)
+        .replace("{{checksum}}", &vars.checksum)
+        .replace("{{original_contents}}", &vars.original_contents)
+}
+
+/// Comment block noting where the file came from, in the file type's syntax.
+/// None for formats without comments (JSON).
+pub fn header_comment(vars: &TemplateVars) -> Option<String> {
+    let lines = [
+        format!("detour: custom copy of {}", vars.original),
+        format!("created {} on {}", vars.date, vars.hostname),
+        format!("original sha256 {}", vars.checksum),
+    ];
+    let ext = Path::new(&vars.original).extension().and_then(|e| e.to_str()).unwrap_or("");
+    let block = match ext {
+        "json" => return None,
+        "xml" | "html" | "htm" | "svg" => format!("<!--\n{}\n-->\n", lines.join("\n")),
+        "css" => format!("/*\n{}\n*/\n", lines.iter().map(|l| format!(" * {}", l)).collect::<Vec<_>>().join("\n")),
+        "c" | "h" | "cpp" | "hpp" | "rs" | "go" | "js" | "ts" | "java" => {
+            lines.iter().map(|l| format!("// {}\n", l)).collect()
+        }
+        "ini" => lines.iter().map(|l| format!("; {}\n", l)).collect(),
+        _ => lines.iter().map(|l| format!("# {}\n", l)).collect(),
+    };
+    Some(block)
+}
+
+/// Contents for a new custom file
+pub fn generate(source: &FileSource, vars: &TemplateVars, settings: &TemplateSettings) -> Result<String, String> {
+    let body = match source {
+        FileSource::Original => vars.original_contents.clone(),
+        FileSource::Template(path) => {
+            let template = fs::read_to_string(path)
+                .map_err(|e| format!("Failed to read template {}: {}", path.display(), e))?;
+            render(&template, vars)
+        }
+        FileSource::Empty => String::new(),
+    };
+
+    match header_comment(vars).filter(|_| settings.header) {
+        // Keep a shebang on the first line
+        Some(header) if body.starts_with("#!") => {
+            let (shebang, rest) = body.split_once('\n').unwrap_or((&body, ""));
+            Ok(format!("{}\n{}{}", shebang, header, rest))
+        }
+        Some(header) => Ok(format!("{}{}", header, body)),
+        None => Ok(body),
+    }
+}
+
+/// Sources offered when creating a custom file: original, templates, empty
+pub fn available_sources(settings: &TemplateSettings) -> Vec<FileSource> {
+    let mut sources = vec![FileSource::Original];
+    if let Some(dir) = &settings.dir {
+        if let Ok(entries) = fs::read_dir(expand_home(dir)) {
+            let mut templates: Vec<PathBuf> = entries.flatten()
+                .map(|e| e.path())
+                .filter(|p| p.is_file())
+                .collect();
+            templates.sort();
+            sources.extend(templates.into_iter().map(FileSource::Template));
+        }
+    }
+    sources.push(FileSource::Empty);
+    sources
+}
+
+/// `<managed_dir>/<original path>`, e.g. ~/.detour/overlays/etc/nginx/nginx.conf
+pub fn managed_path(settings: &TemplateSettings, original: &str) -> Option<String> {
+    let dir = managed_dir(settings)?;
+    let relative = original.trim_start_matches('/');
+    if relative.is_empty() {
+        return None;
+    }
+    Some(dir.join(relative).to_string_lossy().to_string())
+}
+
+/// `managed_dir` with `~` expanded
+pub fn managed_dir(settings: &TemplateSettings) -> Option<PathBuf> {
+    settings.managed_dir.as_ref().map(|dir| PathBuf::from(expand_home(dir)))
+}
+
+pub fn expand_home(path: &str) -> String {
+    match path.strip_prefix('~') {
+        Some(rest) => format!("{}{}", std::env::var("HOME").unwrap_or_default(), rest),
+        None => path.to_string(),
+    }
+}
+
+pub fn hostname() -> String {
+    fs::read_to_string("/proc/sys/kernel/hostname")
+        .map(|h| h.trim().to_string())
+        .unwrap_or_else(|_| "unknown".to_string())
+}
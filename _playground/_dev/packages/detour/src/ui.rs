@@ -45,6 +45,9 @@ pub fn ui(f: &mut Frame, app: &mut App) {
         area
     );
     
+    // Rebuilt by every draw below
+    app.hit_areas = crate::app::HitAreas::default();
+    
     // Check minimum size
     if area.width < 120 || area.height < 16 {
         draw_minimal_ui(f, app);
@@ -75,6 +78,7 @@ pub fn ui(f: &mut Frame, app: &mut App) {
         width: col1_width,
         height: content_height,
     };
+    app.hit_areas.views = col1_area;
     draw_view_column(f, col1_area, app);
     
     // Column 2: Actions
@@ -85,6 +89,7 @@ pub fn ui(f: &mut Frame, app: &mut App) {
         width: col2_width,
         height: content_height,
     };
+    app.hit_areas.actions = col2_area;
     draw_action_column(f, col2_area, app);
     
     // Column 3: Content
@@ -96,6 +101,7 @@ pub fn ui(f: &mut Frame, app: &mut App) {
         width: col3_width,
         height: content_height,
     };
+    app.hit_areas.content = col3_area;
     draw_content_column(f, col3_area, app);
     
     // Bottom status area
@@ -121,7 +127,7 @@ pub fn ui(f: &mut Frame, app: &mut App) {
     
     // Draw popup last (overlays everything)
     if let Some(popup) = &app.popup {
-        popup::draw_popup(f, area, popup);
+        app.hit_areas.popup = popup::draw_popup(f, area, popup);
     }
     
     // Draw file browser (overlays everything)
@@ -378,6 +384,7 @@ fn draw_view_column(f: &mut Frame, area: Rect, app: &mut App) {
         .highlight_style(highlight_style);
     
     f.render_stateful_widget(list, area, &mut state);
+    app.hit_areas.view_rows = crate::components::list_panel::row_areas(area, &vec![1; app.views.len()], state.offset());
 }
 
 fn draw_action_column(f: &mut Frame, area: Rect, app: &mut App) {
@@ -437,6 +444,7 @@ fn draw_action_column(f: &mut Frame, area: Rect, app: &mut App) {
         .highlight_symbol("");  // Empty string = no arrow indicator
     
     f.render_stateful_widget(list, area, &mut state);
+    app.hit_areas.action_rows = crate::components::list_panel::row_areas(area, &vec![1; actions.len()], state.offset());
 }
 
 fn draw_content_column(f: &mut Frame, area: Rect, app: &mut App) {
@@ -493,12 +501,40 @@ fn draw_content_column(f: &mut Frame, area: Rect, app: &mut App) {
     }
 }
 
+/// List title with counts, marks and the active search/filter/sort
+fn list_title(name: &str, shown: usize, total: usize, marked: usize, filter: &crate::search::ListFilter) -> String {
+    let mut title = if filter.is_active() || filter.editing {
+        format!(" {} ({}/{})", name, shown, total)
+    } else {
+        format!(" {} ({})", name, total)
+    };
+    if marked > 0 {
+        title.push_str(&format!(" · {} marked", marked));
+    }
+    let summary = filter.summary();
+    if !summary.is_empty() {
+        title.push_str(&format!(" · {}", summary));
+    }
+    title.push(' ');
+    title
+}
+
+fn profile_tag(profile: &Option<String>) -> String {
+    profile.as_ref().map(|p| format!("  |  🏷 {}", p)).unwrap_or_default()
+}
+
+fn skip_tag(skipped: &Option<String>) -> String {
+    skipped.as_ref().map(|r| format!("  |  ⏸ skipped: {}", r)).unwrap_or_default()
+}
+
 fn draw_detours_list(f: &mut Frame, area: Rect, app: &mut App, modal_visible: bool) {
     let is_active = app.active_column == ActiveColumn::Content && !modal_visible;
+    let matches = app.detour_filter.apply(&app.detours);
     let items: Vec<crate::components::list_panel::ItemRow> = if app.detours.is_empty() {
         vec![]
     } else {
-        app.detours.iter().map(|detour| {
+        matches.iter().map(|m| {
+            let detour = &app.detours[m.index];
             let size_str = detour.size_display();
             let status_text = detour.status_text();
             crate::components::list_panel::ItemRow {
@@ -507,20 +543,27 @@ fn draw_detours_list(f: &mut Frame, area: Rect, app: &mut App, modal_visible: bo
                     detour.original,
                     detour.custom
                 ),
-                line2: Some(format!("   📝 {}  |  📏 {}  |  {}", 
+                line2: Some(format!("   📝 {}  |  📏 {}  |  {}{}{}{}", 
                     detour.modified_ago(),
                     size_str,
-                    status_text
+                    status_text,
+                    detour.scope.as_ref().map(|s| format!("  |  🔒 {}", s.label())).unwrap_or_default(),
+                    profile_tag(&detour.profile),
+                    skip_tag(&detour.skipped)
                 )),
                 status_icon: Some(if detour.active { "✓".to_string() } else { "○".to_string() }),
+                // line1 is prefixed with the status icon and a space
+                highlights: m.positions.iter().map(|p| p + 2).collect(),
+                marked: app.marked.contains(&detour.id),
+                dimmed: detour.skipped.is_some(),
             }
         }).collect()
     };
 
-    crate::components::list_panel::draw_list_panel(
+    app.hit_areas.content_rows = crate::components::list_panel::draw_list_panel(
         f,
         area,
-        &format!(" Detours ({}) ", app.detours.len()),
+        &list_title("Detours", matches.len(), app.detours.len(), app.marked_in_view().len(), &app.detour_filter),
         &items,
         &mut app.detour_state,
         is_active,
@@ -533,11 +576,11 @@ fn draw_detours_add(f: &mut Frame, area: Rect, app: &App, modal_visible: bool) {
     let is_active = app.active_column == ActiveColumn::Content && !modal_visible;
     let fields = vec![
         crate::components::form_panel::FormField { label: "Original Path:".to_string(), value: app.add_form.original_path.clone(), placeholder: "/path/to/original/file".to_string() },
-        crate::components::form_panel::FormField { label: "Custom Path:".to_string(), value: app.add_form.custom_path.clone(), placeholder: "/path/to/custom/file".to_string() },
+        crate::components::form_panel::FormField { label: "Custom Path:".to_string(), value: app.add_form.custom_path.clone(), placeholder: "/path/to/custom/file (empty = managed dir)".to_string() },
         crate::components::form_panel::FormField { label: "Description (optional):".to_string(), value: app.add_form.description.clone(), placeholder: "Brief description of this detour".to_string() },
     ];
     let state = crate::components::form_panel::FormState { active_field: app.add_form.active_field, cursor_pos: app.add_form.cursor_pos };
-    let title = if app.add_form.editing_index.is_some() {
+    let title = if app.add_form.editing_id.is_some() {
         " Edit Detour "
     } else {
         " Add New Detour "
@@ -554,7 +597,7 @@ fn draw_detours_add(f: &mut Frame, area: Rect, app: &App, modal_visible: bool) {
 }
 
 fn draw_detours_edit(f: &mut Frame, area: Rect, app: &App, modal_visible: bool) {
-    // Reuse the same form component, title will be set based on editing_index
+    // Reuse the same form component, title will be set based on editing_id
     draw_detours_add(f, area, app, modal_visible);
 }
 
@@ -566,7 +609,7 @@ fn draw_injections_add(f: &mut Frame, area: Rect, app: &App, modal_visible: bool
         crate::components::form_panel::FormField { label: "Description (optional):".to_string(), value: app.injection_form.description.clone(), placeholder: "Brief description of this injection".to_string() },
     ];
     let state = crate::components::form_panel::FormState { active_field: app.injection_form.active_field, cursor_pos: app.injection_form.cursor_pos };
-    let title = if app.injection_form.editing_index.is_some() {
+    let title = if app.injection_form.editing_id.is_some() {
         " Edit Injection "
     } else {
         " Add Injection "
@@ -584,10 +627,12 @@ fn draw_injections_add(f: &mut Frame, area: Rect, app: &App, modal_visible: bool
 
 fn draw_injections_list(f: &mut Frame, area: Rect, app: &mut App, modal_visible: bool) {
     let is_active = app.active_column == ActiveColumn::Content && !modal_visible;
+    let matches = app.injection_filter.apply(&app.injections);
     let items: Vec<crate::components::list_panel::ItemRow> = if app.injections.is_empty() {
         vec![]
     } else {
-        app.injections.iter().map(|inc| {
+        matches.iter().map(|m| {
+            let inc = &app.injections[m.index];
             // Use cached values (only updated on reload)
             let size_str = if inc.size > 1024 * 1024 {
                 format!("{:.1} MB", inc.size as f64 / 1024.0 / 1024.0)
@@ -599,16 +644,21 @@ fn draw_injections_list(f: &mut Frame, area: Rect, app: &mut App, modal_visible:
             let status_text = if inc.active { "✓ Active" } else { "○ Inactive" };
             crate::components::list_panel::ItemRow {
                 line1: format!("{} ← {}", inc.target, inc.include_file),
-                line2: Some(format!("   📝 {}  |  📏 {}  |  {}", inc.modified, size_str, status_text)),
+                line2: Some(format!("   📝 {}  |  📏 {}  |  {}{}{}{}", inc.modified, size_str, status_text,
+                    if inc.template { "  |  🔐 template" } else { "" },
+                    profile_tag(&inc.profile), skip_tag(&inc.skipped))),
                 status_icon: Some(if inc.active { "✓".to_string() } else { "○".to_string() }),
+                highlights: m.positions.clone(),
+                marked: app.marked.contains(&inc.id),
+                dimmed: inc.skipped.is_some(),
             }
         }).collect()
     };
 
-    crate::components::list_panel::draw_list_panel(
+    app.hit_areas.content_rows = crate::components::list_panel::draw_list_panel(
         f,
         area,
-        &format!(" Injections ({}) ", app.injections.len()),
+        &list_title("Injections", matches.len(), app.injections.len(), app.marked_in_view().len(), &app.injection_filter),
         &items,
         &mut app.injection_state,
         is_active,
@@ -619,10 +669,12 @@ fn draw_injections_list(f: &mut Frame, area: Rect, app: &mut App, modal_visible:
 
 fn draw_mirrors_list(f: &mut Frame, area: Rect, app: &mut App, modal_visible: bool) {
     let is_active = app.active_column == ActiveColumn::Content && !modal_visible;
+    let matches = app.mirror_filter.apply(&app.mirrors);
     let items: Vec<crate::components::list_panel::ItemRow> = if app.mirrors.is_empty() {
         vec![]
     } else {
-        app.mirrors.iter().map(|mirror| {
+        matches.iter().map(|m| {
+            let mirror = &app.mirrors[m.index];
             let size_str = if mirror.size > 1024 * 1024 {
                 format!("{:.1} MB", mirror.size as f64 / 1024.0 / 1024.0)
             } else if mirror.size > 1024 {
@@ -637,16 +689,19 @@ fn draw_mirrors_list(f: &mut Frame, area: Rect, app: &mut App, modal_visible: bo
                     mirror.source,
                     mirror.target
                 ),
-                line2: Some(format!("   📝 {}  |  📏 {}  |  {}", mirror.modified, size_str, status_text)),
+                line2: Some(format!("   📝 {}  |  📏 {}  |  {}{}{}", mirror.modified, size_str, status_text, profile_tag(&mirror.profile), skip_tag(&mirror.skipped))),
                 status_icon: Some(if mirror.active { "✓".to_string() } else { "○".to_string() }),
+                highlights: m.positions.iter().map(|p| p + 2).collect(),
+                marked: app.marked.contains(&mirror.id),
+                dimmed: mirror.skipped.is_some(),
             }
         }).collect()
     };
 
-    crate::components::list_panel::draw_list_panel(
+    app.hit_areas.content_rows = crate::components::list_panel::draw_list_panel(
         f,
         area,
-        &format!(" Mirrors ({}) ", app.mirrors.len()),
+        &list_title("Mirrors", matches.len(), app.mirrors.len(), app.marked_in_view().len(), &app.mirror_filter),
         &items,
         &mut app.mirror_state,
         is_active,
@@ -755,74 +810,165 @@ fn draw_services_list(f: &mut Frame, area: Rect, app: &mut App, modal_visible: b
         .highlight_style(highlight_style);
     
     f.render_stateful_widget(list, area, &mut app.service_state);
+    app.hit_areas.content_rows = crate::components::list_panel::row_areas(area, &vec![2; app.services.len()], app.service_state.offset());
 }
 
-fn draw_status_overview(f: &mut Frame, area: Rect, app: &App, modal_visible: bool) {
+fn draw_status_overview(f: &mut Frame, area: Rect, app: &mut App, modal_visible: bool) {
+    use crate::status::{CategoryStatus, StatusTile};
+    
+    // Collected lazily - shelling out to mount/systemctl is too slow for every frame
+    if app.status_report.is_none() {
+        app.refresh_status();
+    }
+    let report = match &app.status_report {
+        Some(report) => report,
+        None => return,
+    };
+    
+    let is_active = app.active_column == ActiveColumn::Content && !modal_visible;
     let border_color = if modal_visible {
         hex_color(0x222222)
     } else {
         Color::White
     };
     
+    let (overall, overall_color) = if report.is_healthy() {
+        ("✓ Healthy", Color::Green)
+    } else {
+        ("⚠ Needs attention", Color::Yellow)
+    };
+    
     let block = Block::default()
-        .title(" System Status ")
+        .title(format!(" System Status - {} ", report.generated_at))
+        .title_bottom(Line::from(Span::styled(format!(" {} ", overall), Style::default().fg(overall_color))))
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
         .border_style(Style::default().fg(border_color));
     
     f.render_widget(block, area);
     
-    let content_area = Rect {
-        x: area.x + 2,
-        y: area.y + 2,
-        width: area.width.saturating_sub(4),
-        height: area.height.saturating_sub(4),
+    let inner = Rect {
+        x: area.x + 1,
+        y: area.y + 1,
+        width: area.width.saturating_sub(2),
+        height: area.height.saturating_sub(2),
     };
     
-    let active_count = app.detours.iter().filter(|d| d.active).count();
-    let total_count = app.detours.len();
-    let injection_count = app.injections.iter().filter(|i| i.active).count();
-    let service_count = app.services.len();
-    
-    let overall_status = if active_count == total_count && total_count > 0 {
-        ("✓ All Active", Color::Green)
-    } else if active_count > 0 {
-        ("⚠ Partial", Color::Yellow)
-    } else {
-        ("○ None Active", Color::DarkGray)
+    let label = Style::default().fg(hex_color(0x888888));
+    let ok = Style::default().fg(Color::Green);
+    let warn = Style::default().fg(Color::Yellow);
+    let bad = Style::default().fg(Color::Red);
+    let dim = Style::default().fg(hex_color(0x666666));
+    
+    let category_lines = |cat: &CategoryStatus| -> Vec<Line> {
+        let mut lines = vec![Line::from(vec![
+            Span::styled(format!("{} total  ", cat.total), label),
+            Span::styled(format!("✓ {}  ", cat.active), ok),
+            Span::styled(format!("○ {}  ", cat.inactive), dim),
+            Span::styled(format!("✗ {}", cat.broken), if cat.broken > 0 { bad } else { dim }),
+        ])];
+        for entry in &cat.failing {
+            lines.push(Line::from(Span::styled(format!("✗ {} ({})", entry.label, entry.reason), bad)));
+        }
+        lines
     };
     
-    let lines = vec![
-        Line::from(vec![
-            Span::styled("Overall: ", Style::default().fg(hex_color(0x888888))),
-            Span::styled(overall_status.0, Style::default().fg(overall_status.1).add_modifier(Modifier::BOLD)),
-        ]),
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("Detours:  ", Style::default().fg(hex_color(0x888888))),
-            Span::styled(format!("{}/{} active", active_count, total_count), Style::default().fg(Color::White)),
-        ]),
-        Line::from(vec![
-            Span::styled("Injections: ", Style::default().fg(hex_color(0x888888))),
-            Span::styled(format!("{} active", injection_count), Style::default().fg(Color::White)),
-        ]),
-        Line::from(vec![
-            Span::styled("Services: ", Style::default().fg(hex_color(0x888888))),
-            Span::styled(format!("{} configured", service_count), Style::default().fg(Color::White)),
-        ]),
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("Profile:  ", Style::default().fg(hex_color(0x888888))),
-            Span::styled(&app.profile, Style::default().fg(Color::Cyan)),
-        ]),
-        Line::from(vec![
-            Span::styled("Config:   ", Style::default().fg(hex_color(0x888888))),
-            Span::styled(&app.config_path, Style::default().fg(hex_color(0x666666))),
-        ]),
-    ];
+    let tile_lines = |tile: StatusTile| -> (Vec<Line>, bool) {
+        match tile {
+            StatusTile::Config => {
+                let mut lines = vec![Line::from(Span::styled(report.config.path.clone(), dim))];
+                match &report.config.error {
+                    Some(error) => lines.push(Line::from(Span::styled(format!("✗ {}", error), bad))),
+                    None => lines.push(Line::from(Span::styled("✓ Parsed", ok))),
+                }
+                (lines, !report.config.parsed)
+            }
+            StatusTile::Privilege => {
+                let usable = report.privilege.sudo_usable || report.privilege.is_root;
+                let who = if report.privilege.is_root { "root" } else { "user" };
+                (vec![Line::from(vec![
+                    Span::styled(if usable { "✓ " } else { "✗ " }, if usable { ok } else { bad }),
+                    Span::styled(format!("{} - {}", who, report.privilege.detail), label),
+                ])], !usable)
+            }
+            StatusTile::Detours => (category_lines(&report.detours), report.detours.broken > 0),
+            StatusTile::Injections => (category_lines(&report.injections), report.injections.broken > 0),
+            StatusTile::Mirrors => (category_lines(&report.mirrors), report.mirrors.broken > 0),
+            StatusTile::Services => {
+                let mut lines = vec![];
+                if report.services.is_empty() {
+                    lines.push(Line::from(Span::styled("No services configured", dim)));
+                }
+                for service in &report.services {
+                    let style = match service.state.as_str() {
+                        "active" => ok,
+                        "failed" => bad,
+                        _ => dim,
+                    };
+                    lines.push(Line::from(vec![
+                        Span::styled(format!("{} ", service.name), label),
+                        Span::styled(format!("[{}]", service.state), style),
+                    ]));
+                }
+                (lines, !report.failed_services().is_empty())
+            }
+            StatusTile::Drift => {
+                let mut lines = vec![];
+                if report.drift.is_empty() {
+                    lines.push(Line::from(Span::styled("✓ No drift detected", ok)));
+                }
+                for entry in &report.drift {
+                    lines.push(Line::from(Span::styled(format!("⚠ {} ({})", entry.label, entry.reason), warn)));
+                }
+                (lines, !report.drift.is_empty())
+            }
+        }
+    };
     
-    let paragraph = Paragraph::new(lines);
-    f.render_widget(paragraph, content_area);
+    // Two-column grid of tiles
+    let rows = StatusTile::ALL.len().div_ceil(2);
+    let tile_height = (inner.height / rows as u16).max(3);
+    let tile_width = inner.width / 2;
+    
+    let mut tile_areas = vec![];
+    for (i, tile) in StatusTile::ALL.iter().enumerate() {
+        let row = (i / 2) as u16;
+        let col = (i % 2) as u16;
+        let y = inner.y + row * tile_height;
+        if y + 2 > inner.y + inner.height {
+            break;
+        }
+        let tile_area = Rect {
+            x: inner.x + col * tile_width,
+            y,
+            width: if col == 1 { inner.width - tile_width } else { tile_width },
+            height: tile_height.min(inner.y + inner.height - y),
+        };
+        
+        let (lines, failing) = tile_lines(*tile);
+        let is_selected = i == app.selected_tile;
+        let tile_border = if is_selected && is_active {
+            Color::Cyan
+        } else if modal_visible {
+            hex_color(0x222222)
+        } else if failing {
+            Color::Red
+        } else {
+            hex_color(0x444444)
+        };
+        let title_style = if failing { bad } else { Style::default().fg(Color::White) };
+        
+        let tile_block = Block::default()
+            .title(Span::styled(format!(" {} ", tile.title()), title_style.add_modifier(Modifier::BOLD)))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(tile_border));
+        
+        let paragraph = Paragraph::new(lines).block(tile_block);
+        f.render_widget(paragraph, tile_area);
+        tile_areas.push((i, tile_area));
+    }
+    app.hit_areas.content_rows = tile_areas;
 }
 
 fn draw_logs_live(f: &mut Frame, area: Rect, app: &App, modal_visible: bool) {
@@ -882,58 +1028,132 @@ fn draw_logs_live(f: &mut Frame, area: Rect, app: &App, modal_visible: bool) {
     }
 }
 
-fn draw_config_edit(f: &mut Frame, area: Rect, app: &App) {
+fn draw_config_edit(f: &mut Frame, area: Rect, app: &mut App) {
+    use crate::config_editor::HighlightKind;
+    
     let modal_visible = app.is_modal_visible();
-    let border_color = if modal_visible {
-        hex_color(0x222222)
+    let is_active = app.active_column == ActiveColumn::Content && !modal_visible;
+    let border_style = if modal_visible {
+        Style::default().fg(hex_color(0x222222))
+    } else if is_active {
+        Style::default().fg(Color::White)
     } else {
-        Color::White
+        Style::default().fg(hex_color(0x333333))
+    };
+    let border_type = if is_active { BorderType::Thick } else { BorderType::Plain };
+    
+    // Preview from Views/Actions columns without loading an editor buffer
+    if app.config_editor.is_none() {
+        app.config_editor = Some(crate::config_editor::ConfigEditor::open(&app.config_path));
+    }
+    let editor = match app.config_editor.as_mut() {
+        Some(editor) => editor,
+        None => return,
     };
     
+    let title = format!(" Configuration - {}{} ", editor.path, if editor.dirty { " [modified]" } else { "" });
     let block = Block::default()
-        .title(format!(" Configuration - {} ", app.config_path))
+        .title(Span::styled(title, Style::default().fg(if is_active { Color::Cyan } else { hex_color(0x777777) })))
         .borders(Borders::ALL)
-        .border_type(BorderType::Rounded)
-        .border_style(Style::default().fg(border_color));
-    
+        .border_type(border_type)
+        .border_style(border_style);
     f.render_widget(block, area);
     
     let content_area = Rect {
-        x: area.x + 2,
-        y: area.y + 2,
-        width: area.width.saturating_sub(4),
-        height: area.height.saturating_sub(4),
+        x: area.x + 1,
+        y: area.y + 1,
+        width: area.width.saturating_sub(2),
+        height: area.height.saturating_sub(3), // Leave room for status line
     };
     
-    // Read config file and display
-    let config_content = std::fs::read_to_string(&app.config_path)
-        .unwrap_or_else(|_| "# Config file not found\n# Create ~/.detour.conf to get started".to_string());
-    
-    let lines: Vec<Line> = config_content.lines()
-        .take(content_area.height as usize)
-        .enumerate()
-        .map(|(idx, line)| {
-            let line_num = format!("{:>4} │ ", idx + 1);
-            let color = if line.trim().starts_with('#') {
-                hex_color(0x666666)
-            } else if line.trim().starts_with("detour ") {
-                Color::Cyan
-            } else if line.trim().starts_with("include ") {
-                Color::Green
-            } else if line.trim().starts_with("service ") {
-                Color::Yellow
-            } else {
-                Color::White
-            };
-            
-            Line::from(vec![
-                Span::styled(line_num, Style::default().fg(hex_color(0x444444))),
-                Span::styled(line, Style::default().fg(color)),
-            ])
-        }).collect();
+    let visible_height = content_area.height as usize;
+    editor.adjust_scroll(visible_height);
+    
+    let dim = modal_visible || !is_active;
+    let color_for = |kind: HighlightKind| -> Color {
+        if modal_visible {
+            return hex_color(0x444444);
+        }
+        match kind {
+            HighlightKind::Key => Color::Cyan,
+            HighlightKind::Dash => Color::Yellow,
+            HighlightKind::String => Color::Green,
+            HighlightKind::Number => Color::Magenta,
+            HighlightKind::Comment => hex_color(0x666666),
+            HighlightKind::Plain => Color::White,
+        }
+    };
     
-    let paragraph = Paragraph::new(lines);
-    f.render_widget(paragraph, content_area);
+    let mut lines: Vec<Line> = vec![];
+    let mut row = editor.scroll_offset;
+    while lines.len() < visible_height && row < editor.lines.len() {
+        let line_no = row + 1;
+        let diags = editor.diagnostics_for_line(line_no);
+        let gutter_style = if diags.iter().any(|d| d.blocking) {
+            Style::default().fg(Color::Red)
+        } else if !diags.is_empty() {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default().fg(hex_color(0x444444))
+        };
+        let marker = if diags.is_empty() { " " } else { "●" };
+        let mut spans = vec![Span::styled(format!("{}{:>4} │ ", marker, line_no), gutter_style)];
+        
+        let text = &editor.lines[row];
+        let is_cursor_line = is_active && row == editor.cursor_row;
+        let mut col = 0usize;
+        for (segment, kind) in crate::config_editor::highlight_line(text) {
+            let style = Style::default().fg(color_for(kind));
+            if is_cursor_line {
+                // Split the segment around the cursor cell
+                for ch in segment.chars() {
+                    let cell_style = if col == editor.cursor_col { style.add_modifier(Modifier::REVERSED) } else { style };
+                    spans.push(Span::styled(ch.to_string(), cell_style));
+                    col += 1;
+                }
+            } else {
+                spans.push(Span::styled(segment, style));
+            }
+        }
+        if is_cursor_line && editor.cursor_col >= col {
+            spans.push(Span::styled(" ", Style::default().add_modifier(Modifier::REVERSED)));
+        }
+        lines.push(Line::from(spans));
+        
+        // Inline diagnostics under the offending line
+        for diag in diags {
+            if lines.len() >= visible_height {
+                break;
+            }
+            let color = if dim { hex_color(0x664444) } else if diag.blocking { Color::Red } else { Color::Yellow };
+            lines.push(Line::from(Span::styled(
+                format!("       {}└─ {}:{} {}", " ".repeat(diag.column.saturating_sub(1).min(60)), diag.line, diag.column, diag.message),
+                Style::default().fg(color),
+            )));
+        }
+        row += 1;
+    }
+    f.render_widget(Paragraph::new(lines), content_area);
+    
+    // Status line
+    let (status_text, status_color) = if editor.has_blocking_errors() {
+        let diag = &editor.diagnostics[0];
+        (format!("✗ Line {}, col {}: {}", diag.line, diag.column, diag.message), Color::Red)
+    } else if !editor.diagnostics.is_empty() {
+        (format!("⚠ {} warning(s)", editor.diagnostics.len()), Color::Yellow)
+    } else {
+        ("✓ Valid".to_string(), Color::Green)
+    };
+    let status = Line::from(vec![
+        Span::styled(format!(" Ln {}, Col {}  ", editor.cursor_row + 1, editor.cursor_col + 1), Style::default().fg(hex_color(0x888888))),
+        Span::styled(status_text, Style::default().fg(if modal_visible { hex_color(0x444444) } else { status_color })),
+    ]);
+    f.render_widget(Paragraph::new(status), Rect {
+        x: area.x + 1,
+        y: area.y + area.height.saturating_sub(2),
+        width: area.width.saturating_sub(2),
+        height: 1,
+    });
 }
 
 fn draw_validation_report(f: &mut Frame, area: Rect, report: &crate::app::ValidationReport) {
@@ -962,7 +1182,7 @@ fn draw_validation_report(f: &mut Frame, area: Rect, report: &crate::app::Valida
     f.render_widget(Clear, popup_area);
     
     // Title and border color based on issues
-    let (title, border_color) = if report.has_issues {
+    let (title, border_color) = if report.has_issues() {
         (" Validation Issues Found ", Color::Red)
     } else {
         (" Validation Passed ✓ ", Color::Green)
@@ -987,19 +1207,59 @@ fn draw_validation_report(f: &mut Frame, area: Rect, report: &crate::app::Valida
         height: inner_area.height,
     };
     
-    // Render content
-    let paragraph = Paragraph::new(report.content.clone())
-        .style(Style::default().fg(Color::White))
-        .alignment(Alignment::Left);
+    // One block per finding: headline, entry, suggestion
+    use crate::checks::Severity;
+    let mut lines: Vec<Line> = vec![];
+    let mut selected_line = 0;
+    if report.report.findings.is_empty() {
+        lines.push(Line::from(Span::styled("✓ No findings", Style::default().fg(Color::Green))));
+    }
+    for (i, finding) in report.report.findings.iter().enumerate() {
+        let is_selected = i == report.selected;
+        if is_selected {
+            selected_line = lines.len();
+        }
+        let color = match finding.severity {
+            Severity::Error => Color::Red,
+            Severity::Warning => Color::Yellow,
+            Severity::Info => Color::Cyan,
+        };
+        let marker = if is_selected { "▶ " } else { "  " };
+        let row_style = if is_selected { Style::default().bg(hex_color(0x1A2A2A)) } else { Style::default() };
+        lines.push(Line::from(vec![
+            Span::styled(marker, Style::default().fg(Color::Cyan)),
+            Span::styled(format!("{} ", finding.severity.icon()), Style::default().fg(color)),
+            Span::styled(finding.message.clone(), Style::default().fg(Color::White).add_modifier(Modifier::BOLD)),
+            Span::styled(format!("  [{}]", finding.check), Style::default().fg(hex_color(0x666666))),
+        ]).style(row_style));
+        if let Some(entry) = &finding.entry {
+            lines.push(Line::from(Span::styled(format!("     {}", entry.label), Style::default().fg(hex_color(0x888888)))).style(row_style));
+        }
+        if let Some(suggestion) = &finding.suggestion {
+            let fix_hint = if finding.fix.is_some() { "[f] " } else { "" };
+            lines.push(Line::from(Span::styled(format!("     → {}{}", fix_hint, suggestion), Style::default().fg(Color::Green))).style(row_style));
+        }
+    }
+    
+    let visible = padded_area.height.saturating_sub(1) as usize;
+    let scroll = selected_line.saturating_sub(visible.saturating_sub(3));
+    let paragraph = Paragraph::new(lines)
+        .alignment(Alignment::Left)
+        .scroll((scroll as u16, 0));
     
-    f.render_widget(paragraph, padded_area);
+    f.render_widget(paragraph, Rect { height: padded_area.height.saturating_sub(1), ..padded_area });
     
     // Help text at bottom
-    let help_text = " Press [Enter] to close ";
+    let help_text = format!(" {} errors · {} warnings · {} checks in {:.1}ms   [↑↓] Select  [Enter] Go to  [f] Fix  [Esc] Close ",
+        report.report.count(Severity::Error),
+        report.report.count(Severity::Warning),
+        report.report.checks.len(),
+        report.report.duration_ms);
+    let help_width = (help_text.chars().count() as u16).min(popup_area.width);
     let help_area = Rect {
-        x: popup_area.x + (popup_area.width.saturating_sub(help_text.len() as u16)) / 2,
+        x: popup_area.x + (popup_area.width.saturating_sub(help_width)) / 2,
         y: popup_area.y + popup_area.height.saturating_sub(1),
-        width: help_text.len() as u16,
+        width: help_width,
         height: 1,
     };
     
@@ -1010,26 +1270,29 @@ fn draw_validation_report(f: &mut Frame, area: Rect, report: &crate::app::Valida
 }
 
 fn get_panel_help(app: &App) -> String {
+    if app.is_searching() && app.active_column == ActiveColumn::Content {
+        return "[type] Search  [↑↓] Move  [Enter] Keep  [Esc] Clear".to_string();
+    }
     match app.view_mode {
         ViewMode::DetoursList => {
             match app.active_column {
                 ActiveColumn::Views => "[n] New  [v] Verify All  [a] Activate All".to_string(),
                 ActiveColumn::Actions => "[n] New  [v] Verify All  [a] Activate All".to_string(),
-                ActiveColumn::Content => "[Space] Toggle  [n] New  [e] Edit  [Del] Remove  [d] Diff  [v] Verify".to_string(),
+                ActiveColumn::Content => "[Space] Toggle  [n] New  [e] Edit  [Del] Remove  [d] Diff  [P] Pristine  [v] Verify  [/] Search  [f] Filter  [o] Sort  [m/M] Mark  [b] Bulk  [H] History  [E] $EDITOR".to_string(),
             }
         }
         ViewMode::InjectionsList => {
             match app.active_column {
                 ActiveColumn::Views => "[n] New".to_string(),
                 ActiveColumn::Actions => "[n] New".to_string(),
-                ActiveColumn::Content => "[Space] Toggle  [n] New  [e] Edit  [Del] Remove  [v] Verify".to_string(),
+                ActiveColumn::Content => "[Space] Toggle  [n] New  [e] Edit  [Del] Remove  [v] Verify  [/] Search  [f] Filter  [o] Sort  [m/M] Mark  [b] Bulk  [H] History  [E] $EDITOR".to_string(),
             }
         }
         ViewMode::MirrorsList => {
             match app.active_column {
                 ActiveColumn::Views => "[n] New".to_string(),
                 ActiveColumn::Actions => "[n] New".to_string(),
-                ActiveColumn::Content => "[Space] Toggle  [n] New  [e] Edit  [Del] Remove".to_string(),
+                ActiveColumn::Content => "[Space] Toggle  [n] New  [e] Edit  [Del] Remove  [v] Verify  [/] Search  [f] Filter  [o] Sort  [m/M] Mark  [b] Bulk  [H] History  [E] $EDITOR".to_string(),
             }
         }
         ViewMode::MirrorsAdd | ViewMode::MirrorsEdit => {
@@ -1042,10 +1305,13 @@ fn get_panel_help(app: &App) -> String {
             "[c] Clear  [s] Save".to_string()
         }
         ViewMode::ConfigEdit => {
-            "[r] Reload  [v] Validate".to_string()
+            match app.active_column {
+                ActiveColumn::Content => "[Ctrl+S] Save  [Ctrl+Z] Undo  [Ctrl+Y] Redo  [Ctrl+G] Go to Error  [Ctrl+R] Revert  [Esc] Back".to_string(),
+                _ => "[Enter] Edit".to_string(),
+            }
         }
         ViewMode::StatusOverview => {
-            "[s] Stop All".to_string()
+            "[↑↓] Select Tile  [Enter] Go to Failing  [r] Refresh".to_string()
         }
         ViewMode::DetoursAdd | ViewMode::DetoursEdit => {
             "[Tab] Next Field  [Ctrl+F] Browse  [Ctrl+V] Paste  [Enter] Save  [Esc] Cancel".to_string()
@@ -0,0 +1,92 @@
+// Bulk operations: confirmation text and execution over marked entries
+
+use detour::config::EntryKind;
+use detour::operations::bulk::{BulkOp, BulkPlan};
+use detour::operations::config_ops::load_config;
+use tui_common::testing::ScratchDir;
+
+const CONFIG: &str = "detours:
+  - id: d1
+    original: /nonexistent/etc/a.conf
+    custom: /nonexistent/home/a.conf
+  - id: d2
+    original: /nonexistent/etc/b.conf
+    custom: /nonexistent/home/b.conf
+    profile: web
+    when:
+      file_exists: /nonexistent/marker
+mirrors:
+  - id: m1
+    source: /nonexistent/data
+    target: /nonexistent/srv/data
+";
+
+fn setup(name: &str) -> (ScratchDir, String) {
+    let dir = ScratchDir::new("detour-bulk", name);
+    let config = dir.path().join("detour.yaml");
+    std::fs::write(&config, CONFIG).unwrap();
+    let config = config.to_string_lossy().to_string();
+    (dir, config)
+}
+
+fn plan(op: BulkOp, kind: EntryKind, ids: &[&str]) -> BulkPlan {
+    BulkPlan { op, kind, ids: ids.iter().map(|id| id.to_string()).collect() }
+}
+
+#[test]
+fn describe_lists_entries_and_truncates() {
+    let (_dir, path) = setup("describe");
+    let config = load_config(&path);
+
+    let text = plan(BulkOp::Apply, EntryKind::Detour, &["d1", "d2"]).describe(&config);
+    assert_eq!(text, "Apply 2 Detour entries:\n\n• /nonexistent/etc/a.conf ← /nonexistent/home/a.conf\n• /nonexistent/etc/b.conf ← /nonexistent/home/b.conf");
+
+    let delete = plan(BulkOp::Delete, EntryKind::Mirror, &["m1"]).describe(&config);
+    assert!(delete.starts_with("Delete 1 Mirror entries:\n\n• /nonexistent/data → /nonexistent/srv/data\n"), "{}", delete);
+    assert!(delete.ends_with("custom files are kept."));
+
+    let ids: Vec<String> = (0..13).map(|i| format!("gone{}", i)).collect();
+    let many = BulkPlan { op: BulkOp::MoveToProfile("lab".to_string()), kind: EntryKind::Detour, ids }.describe(&config);
+    assert!(many.starts_with("Move to profile 'lab' 13 Detour entries:"));
+    // Unknown ids fall back to the id itself
+    assert!(many.contains("\n• gone9\n…and 3 more"), "{}", many);
+    assert!(!many.contains("gone10"));
+}
+
+#[test]
+fn move_to_profile_is_one_save_and_blank_clears_it() {
+    let (_dir, path) = setup("profile");
+
+    let report = plan(BulkOp::MoveToProfile(" lab ".to_string()), EntryKind::Detour, &["d1", "d2"]).execute(&path);
+    assert_eq!(report.to_text(), "2 succeeded, 0 failed");
+    let config = load_config(&path);
+    assert!(config.detours.iter().all(|d| d.profile.as_deref() == Some("lab")));
+
+    plan(BulkOp::MoveToProfile("  ".to_string()), EntryKind::Detour, &["d2"]).execute(&path);
+    let config = load_config(&path);
+    assert_eq!(config.detours[0].profile.as_deref(), Some("lab"));
+    assert_eq!(config.detours[1].profile, None);
+}
+
+#[test]
+fn delete_drops_inactive_entries_from_config() {
+    let (_dir, path) = setup("delete");
+
+    let report = plan(BulkOp::Delete, EntryKind::Mirror, &["m1"]).execute(&path);
+    assert_eq!(report.failed().count(), 0);
+    assert_eq!(report.outcomes[0].label, "/nonexistent/data → /nonexistent/srv/data");
+    let config = load_config(&path);
+    assert!(config.mirrors.is_empty());
+    assert_eq!(config.detours.len(), 2);
+}
+
+#[test]
+fn apply_skips_entries_whose_when_does_not_match() {
+    let (_dir, path) = setup("skip");
+
+    let report = plan(BulkOp::Apply, EntryKind::Detour, &["d2"]).execute(&path);
+    assert_eq!(
+        report.to_text(),
+        "0 succeeded, 1 failed\n✗ /nonexistent/etc/b.conf ← /nonexistent/home/b.conf: skipped, when: /nonexistent/marker does not exist"
+    );
+}
@@ -0,0 +1,110 @@
+// Bundles: path remapping, import planning and checksum verification
+
+use detour::bundle::{export, remap_path, Action, ImportPlan, Remap};
+use detour::config::EntryKind;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Scratch dir holding a config with one detour whose custom file lives under `home/`
+fn setup(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("detour-bundle-{}-{}", std::process::id(), name));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("home")).unwrap();
+    std::fs::write(dir.join("home/app.conf"), "port: 80\n").unwrap();
+    std::fs::write(dir.join("detour.yaml"), format!(
+        "detours:\n  - id: app\n    original: /etc/app.conf\n    custom: {}\n",
+        dir.join("home/app.conf").display()
+    )).unwrap();
+    dir
+}
+
+fn export_bundle(dir: &Path) -> PathBuf {
+    let bundle = dir.join("bundle.tar.gz");
+    export(&dir.join("detour.yaml").to_string_lossy(), &bundle).unwrap();
+    bundle
+}
+
+/// Import target: a config at `dir/local.yaml` and the bundle's home/ remapped to `dir/other`
+fn prepare(dir: &Path, bundle: &Path, local: &str) -> ImportPlan {
+    std::fs::write(dir.join("local.yaml"), local).unwrap();
+    let remap = Remap::parse(&format!("{}={}", dir.join("home").display(), dir.join("other").display())).unwrap();
+    ImportPlan::prepare(bundle, &[remap], &dir.join("local.yaml").to_string_lossy()).unwrap()
+}
+
+#[test]
+fn remaps_match_whole_path_components() {
+    let remaps = vec![Remap::parse("/home/pi/=/home/alice").unwrap(), Remap::parse("/home=/srv").unwrap()];
+    assert_eq!(remap_path("/home/pi/app.conf", &remaps), "/home/alice/app.conf");
+    assert_eq!(remap_path("/home/pi", &remaps), "/home/alice");
+    // `/home/pi` is not a prefix of `/home/pipe` on a component boundary
+    assert_eq!(remap_path("/home/pipe/x", &remaps), "/srv/pipe/x");
+    assert_eq!(remap_path("/homes/x", &remaps), "/homes/x");
+    assert_eq!(remap_path("/etc/app.conf", &[]), "/etc/app.conf");
+
+    assert!(Remap::parse("no-separator").is_err());
+    assert!(Remap::parse("=/to").is_err());
+}
+
+#[test]
+fn import_plan_remaps_and_creates() {
+    let dir = setup("create");
+    let bundle = export_bundle(&dir);
+    let plan = prepare(&dir, &bundle, "detours: []\n");
+
+    assert!(plan.checksum_errors.is_empty());
+    assert!(!plan.has_conflicts());
+    assert_eq!(plan.config.detours[0].custom, dir.join("other/app.conf").to_string_lossy());
+    assert_eq!(plan.files[0].action, Action::Create);
+    assert_eq!((plan.entries[0].kind, plan.entries[0].action.clone()), (EntryKind::Detour, Action::Create));
+
+    let summary = plan.apply(&dir.join("local.yaml").to_string_lossy(), false).unwrap();
+    assert_eq!((summary.files_written, summary.entries_added), (1, 1));
+    assert_eq!(std::fs::read_to_string(dir.join("other/app.conf")).unwrap(), "port: 80\n");
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn entries_conflict_by_id_and_by_slot() {
+    let dir = setup("conflict");
+    let bundle = export_bundle(&dir);
+
+    // Same id, different paths
+    let plan = prepare(&dir, &bundle, "detours:\n  - id: app\n    original: /etc/other.conf\n    custom: /x\n");
+    assert!(plan.has_conflicts());
+    assert!(matches!(&plan.entries[0].action, Action::Conflict { reason, local_id: Some(id) }
+        if reason.starts_with("id already used by") && id == "app"));
+
+    // Different id, same original
+    let plan = prepare(&dir, &bundle, "detours:\n  - id: mine\n    original: /etc/app.conf\n    custom: /x\n");
+    assert!(matches!(&plan.entries[0].action, Action::Conflict { reason, local_id: Some(id) }
+        if reason.starts_with("already configured as mine") && id == "mine"));
+
+    // Identical entry
+    let local = format!("detours:\n  - id: app\n    original: /etc/app.conf\n    custom: {}\n", dir.join("other/app.conf").display());
+    let plan = prepare(&dir, &bundle, &local);
+    assert_eq!(plan.entries[0].action, Action::Unchanged);
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn tampered_files_are_rejected() {
+    let dir = setup("checksum");
+    let bundle = export_bundle(&dir);
+
+    // Repack the bundle with the custom file changed behind the manifest's back
+    let unpacked = dir.join("unpacked");
+    std::fs::create_dir_all(&unpacked).unwrap();
+    let tar = |args: &[&str]| assert!(Command::new("tar").args(args).status().unwrap().success());
+    tar(&["-xzf", &bundle.to_string_lossy(), "-C", &unpacked.to_string_lossy()]);
+    let bundled = unpacked.join("files").join(dir.join("home/app.conf").to_string_lossy().trim_start_matches('/'));
+    std::fs::write(&bundled, "port: 6666\n").unwrap();
+    tar(&["-czf", &bundle.to_string_lossy(), "-C", &unpacked.to_string_lossy(), "config.yaml", "manifest.json", "files"]);
+
+    let plan = prepare(&dir, &bundle, "detours: []\n");
+    assert_eq!(plan.checksum_errors, vec![dir.join("home/app.conf").to_string_lossy().to_string()]);
+    assert!(plan.files.is_empty());
+    let err = plan.apply(&dir.join("local.yaml").to_string_lossy(), true).unwrap_err();
+    assert!(err.starts_with("Refusing to import"), "{}", err);
+    assert!(!dir.join("other/app.conf").exists());
+    std::fs::remove_dir_all(&dir).ok();
+}
@@ -0,0 +1,75 @@
+// `when:` host predicates: globs, package specs and evaluation
+
+use detour::conditions::{glob_match, parse_package, skip_reason, Host};
+use detour::config::When;
+
+fn host() -> Host {
+    Host { hostname: "pi-kitchen".to_string(), arch: "aarch64".to_string(), model: String::new() }
+}
+
+#[test]
+fn glob_supports_star_and_question_mark() {
+    assert!(glob_match("pi-*", "pi-kitchen"));
+    assert!(glob_match("*kitchen", "pi-kitchen"));
+    assert!(glob_match("pi-?itchen", "pi-kitchen"));
+    assert!(glob_match("*", ""));
+    assert!(glob_match("a*b*c", "aXbYbZc"));
+    assert!(glob_match("Raspberry Pi 4*", "Raspberry Pi 4 Model B Rev 1.4"));
+
+    assert!(!glob_match("pi-*", "nuc-kitchen"));
+    assert!(!glob_match("pi-?", "pi-"));
+    assert!(!glob_match("aarch64", "aarch64x"));
+    assert!(!glob_match("", "x"));
+}
+
+#[test]
+fn package_specs_split_name_operator_and_version() {
+    assert_eq!(parse_package("nginx"), ("nginx", None));
+    assert_eq!(parse_package(" nginx "), ("nginx", None));
+    assert_eq!(parse_package("nginx>=1.18"), ("nginx", Some((">=", "1.18"))));
+    assert_eq!(parse_package("nginx <= 1.18"), ("nginx", Some(("<=", "1.18"))));
+    assert_eq!(parse_package("libc6>2.31"), ("libc6", Some((">", "2.31"))));
+    assert_eq!(parse_package("libc6<2.31"), ("libc6", Some(("<", "2.31"))));
+    assert_eq!(parse_package("klipper=0.12.0-1"), ("klipper", Some(("=", "0.12.0-1"))));
+}
+
+#[test]
+fn every_set_predicate_must_match() {
+    let host = host();
+    assert_eq!(When::default().evaluate(&host), Ok(()));
+
+    let when = When { hostname: Some("pi-*".to_string()), arch: Some("aarch64".to_string()), ..Default::default() };
+    assert_eq!(when.evaluate(&host), Ok(()));
+
+    let when = When { hostname: Some("pi-*".to_string()), arch: Some("armv7*".to_string()), ..Default::default() };
+    assert_eq!(when.evaluate(&host), Err("arch aarch64 ≠ armv7*".to_string()));
+
+    let when = When { model: Some("Raspberry Pi*".to_string()), ..Default::default() };
+    assert_eq!(when.evaluate(&host), Err("model unknown ≠ Raspberry Pi*".to_string()));
+}
+
+#[test]
+fn file_and_env_predicates() {
+    let host = host();
+    let manifest = concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml");
+    assert_eq!(When { file_exists: Some(manifest.to_string()), ..Default::default() }.evaluate(&host), Ok(()));
+    assert_eq!(
+        When { file_exists: Some("/nonexistent/marker".to_string()), ..Default::default() }.evaluate(&host),
+        Err("/nonexistent/marker does not exist".to_string())
+    );
+
+    std::env::set_var("DETOUR_TEST_WHEN", "production-eu");
+    let env = |spec: &str| When { env: Some(spec.to_string()), ..Default::default() }.evaluate(&host);
+    assert_eq!(env("DETOUR_TEST_WHEN"), Ok(()));
+    assert_eq!(env("DETOUR_TEST_WHEN=production-*"), Ok(()));
+    assert_eq!(env("DETOUR_TEST_WHEN=staging"), Err("env DETOUR_TEST_WHEN=staging not matched".to_string()));
+    assert_eq!(env("DETOUR_TEST_WHEN_UNSET"), Err("env DETOUR_TEST_WHEN_UNSET not matched".to_string()));
+}
+
+#[test]
+fn skip_reason_is_the_first_mismatch() {
+    let host = host();
+    let when = When { hostname: Some("nuc-*".to_string()), arch: Some("x86_64".to_string()), ..Default::default() };
+    assert_eq!(skip_reason(Some(&when), &host), Some("hostname pi-kitchen ≠ nuc-*".to_string()));
+    assert_eq!(skip_reason(None, &host), None);
+}
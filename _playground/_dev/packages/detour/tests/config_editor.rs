@@ -0,0 +1,134 @@
+// Embedded config editor: undo/redo, dirty tracking and live validation
+
+use detour::config_editor::{ConfigEditor, MAX_UNDO};
+
+const CONFIG: &str = "detours:\n  - original: /etc/a\n    custom: /home/a\n";
+
+fn type_text(editor: &mut ConfigEditor, text: &str) {
+    for c in text.chars() {
+        editor.insert_char(c);
+    }
+}
+
+#[test]
+fn undo_and_redo_restore_text_and_cursor() {
+    let mut editor = ConfigEditor::from_content("/tmp/unused.yaml", CONFIG);
+    editor.move_end();
+    type_text(&mut editor, " #x");
+    assert_eq!(editor.lines[0], "detours: #x");
+
+    assert!(editor.undo());
+    assert_eq!(editor.lines[0], "detours: #");
+    assert_eq!(editor.cursor_col, 10);
+
+    assert!(editor.redo());
+    assert_eq!(editor.lines[0], "detours: #x");
+    assert_eq!(editor.cursor_col, 11);
+}
+
+#[test]
+fn new_edit_clears_redo() {
+    let mut editor = ConfigEditor::from_content("/tmp/unused.yaml", CONFIG);
+    editor.move_end();
+    type_text(&mut editor, "ab");
+    editor.undo();
+    editor.insert_char('c');
+    assert!(!editor.redo());
+    assert_eq!(editor.lines[0], "detours:ac");
+}
+
+#[test]
+fn undoing_back_to_saved_text_is_clean() {
+    let mut editor = ConfigEditor::from_content("/tmp/unused.yaml", CONFIG);
+    assert!(!editor.dirty);
+
+    editor.move_end();
+    type_text(&mut editor, " #");
+    assert!(editor.dirty);
+
+    editor.undo();
+    assert!(editor.dirty);
+    editor.undo();
+    assert!(!editor.dirty);
+    assert_eq!(editor.content(), CONFIG);
+
+    editor.redo();
+    assert!(editor.dirty);
+}
+
+#[test]
+fn saved_text_becomes_the_clean_state() {
+    let path = std::env::temp_dir().join(format!("detour-editor-{}.yaml", std::process::id()));
+    std::fs::write(&path, CONFIG).unwrap();
+    let mut editor = ConfigEditor::open(&path.to_string_lossy());
+
+    editor.move_end();
+    editor.insert_char(' ');
+    editor.save().unwrap();
+    assert!(!editor.dirty);
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "detours: \n  - original: /etc/a\n    custom: /home/a\n");
+
+    // Undo now moves away from what is on disk
+    editor.undo();
+    assert!(editor.dirty);
+    std::fs::remove_file(&path).ok();
+}
+
+#[test]
+fn undo_history_is_capped() {
+    let mut editor = ConfigEditor::from_content("/tmp/unused.yaml", CONFIG);
+    editor.move_end();
+    for _ in 0..MAX_UNDO + 25 {
+        editor.insert_char(' ');
+    }
+
+    let mut undone = 0;
+    while editor.undo() {
+        undone += 1;
+    }
+    assert_eq!(undone, MAX_UNDO);
+    // The oldest edits fell off, so the buffer keeps 25 of the spaces
+    assert_eq!(editor.lines[0], format!("detours:{}", " ".repeat(25)));
+}
+
+#[test]
+fn parse_errors_are_blocking_and_located() {
+    let mut editor = ConfigEditor::from_content("/tmp/unused.yaml", CONFIG);
+    assert!(editor.diagnostics.is_empty());
+
+    // Break the indentation of the second line
+    editor.move_down();
+    editor.move_home();
+    editor.delete();
+    assert!(editor.has_blocking_errors());
+    let diagnostic = &editor.diagnostics[0];
+    assert!(diagnostic.line >= 2, "{:?}", diagnostic);
+    assert!(!diagnostic.message.contains(" at line "));
+
+    editor.undo();
+    assert!(!editor.has_blocking_errors());
+}
+
+#[test]
+fn save_is_refused_while_invalid() {
+    let mut editor = ConfigEditor::from_content("/nonexistent/detour.yaml", "detours: [\n");
+    let err = editor.save().unwrap_err();
+    assert!(err.starts_with("Cannot save while config is invalid"), "{}", err);
+}
+
+#[test]
+fn unknown_sections_are_warnings() {
+    let editor = ConfigEditor::from_content("/tmp/unused.yaml", "detours: []\nextras: 1\n");
+    assert!(!editor.has_blocking_errors());
+    let warnings = editor.diagnostics_for_line(2);
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].message.contains("extras"));
+}
+
+#[test]
+fn duplicate_ids_are_blocking() {
+    let config = "detours:\n  - id: same\n    original: /etc/a\n    custom: /home/a\n  - id: same\n    original: /etc/b\n    custom: /home/b\n";
+    let editor = ConfigEditor::from_content("/tmp/unused.yaml", config);
+    assert!(editor.has_blocking_errors());
+    assert_eq!(editor.diagnostics_for_line(5).len(), 1);
+}
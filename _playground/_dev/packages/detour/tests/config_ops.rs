@@ -0,0 +1,58 @@
+// Config loading: generated ids stay in memory until explicitly written
+
+use detour::operations::config_ops::{has_missing_ids, load_config, write_missing_ids};
+
+const CONFIG: &str = "# my detours\ndetours:\n  - original: /etc/a.conf   # keep me\n    custom: /home/pi/a.conf\n";
+
+fn scratch(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("detour-config-ops-{}-{}", std::process::id(), name));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir.join("detour.yaml")
+}
+
+#[test]
+fn loading_does_not_rewrite_the_file() {
+    let path = scratch("load");
+    std::fs::write(&path, CONFIG).unwrap();
+    let path_str = path.to_string_lossy();
+
+    let config = load_config(&path_str);
+    assert!(!config.detours[0].id.is_empty());
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), CONFIG);
+    assert!(has_missing_ids(&path_str));
+}
+
+#[test]
+fn generated_ids_are_stable_across_loads() {
+    let path = scratch("stable");
+    std::fs::write(&path, CONFIG).unwrap();
+    let path_str = path.to_string_lossy();
+
+    let first = load_config(&path_str).detours[0].id.clone();
+    let second = load_config(&path_str).detours[0].id.clone();
+    assert_eq!(first, second);
+
+    // Writing them back keeps the id the TUI has been using
+    assert_eq!(write_missing_ids(&path_str), Ok(true));
+    assert!(!has_missing_ids(&path_str));
+    assert_eq!(load_config(&path_str).detours[0].id, first);
+    assert_eq!(write_missing_ids(&path_str), Ok(false));
+}
+
+#[test]
+fn failures_are_reported() {
+    // A directory where the file should be fails even as root
+    let blocked = scratch("blocked").with_file_name("blocked.yaml");
+    std::fs::create_dir_all(&blocked).unwrap();
+
+    assert!(write_missing_ids(&blocked.to_string_lossy()).is_err());
+}
+
+#[test]
+fn generated_ids_do_not_depend_on_the_toolchain() {
+    let path = scratch("pinned");
+    std::fs::write(&path, CONFIG).unwrap();
+
+    // FNV-1a of the entry seed - a change here breaks ids users have already seen
+    assert_eq!(load_config(&path.to_string_lossy()).detours[0].id, "63880a5f");
+}
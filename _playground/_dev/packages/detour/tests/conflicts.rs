@@ -0,0 +1,89 @@
+// Conflict detection between config entries
+
+use detour::checks::{default_checks, Check, Severity};
+use detour::checks::duplicates::{DuplicateIds, DuplicateOriginals};
+use detour::checks::mirrors::CircularMirrors;
+use detour::config::DetourConfig;
+
+fn config(yaml: &str) -> DetourConfig {
+    serde_yaml::from_str(yaml).unwrap()
+}
+
+fn severities(check: &dyn Check, config: &DetourConfig) -> Vec<(String, Severity)> {
+    check.run(config).into_iter()
+        .map(|f| (f.entry.map(|e| e.id).unwrap_or_default(), f.severity))
+        .collect()
+}
+
+#[test]
+fn same_original_with_different_custom_is_an_error() {
+    let config = config("
+detours:
+  - {id: a, original: /etc/app.conf, custom: /home/pi/one.conf}
+  - {id: b, original: /etc/app.conf, custom: /home/pi/two.conf}
+  - {id: c, original: /etc/other.conf, custom: /home/pi/two.conf}
+");
+    assert_eq!(severities(&DuplicateOriginals, &config), vec![("b".to_string(), Severity::Error)]);
+}
+
+#[test]
+fn same_original_with_same_custom_is_a_warning() {
+    let config = config("
+detours:
+  - {id: a, original: /etc/app.conf, custom: /home/pi/app.conf}
+  - {id: b, original: /etc/app.conf, custom: /home/pi/app.conf}
+");
+    assert_eq!(severities(&DuplicateOriginals, &config), vec![("b".to_string(), Severity::Warning)]);
+}
+
+#[test]
+fn reused_id_across_kinds_is_an_error() {
+    let config = config("
+detours:
+  - {id: dup, original: /etc/app.conf, custom: /home/pi/app.conf}
+mirrors:
+  - {id: dup, source: /data/a, target: /srv/a}
+  - {id: unique, source: /data/b, target: /srv/b}
+");
+    assert_eq!(severities(&DuplicateIds, &config), vec![("dup".to_string(), Severity::Error)]);
+}
+
+#[test]
+fn mirror_into_itself_is_an_error() {
+    let config = config("
+mirrors:
+  - {id: self, source: /data, target: /data/nested/link}
+");
+    assert_eq!(severities(&CircularMirrors, &config), vec![("self".to_string(), Severity::Error)]);
+}
+
+#[test]
+fn mirror_cycle_flags_every_member() {
+    let config = config("
+mirrors:
+  - {id: a, source: /one, target: /two}
+  - {id: b, source: /two/x, target: /three}
+  - {id: c, source: /three/x, target: /one}
+  - {id: d, source: /four, target: /five}
+");
+    let flagged: Vec<String> = severities(&CircularMirrors, &config).into_iter().map(|(id, _)| id).collect();
+    assert_eq!(flagged, vec!["a", "b", "c"]);
+}
+
+#[test]
+fn chained_mirrors_without_a_cycle_are_fine() {
+    let config = config("
+mirrors:
+  - {id: a, source: /one, target: /two}
+  - {id: b, source: /two/x, target: /three}
+");
+    assert!(CircularMirrors.run(&config).is_empty());
+}
+
+#[test]
+fn conflict_checks_are_registered() {
+    let names: Vec<&str> = default_checks().iter().map(|c| c.name()).collect();
+    for name in ["duplicate-ids", "duplicate-originals", "circular-mirrors"] {
+        assert!(names.contains(&name), "{} not registered", name);
+    }
+}
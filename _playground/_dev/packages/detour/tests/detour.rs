@@ -0,0 +1,103 @@
+// DetourManager bind mounts against the fake mount table
+
+use detour::backend::fake::{FakeMount, FakeSystem};
+use detour::backend::Filesystem;
+use detour::config::DetourEntry;
+use detour::manager::DetourManager;
+use std::path::Path;
+
+fn setup() -> (FakeSystem, DetourManager) {
+    let system = FakeSystem::new();
+    system.fs.add_file("/etc/app.conf", "original");
+    system.fs.add_file("/home/pi/app.conf", "custom");
+    let manager = DetourManager::with_backend(system.backend());
+    (system, manager)
+}
+
+fn entry(yaml: &str) -> DetourEntry {
+    serde_yaml::from_str(yaml).unwrap()
+}
+
+#[test]
+fn apply_and_remove_bind_mount() {
+    let (system, manager) = setup();
+    assert!(!manager.is_active("/etc/app.conf"));
+
+    manager.apply_detour("/etc/app.conf", "/home/pi/app.conf", false).unwrap();
+    assert!(manager.is_active("/etc/app.conf"));
+    assert_eq!(system.mounts.table(), vec![FakeMount {
+        source: "/home/pi/app.conf".to_string(),
+        target: "/etc/app.conf".to_string(),
+        read_only: false,
+    }]);
+
+    manager.remove_detour("/etc/app.conf").unwrap();
+    assert!(!manager.is_active("/etc/app.conf"));
+}
+
+#[test]
+fn readonly_remounts_the_bind() {
+    let (system, manager) = setup();
+
+    let msg = manager.apply_detour("/etc/app.conf", "/home/pi/app.conf", true).unwrap();
+    assert!(msg.ends_with("(read-only)"), "{}", msg);
+    assert!(system.mounts.table()[0].read_only);
+}
+
+#[test]
+fn failed_readonly_remount_is_rolled_back() {
+    let (system, manager) = setup();
+    *system.mounts.fail_remount.lock().unwrap() = true;
+
+    assert!(manager.apply_detour("/etc/app.conf", "/home/pi/app.conf", true).is_err());
+    assert!(system.mounts.table().is_empty());
+}
+
+#[test]
+fn missing_custom_file_is_an_error() {
+    let (system, manager) = setup();
+
+    assert!(manager.apply_detour("/etc/app.conf", "/home/pi/missing.conf", false).is_err());
+    assert!(system.mounts.table().is_empty());
+}
+
+#[test]
+fn removing_an_inactive_detour_is_an_error() {
+    let (_system, manager) = setup();
+
+    assert!(manager.remove_detour("/etc/app.conf").is_err());
+}
+
+#[test]
+fn entry_reports_owner_mode_mismatch() {
+    let (system, manager) = setup();
+    system.fs.set_owner_mode("/home/pi/app.conf", 1000, 1000, 0o600);
+
+    let msg = manager.apply_entry(&entry("{original: /etc/app.conf, custom: /home/pi/app.conf}")).unwrap();
+    assert!(msg.contains("⚠ original is 0:0 0644, custom is 1000:1000 0600"), "{}", msg);
+    // Left as is without match_permissions
+    assert_eq!(system.fs.owner_mode(Path::new("/home/pi/app.conf")), Some((1000, 1000, 0o600)));
+}
+
+#[test]
+fn entry_matches_permissions_when_asked() {
+    let (system, manager) = setup();
+    system.fs.set_owner_mode("/home/pi/app.conf", 1000, 1000, 0o600);
+
+    manager.apply_entry(&entry("{original: /etc/app.conf, custom: /home/pi/app.conf, match_permissions: true}")).unwrap();
+    assert_eq!(system.fs.owner_mode(Path::new("/home/pi/app.conf")), Some((0, 0, 0o644)));
+    assert!(manager.permission_mismatch("/etc/app.conf", "/home/pi/app.conf").is_none());
+}
+
+#[test]
+fn entry_readonly_flag_reaches_the_mount() {
+    let (system, manager) = setup();
+
+    manager.apply_entry(&entry("{original: /etc/app.conf, custom: /home/pi/app.conf, readonly: true}")).unwrap();
+    assert!(system.mounts.table()[0].read_only);
+    assert!(manager.is_entry_active("", "/etc/app.conf", None));
+
+    manager.remove_entry("", "/etc/app.conf", None).unwrap();
+    assert!(system.mounts.table().is_empty());
+    assert!(system.fs.contents(Path::new("/etc/app.conf")).is_some());
+}
@@ -0,0 +1,19 @@
+// Shared file operations
+
+use detour::operations::file_ops::write_private_temp;
+use std::os::unix::fs::PermissionsExt;
+
+#[test]
+fn private_temp_files_are_unique_and_owner_only() {
+    let first = write_private_temp("a.conf", "one").unwrap();
+    let second = write_private_temp("a.conf", "two").unwrap();
+    assert_ne!(first, second);
+    assert!(first.file_name().unwrap().to_string_lossy().ends_with("-a.conf"));
+
+    assert_eq!(std::fs::read_to_string(&first).unwrap(), "one");
+    assert_eq!(std::fs::read_to_string(&second).unwrap(), "two");
+    assert_eq!(std::fs::metadata(&first).unwrap().permissions().mode() & 0o777, 0o600);
+
+    std::fs::remove_file(&first).ok();
+    std::fs::remove_file(&second).ok();
+}
@@ -0,0 +1,85 @@
+// File browser keys and recent-path state through events::handle_event
+
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use detour::app::App;
+use detour::events;
+use detour::filebrowser::{load_recent, BrowserMode};
+use std::path::PathBuf;
+
+const CONFIG: &str = "# hand-written\ndetours: []   # none yet\n";
+
+/// App over a scratch config, with a directory of three files to browse
+fn setup(name: &str) -> (App, PathBuf) {
+    let dir = std::env::temp_dir().join(format!("detour-browser-{}-{}", std::process::id(), name));
+    let files = dir.join("files");
+    std::fs::create_dir_all(&files).unwrap();
+    for file in ["alpha.conf", "beta.conf", "gamma.conf"] {
+        std::fs::write(files.join(file), "x").unwrap();
+    }
+    let config = dir.join("detour.yaml");
+    std::fs::write(&config, CONFIG).unwrap();
+
+    let mut app = App::with_config_path(&config.to_string_lossy());
+    app.recent_paths_file = dir.join("state").join("recent-paths");
+    app.open_file_browser(&files.to_string_lossy());
+    (app, dir)
+}
+
+fn keys(app: &mut App, codes: &[KeyCode]) {
+    for code in codes {
+        events::handle_event(Event::Key(KeyEvent::new(*code, KeyModifiers::NONE)), app);
+    }
+}
+
+fn selected_name(app: &App) -> String {
+    let browser = app.file_browser.as_ref().unwrap();
+    browser.entries[browser.selected_index].name.clone()
+}
+
+#[test]
+fn j_and_k_navigate() {
+    let (mut app, _) = setup("jk");
+    keys(&mut app, &[KeyCode::Char('j'), KeyCode::Char('j')]);
+    assert_eq!(selected_name(&app), "beta.conf");
+    keys(&mut app, &[KeyCode::Char('k')]);
+    assert_eq!(selected_name(&app), "alpha.conf");
+    assert!(app.file_browser.as_ref().unwrap().filter.is_empty());
+}
+
+#[test]
+fn slash_opens_the_filter() {
+    let (mut app, _) = setup("filter");
+    keys(&mut app, &[KeyCode::Char('/'), KeyCode::Char('g'), KeyCode::Char('a')]);
+    let browser = app.file_browser.as_ref().unwrap();
+    assert_eq!(browser.mode, BrowserMode::Filter);
+    assert_eq!(browser.filter, "ga");
+    assert!(browser.entries.iter().all(|e| e.name == ".." || e.name == "gamma.conf"));
+
+    // Enter keeps the filter and hands j/k back to navigation
+    keys(&mut app, &[KeyCode::Enter, KeyCode::Char('j')]);
+    let browser = app.file_browser.as_ref().unwrap();
+    assert_eq!(browser.mode, BrowserMode::Browse);
+    assert_eq!(browser.filter, "ga");
+    assert_eq!(selected_name(&app), "gamma.conf");
+
+    // Esc while filtering clears it
+    keys(&mut app, &[KeyCode::Char('/'), KeyCode::Esc]);
+    let browser = app.file_browser.as_ref().unwrap();
+    assert!(browser.filter.is_empty());
+    assert_eq!(browser.entries.len(), 4);
+}
+
+#[test]
+fn picked_paths_go_to_the_state_file_not_the_config() {
+    let (mut app, dir) = setup("recent");
+    keys(&mut app, &[KeyCode::Char('j'), KeyCode::Enter]);
+    assert!(app.file_browser.is_none());
+
+    let picked = dir.join("files").join("alpha.conf").to_string_lossy().to_string();
+    assert_eq!(load_recent(&app.recent_paths_file), vec![picked.clone()]);
+    assert_eq!(std::fs::read_to_string(dir.join("detour.yaml")).unwrap(), CONFIG);
+
+    // The next browser offers it as a place
+    app.open_file_browser(&dir.to_string_lossy());
+    assert_eq!(app.file_browser.as_ref().unwrap().recent, vec![picked]);
+}
@@ -0,0 +1,62 @@
+// Hook commands: output capture, timeouts and the apply/rollback sequence
+
+use detour::config::EntryHooks;
+use detour::hooks;
+use std::time::{Duration, Instant};
+
+#[test]
+fn output_and_status_are_captured() {
+    let run = hooks::run("validate", "echo out; echo err >&2; exit 3", Duration::from_secs(5));
+    assert!(!run.success);
+    assert!(!run.timed_out);
+    assert_eq!(run.output, "out\nerr");
+}
+
+#[test]
+fn timeout_kills_the_hook() {
+    let started = Instant::now();
+    let run = hooks::run("pre_apply", "sleep 5", Duration::from_millis(300));
+    assert!(run.timed_out);
+    assert!(!run.success);
+    assert!(started.elapsed() < Duration::from_secs(2), "took {:?}", started.elapsed());
+}
+
+#[test]
+fn timeout_kills_child_processes_too() {
+    // `sleep` inherits the pipes, so only killing `sh` would leave the readers blocked
+    let started = Instant::now();
+    let run = hooks::run("pre_apply", "echo started; sleep 8; true", Duration::from_millis(300));
+    assert!(run.timed_out);
+    assert!(started.elapsed() < Duration::from_secs(2), "took {:?}", started.elapsed());
+    assert_eq!(run.output, "started");
+}
+
+#[test]
+fn background_process_holding_the_pipes_does_not_block() {
+    let started = Instant::now();
+    let run = hooks::run("post_apply", "sleep 8 & echo done", Duration::from_millis(500));
+    assert!(run.success);
+    assert!(!run.timed_out);
+    assert!(started.elapsed() < Duration::from_secs(2), "took {:?}", started.elapsed());
+    assert_eq!(run.output, "done");
+}
+
+#[test]
+fn failing_validate_rolls_back() {
+    let hooks = EntryHooks { validate: Some("exit 1".to_string()), ..Default::default() };
+    let mut rolled_back = false;
+    let result = hooks::apply(Some(&hooks), || Ok("applied".to_string()), || {
+        rolled_back = true;
+        Ok(String::new())
+    });
+    let err = result.unwrap_err();
+    assert!(err.starts_with("validate hook failed, rolled back"), "{}", err);
+    assert!(rolled_back);
+}
+
+#[test]
+fn failing_pre_apply_skips_apply() {
+    let hooks = EntryHooks { pre_apply: Some("exit 1".to_string()), ..Default::default() };
+    let result = hooks::apply(Some(&hooks), || panic!("applied despite pre_apply"), || Ok(String::new()));
+    assert!(result.unwrap_err().starts_with("pre_apply hook failed"));
+}
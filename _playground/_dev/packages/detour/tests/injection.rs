@@ -0,0 +1,160 @@
+// InjectionManager against the in-memory backend, one block per file type
+
+use detour::backend::fake::FakeSystem;
+use detour::injection::InjectionManager;
+use std::path::Path;
+
+fn setup() -> (FakeSystem, InjectionManager) {
+    let system = FakeSystem::new();
+    let manager = InjectionManager::with_backend(system.backend());
+    (system, manager)
+}
+
+/// Apply, check, remove and check again, returning the applied contents
+fn round_trip(system: &FakeSystem, manager: &InjectionManager, target: &str, include: &str) -> String {
+    let (target, include) = (Path::new(target), Path::new(include));
+    assert!(!manager.is_applied(target, include));
+
+    manager.apply(target, include).expect("apply");
+    assert!(manager.is_applied(target, include));
+    let applied = system.fs.contents(target).unwrap();
+
+    manager.remove(target, include).expect("remove");
+    assert!(!manager.is_applied(target, include));
+    applied
+}
+
+#[test]
+fn yaml_include_is_added_under_the_include_stem() {
+    let (system, manager) = setup();
+    system.fs.add_file("/cfg/configuration.yaml", "homeassistant:\n  name: Home\n");
+    system.fs.add_file("/cfg/custom.yaml", "sensor: []\n");
+
+    let applied = round_trip(&system, &manager, "/cfg/configuration.yaml", "/cfg/custom.yaml");
+    assert!(applied.contains("custom: '!include custom.yaml'"), "{}", applied);
+
+    let removed = system.fs.contents("/cfg/configuration.yaml").unwrap();
+    assert!(removed.contains("name: Home"));
+    assert!(!removed.contains("custom"));
+}
+
+#[test]
+fn yaml_apply_is_idempotent() {
+    let (system, manager) = setup();
+    system.fs.add_file("/cfg/configuration.yaml", "homeassistant: {}\n");
+    system.fs.add_file("/cfg/custom.yaml", "");
+    let (target, include) = (Path::new("/cfg/configuration.yaml"), Path::new("/cfg/custom.yaml"));
+
+    manager.apply(target, include).unwrap();
+    let once = system.fs.contents(target).unwrap();
+    manager.apply(target, include).unwrap();
+    assert_eq!(system.fs.contents(target).unwrap(), once);
+}
+
+#[test]
+fn c_header_include_goes_after_existing_includes() {
+    let (system, manager) = setup();
+    let original = "#include <stdio.h>\nint x;";
+    system.fs.add_file("/src/config.h", original);
+    system.fs.add_file("/src/local.h", "#define LOCAL 1\n");
+
+    let applied = round_trip(&system, &manager, "/src/config.h", "/src/local.h");
+    assert_eq!(applied, "#include <stdio.h>\n#include \"local.h\"\nint x;");
+    assert_eq!(system.fs.contents("/src/config.h").unwrap(), original);
+}
+
+#[test]
+fn shell_source_goes_after_the_shebang() {
+    let (system, manager) = setup();
+    let original = "#!/bin/sh\necho hi";
+    system.fs.add_file("/etc/profile.d/env.sh", original);
+    system.fs.add_file("/etc/profile.d/extra.sh", "export A=1\n");
+
+    let applied = round_trip(&system, &manager, "/etc/profile.d/env.sh", "/etc/profile.d/extra.sh");
+    assert_eq!(applied, "#!/bin/sh\nsource \"extra.sh\"\necho hi");
+    assert_eq!(system.fs.contents("/etc/profile.d/env.sh").unwrap(), original);
+}
+
+#[test]
+fn raspberry_pi_config_gets_a_wrapped_block() {
+    let (system, manager) = setup();
+    let original = "dtparam=audio=on\n";
+    system.fs.add_file("/boot/firmware/config.txt", original);
+    system.fs.add_file("/boot/firmware/extra.txt", "dtoverlay=vc4-kms-v3d\n");
+
+    let applied = round_trip(&system, &manager, "/boot/firmware/config.txt", "/boot/firmware/extra.txt");
+    assert_eq!(applied, "dtparam=audio=on\n# BEGIN DETOUR INJECTION\ninclude extra.txt\n# END DETOUR INJECTION\n");
+    assert_eq!(system.fs.contents("/boot/firmware/config.txt").unwrap(), original);
+}
+
+#[test]
+fn config_file_gets_a_comment_with_the_absolute_path() {
+    let (system, manager) = setup();
+    system.fs.add_file("/etc/app/app.conf", "key=value");
+    system.fs.add_file("/opt/overrides/extra.conf", "other=1\n");
+
+    let applied = round_trip(&system, &manager, "/etc/app/app.conf", "/opt/overrides/extra.conf");
+    assert_eq!(applied, "key=value\n# Include: /opt/overrides/extra.conf\n");
+    assert_eq!(system.fs.contents("/etc/app/app.conf").unwrap(), "key=value");
+}
+
+#[test]
+fn unknown_file_type_uses_the_generic_comment() {
+    let (system, manager) = setup();
+    system.fs.add_file("/etc/app/settings", "plain text");
+    system.fs.add_file("/etc/app/local", "more");
+
+    let applied = round_trip(&system, &manager, "/etc/app/settings", "/etc/app/local");
+    assert_eq!(applied, "plain text\n# Include: local\n");
+}
+
+#[test]
+fn existing_directive_decides_the_type_over_the_extension() {
+    let (system, manager) = setup();
+    // .conf would be generic, but the `source` line marks it as shell
+    system.fs.add_file("/etc/app/env.conf", "source \"base.sh\"\n");
+    system.fs.add_file("/etc/app/extra.sh", "");
+
+    manager.apply(Path::new("/etc/app/env.conf"), Path::new("/etc/app/extra.sh")).unwrap();
+    assert_eq!(system.fs.contents("/etc/app/env.conf").unwrap(), "source \"extra.sh\"\nsource \"base.sh\"");
+}
+
+#[test]
+fn missing_target_file_is_created() {
+    let (system, manager) = setup();
+    system.fs.add_dir("/cfg");
+    system.fs.add_file("/cfg/custom.yaml", "");
+
+    manager.apply(Path::new("/cfg/new.yaml"), Path::new("/cfg/custom.yaml")).unwrap();
+    assert!(system.fs.contents("/cfg/new.yaml").unwrap().contains("!include custom.yaml"));
+}
+
+#[test]
+fn missing_include_file_is_an_error() {
+    let (system, manager) = setup();
+    system.fs.add_file("/cfg/configuration.yaml", "a: 1\n");
+
+    let err = manager.apply(Path::new("/cfg/configuration.yaml"), Path::new("/cfg/missing.yaml")).unwrap_err();
+    assert!(err.contains("Include file does not exist"), "{}", err);
+    assert_eq!(system.fs.contents("/cfg/configuration.yaml").unwrap(), "a: 1\n");
+}
+
+#[test]
+fn protected_target_falls_back_to_privileged_write() {
+    let (system, manager) = setup();
+    system.fs.add_file("/etc/app/app.conf", "key=value");
+    system.fs.add_file("/etc/app/extra.conf", "");
+    system.fs.protect("/etc/app/app.conf");
+
+    manager.apply(Path::new("/etc/app/app.conf"), Path::new("/etc/app/extra.conf")).unwrap();
+    assert!(manager.is_applied(Path::new("/etc/app/app.conf"), Path::new("/etc/app/extra.conf")));
+}
+
+#[test]
+fn removing_from_a_missing_target_is_a_no_op() {
+    let (system, manager) = setup();
+    system.fs.add_file("/cfg/custom.yaml", "");
+
+    assert!(manager.remove(Path::new("/cfg/gone.yaml"), Path::new("/cfg/custom.yaml")).is_ok());
+    assert!(system.fs.contents("/cfg/gone.yaml").is_none());
+}
@@ -0,0 +1,147 @@
+// MirrorManager against the in-memory backend
+
+use detour::backend::fake::{FakeSystem, Node};
+use detour::mirror::MirrorManager;
+use std::path::PathBuf;
+
+fn setup() -> (FakeSystem, MirrorManager) {
+    let system = FakeSystem::new();
+    system.fs.add_file("/data/source.conf", "contents");
+    let manager = MirrorManager::with_backend(system.backend());
+    (system, manager)
+}
+
+fn link(path: &str) -> Node {
+    Node::Symlink(PathBuf::from(path))
+}
+
+#[test]
+fn creates_symlink_and_reports_active() {
+    let (system, manager) = setup();
+
+    manager.apply_mirror("/data/source.conf", "/etc/app/target.conf").unwrap();
+    assert_eq!(system.fs.node("/etc/app/target.conf"), Some(link("/data/source.conf")));
+    assert_eq!(system.fs.contents("/etc/app/target.conf").unwrap(), "contents");
+    assert!(manager.is_active("/data/source.conf", "/etc/app/target.conf"));
+}
+
+#[test]
+fn missing_parent_directories_are_created() {
+    let (system, manager) = setup();
+
+    manager.apply_mirror("/data/source.conf", "/new/nested/dir/target.conf").unwrap();
+    assert_eq!(system.fs.node("/new/nested/dir"), Some(Node::Dir));
+}
+
+#[test]
+fn applying_twice_is_a_no_op() {
+    let (system, manager) = setup();
+    manager.apply_mirror("/data/source.conf", "/etc/target.conf").unwrap();
+
+    let msg = manager.apply_mirror("/data/source.conf", "/etc/target.conf").unwrap();
+    assert!(msg.starts_with("Symlink already exists"), "{}", msg);
+    assert_eq!(system.fs.node("/etc/target.conf"), Some(link("/data/source.conf")));
+}
+
+#[test]
+fn symlink_to_another_source_is_replaced() {
+    let (system, manager) = setup();
+    system.fs.add_file("/data/old.conf", "old");
+    system.fs.add_symlink("/etc/target.conf", "/data/old.conf");
+    assert!(!manager.is_active("/data/source.conf", "/etc/target.conf"));
+
+    manager.apply_mirror("/data/source.conf", "/etc/target.conf").unwrap();
+    assert_eq!(system.fs.node("/etc/target.conf"), Some(link("/data/source.conf")));
+    assert_eq!(system.fs.contents("/data/old.conf").unwrap(), "old");
+}
+
+#[test]
+fn dangling_symlink_is_replaced() {
+    let (system, manager) = setup();
+    system.fs.add_symlink("/etc/target.conf", "/data/deleted.conf");
+
+    manager.apply_mirror("/data/source.conf", "/etc/target.conf").unwrap();
+    assert!(manager.is_active("/data/source.conf", "/etc/target.conf"));
+}
+
+#[test]
+fn regular_file_at_target_is_a_conflict() {
+    let (system, manager) = setup();
+    system.fs.add_file("/etc/target.conf", "real file");
+
+    let err = manager.apply_mirror("/data/source.conf", "/etc/target.conf").unwrap_err();
+    assert!(err.contains("not a symlink"), "{}", err);
+    assert_eq!(system.fs.contents("/etc/target.conf").unwrap(), "real file");
+}
+
+#[test]
+fn directory_at_target_is_a_conflict() {
+    let (system, manager) = setup();
+    system.fs.add_dir("/etc/target.d");
+
+    assert!(manager.apply_mirror("/data/source.conf", "/etc/target.d").is_err());
+    assert_eq!(system.fs.node("/etc/target.d"), Some(Node::Dir));
+}
+
+#[test]
+fn missing_source_is_an_error() {
+    let (system, manager) = setup();
+
+    let err = manager.apply_mirror("/data/missing.conf", "/etc/target.conf").unwrap_err();
+    assert!(err.contains("Source path does not exist"), "{}", err);
+    assert_eq!(system.fs.node("/etc/target.conf"), None);
+}
+
+#[test]
+fn directory_source_can_be_mirrored() {
+    let (system, manager) = setup();
+    system.fs.add_file("/data/tree/a.txt", "a");
+
+    manager.apply_mirror("/data/tree", "/srv/tree").unwrap();
+    assert_eq!(system.fs.contents("/srv/tree/a.txt").unwrap(), "a");
+}
+
+#[test]
+fn remove_deletes_only_the_link() {
+    let (system, manager) = setup();
+    manager.apply_mirror("/data/source.conf", "/etc/target.conf").unwrap();
+
+    manager.remove_mirror("/etc/target.conf").unwrap();
+    assert_eq!(system.fs.node("/etc/target.conf"), None);
+    assert_eq!(system.fs.contents("/data/source.conf").unwrap(), "contents");
+    assert!(!manager.is_active("/data/source.conf", "/etc/target.conf"));
+}
+
+#[test]
+fn remove_of_missing_target_succeeds() {
+    let (_system, manager) = setup();
+
+    let msg = manager.remove_mirror("/etc/target.conf").unwrap();
+    assert!(msg.starts_with("Symlink does not exist"), "{}", msg);
+}
+
+#[test]
+fn remove_refuses_regular_files() {
+    let (system, manager) = setup();
+    system.fs.add_file("/etc/target.conf", "real file");
+
+    assert!(manager.remove_mirror("/etc/target.conf").is_err());
+    assert_eq!(system.fs.contents("/etc/target.conf").unwrap(), "real file");
+}
+
+#[test]
+fn remove_cleans_up_dangling_symlink() {
+    let (system, manager) = setup();
+    system.fs.add_symlink("/etc/target.conf", "/data/deleted.conf");
+
+    manager.remove_mirror("/etc/target.conf").unwrap();
+    assert_eq!(system.fs.node("/etc/target.conf"), None);
+}
+
+#[test]
+fn inactive_when_source_is_gone() {
+    let (system, manager) = setup();
+    system.fs.add_symlink("/etc/target.conf", "/data/deleted.conf");
+
+    assert!(!manager.is_active("/data/deleted.conf", "/etc/target.conf"));
+}
@@ -0,0 +1,45 @@
+// dpkg output parsing for pristine package lookups
+
+use detour::package::{deb_prefix, parse_conffiles, parse_owner};
+
+#[test]
+fn owner_is_the_first_listed_package() {
+    let output = "nginx-common, nginx-core: /etc/nginx/nginx.conf\n";
+    assert_eq!(parse_owner(output, "/etc/nginx/nginx.conf").as_deref(), Some("nginx-common"));
+}
+
+#[test]
+fn owner_skips_diversions_and_other_paths() {
+    let output = "diversion by dash from: /bin/sh\ndash: /bin/sh.distrib\nlibc-bin:arm64: /bin/sh\n";
+    assert_eq!(parse_owner(output, "/bin/sh").as_deref(), Some("libc-bin:arm64"));
+    assert_eq!(parse_owner(output, "/bin/bash"), None);
+}
+
+#[test]
+fn deb_prefix_escapes_epoch_and_drops_arch() {
+    assert_eq!(deb_prefix("openssh-server:arm64", "1:9.2p1-2"), "openssh-server_1%3a9.2p1-2_");
+}
+
+#[test]
+fn conffiles_are_keyed_by_path() {
+    let status = "\
+Package: nginx-common
+Status: install ok installed
+Conffiles:
+ /etc/nginx/nginx.conf 0123456789abcdef0123456789abcdef
+ /etc/nginx/old.conf fedcba9876543210fedcba9876543210 obsolete
+Description: small, powerful, scalable web/proxy server
+ Nginx is a web server.
+
+Package: dash
+Conffiles:
+ /etc/dash.conf aaaabbbbccccddddeeeeffff00001111
+";
+    let conffiles = parse_conffiles(status);
+    assert_eq!(conffiles.len(), 3);
+    let nginx = &conffiles["/etc/nginx/nginx.conf"];
+    assert_eq!((nginx.package.as_str(), nginx.md5.as_str(), nginx.obsolete),
+        ("nginx-common", "0123456789abcdef0123456789abcdef", false));
+    assert!(conffiles["/etc/nginx/old.conf"].obsolete);
+    assert_eq!(conffiles["/etc/dash.conf"].package, "dash");
+}
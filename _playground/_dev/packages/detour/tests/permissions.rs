@@ -0,0 +1,60 @@
+// Permission checks: which unit has to read a file, and the fixes offered
+
+use detour::checks::permissions::ServiceUserReadable;
+use detour::checks::{Check, Fix};
+use detour::config::DetourConfig;
+use std::os::unix::fs::PermissionsExt;
+
+fn config(yaml: &str) -> DetourConfig {
+    serde_yaml::from_str(yaml).unwrap()
+}
+
+#[test]
+fn consumer_is_the_explicit_service_or_the_scoped_unit() {
+    let config = config("
+detours:
+  - {id: a, original: /etc/a.conf, custom: /home/pi/a.conf, scope: {unit: klipper}}
+  - {id: b, original: /etc/b.conf, custom: /home/pi/b.conf, scope: {unit: klipper}, service: moonraker}
+  - {id: c, original: /etc/c.conf, custom: /home/pi/c.conf, scope: {command: make}}
+  - {id: d, original: /etc/d.conf, custom: /home/pi/d.conf}
+");
+    let consumers: Vec<_> = config.detours.iter().map(|d| d.consumer()).collect();
+    assert_eq!(consumers, vec![Some("klipper"), Some("moonraker"), None, None]);
+}
+
+#[test]
+fn files_without_a_consumer_are_not_checked() {
+    // Unreadable to everyone, but no unit is known to read them
+    let dir = std::env::temp_dir().join(format!("detour-permissions-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let custom = dir.join("private.conf");
+    std::fs::write(&custom, "x").unwrap();
+    std::fs::set_permissions(&custom, std::fs::Permissions::from_mode(0o600)).unwrap();
+
+    let config = config(&format!("
+detours:
+  - {{id: a, original: /etc/a.conf, custom: {0}}}
+injections:
+  - {{id: b, target: /etc/b.conf, include: {0}}}
+", custom.display()));
+    assert!(ServiceUserReadable.run(&config).is_empty());
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn read_access_goes_to_the_group_not_everyone() {
+    let fix = Fix::GrantRead { path: "/home/pi/a.conf".to_string(), group: "klipper".to_string() };
+    assert_eq!(fix.describe(), "chgrp klipper and chmod g+r /home/pi/a.conf");
+}
+
+#[test]
+fn missing_file_without_source_is_created_empty() {
+    let dir = std::env::temp_dir().join(format!("detour-create-file-{}", std::process::id()));
+    let path = dir.join("nested").join("new.conf");
+    let fix = Fix::CreateFile { path: path.to_string_lossy().to_string(), source: None };
+    assert_eq!(fix.describe(), format!("Create empty {}", path.display()));
+
+    fix.apply("/nonexistent/detour.yaml").unwrap();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "");
+    std::fs::remove_dir_all(&dir).ok();
+}
@@ -0,0 +1,25 @@
+// Popup click targets line up with what is drawn
+
+use detour::popup::{draw_popup, Popup};
+use ratatui::backend::TestBackend;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::Terminal;
+
+fn text_at(buffer: &Buffer, rect: Rect) -> String {
+    (rect.x..rect.x + rect.width).map(|x| buffer.get(x, rect.y).symbol()).collect()
+}
+
+#[test]
+fn confirm_buttons_are_hit_where_they_are_drawn() {
+    // Odd and even content widths center differently
+    for (width, message) in [(100, "Delete?"), (101, "Delete?"), (120, "Remove the detour for /etc/nginx/nginx.conf?")] {
+        let mut terminal = Terminal::new(TestBackend::new(width, 30)).unwrap();
+        let mut targets = vec![];
+        let frame = terminal.draw(|f| targets = draw_popup(f, f.size(), &Popup::confirm("Confirm", message))).unwrap();
+
+        assert_eq!(targets.len(), 2);
+        assert_eq!(text_at(frame.buffer, targets[0].1), "[ Yes ]", "width {}", width);
+        assert_eq!(text_at(frame.buffer, targets[1].1), "[ No ]", "width {}", width);
+    }
+}
@@ -0,0 +1,58 @@
+// Scoped detours: unit drop-ins and namespace commands
+
+use detour::config::DetourScope;
+use detour::scope::{dropin_contents, dropin_path, namespace_script};
+
+fn unit(name: &str) -> DetourScope {
+    DetourScope { unit: Some(name.to_string()), ..Default::default() }
+}
+
+fn command(cmd: &str) -> DetourScope {
+    DetourScope { command: Some(cmd.to_string()), ..Default::default() }
+}
+
+#[test]
+fn dropin_binds_custom_over_original() {
+    assert_eq!(
+        dropin_contents("a1b2c3d4", "/etc/klipper.cfg", "/home/pi/klipper.cfg", false),
+        "# Managed by detour (entry a1b2c3d4)\n[Service]\nBindPaths=/home/pi/klipper.cfg:/etc/klipper.cfg\n"
+    );
+    assert!(dropin_contents("x", "/etc/a", "/home/a", true).contains("\nBindReadOnlyPaths=/home/a:/etc/a\n"));
+}
+
+#[test]
+fn dropin_path_completes_the_unit_name() {
+    assert_eq!(dropin_path("x", "klipper").to_string_lossy(), "/etc/systemd/system/klipper.service.d/detour-x.conf");
+    assert_eq!(dropin_path("x", "moonraker.socket").to_string_lossy(), "/etc/systemd/system/moonraker.socket.d/detour-x.conf");
+}
+
+#[test]
+fn scope_needs_exactly_one_target() {
+    assert!(unit("klipper").validate().is_ok());
+    assert!(command("python3 app.py").validate().is_ok());
+
+    let both = DetourScope { unit: Some("klipper".to_string()), command: Some("true".to_string()), read_only: false };
+    assert_eq!(both.validate(), Err("scope sets both unit and command".to_string()));
+    assert_eq!(DetourScope::default().validate(), Err("scope needs a unit or a command".to_string()));
+    assert_eq!(unit("../evil").validate(), Err("invalid unit name '../evil'".to_string()));
+}
+
+#[test]
+fn labels_name_the_target() {
+    assert_eq!(unit("klipper").label(), "unit klipper.service");
+    assert_eq!(DetourScope { read_only: true, ..unit("klipper.service") }.label(), "unit klipper.service (ro)");
+    assert_eq!(command("make test").label(), "namespace `make test`");
+    assert_eq!(DetourScope::default().label(), "invalid scope");
+}
+
+#[test]
+fn namespace_command_runs_as_the_caller() {
+    let script = namespace_script(false);
+    let mount = script.find("mount --bind").unwrap();
+    let ready = script.find("echo $$").unwrap();
+    let drop = script.find("exec setpriv --reuid=\"$5\" --regid=\"$6\" --init-groups sh -c \"$3\"").unwrap();
+    assert!(mount < ready && ready < drop, "{}", script);
+    assert!(!script.contains("remount"));
+
+    assert!(namespace_script(true).contains("mount -o remount,bind,ro \"$2\" && echo $$"));
+}
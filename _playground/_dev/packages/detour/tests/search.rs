@@ -0,0 +1,111 @@
+// List search: fuzzy matching, filtering/sorting and selection syncing
+
+use detour::app::App;
+use detour::search::{fuzzy_match, ListFilter, Searchable, SortKey, StatusFilter};
+
+struct Item {
+    text: &'static str,
+    description: Option<&'static str>,
+    active: bool,
+    broken: bool,
+    modified: u64,
+    size: u64,
+}
+
+impl Searchable for Item {
+    fn search_text(&self) -> String { self.text.to_string() }
+    fn description(&self) -> Option<&str> { self.description }
+    fn is_active(&self) -> bool { self.active }
+    fn is_broken(&self) -> bool { self.broken }
+    fn modified_secs(&self) -> u64 { self.modified }
+    fn size(&self) -> u64 { self.size }
+}
+
+fn items() -> Vec<Item> {
+    vec![
+        Item { text: "/etc/nginx/nginx.conf", description: None, active: true, broken: false, modified: 10, size: 300 },
+        Item { text: "/etc/klipper/printer.cfg", description: Some("3D printer"), active: false, broken: true, modified: 30, size: 100 },
+        Item { text: "/etc/gunicorn.conf", description: None, active: false, broken: false, modified: 20, size: 200 },
+    ]
+}
+
+fn indices(filter: &ListFilter, items: &[Item]) -> Vec<usize> {
+    filter.apply(items).into_iter().map(|m| m.index).collect()
+}
+
+#[test]
+fn fuzzy_match_is_a_case_insensitive_subsequence() {
+    let (_, positions) = fuzzy_match("NGX", "nginx.conf").unwrap();
+    assert_eq!(positions, vec![0, 1, 4]);
+    assert_eq!(fuzzy_match("", "anything"), Some((0, vec![])));
+    assert_eq!(fuzzy_match("xn", "nginx"), None);
+    assert_eq!(fuzzy_match("nginxx", "nginx"), None);
+}
+
+#[test]
+fn fuzzy_match_prefers_adjacent_and_boundary_hits() {
+    let score = |query, text| fuzzy_match(query, text).unwrap().0;
+    assert!(score("conf", "app.conf") > score("conf", "c_o_n_f"));
+    assert!(score("nc", "nginx.conf") < score("nc", "nc.conf"));
+    // Word starts after `/`, `.`, `_`, `-` and spaces count extra
+    assert!(score("c", "app.conf") > score("c", "apcnf"));
+}
+
+#[test]
+fn query_filters_and_ranks_best_match_first() {
+    let items = items();
+    let filter = ListFilter { query: "conf".to_string(), ..Default::default() };
+    // printer.cfg has no `o`; gunicorn's earlier `o` splits its match where nginx.conf's `onf` stays together
+    assert_eq!(indices(&filter, &items), vec![0, 2]);
+
+    let filter = ListFilter { query: "gun".to_string(), ..Default::default() };
+    let matches = filter.apply(&items);
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].positions, vec![5, 6, 7]);
+}
+
+#[test]
+fn description_matches_have_nothing_to_highlight() {
+    let items = items();
+    let filter = ListFilter { query: "3d".to_string(), ..Default::default() };
+    let matches = filter.apply(&items);
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].index, 1);
+    assert!(matches[0].positions.is_empty());
+}
+
+#[test]
+fn status_filter_and_sort_keys() {
+    let items = items();
+    let with = |status, sort| ListFilter { status, sort, ..Default::default() };
+    assert_eq!(indices(&with(StatusFilter::All, SortKey::Config), &items), vec![0, 1, 2]);
+    assert_eq!(indices(&with(StatusFilter::Active, SortKey::Config), &items), vec![0]);
+    assert_eq!(indices(&with(StatusFilter::Inactive, SortKey::Config), &items), vec![1, 2]);
+    assert_eq!(indices(&with(StatusFilter::Broken, SortKey::Config), &items), vec![1]);
+
+    assert_eq!(indices(&with(StatusFilter::All, SortKey::Path), &items), vec![2, 1, 0]);
+    assert_eq!(indices(&with(StatusFilter::All, SortKey::Modified), &items), vec![1, 2, 0]);
+    assert_eq!(indices(&with(StatusFilter::All, SortKey::Size), &items), vec![0, 2, 1]);
+    assert!(!ListFilter::default().is_active());
+    assert!(with(StatusFilter::All, SortKey::Size).is_active());
+}
+
+#[test]
+fn selection_follows_the_filter_and_parks_past_the_end_when_empty() {
+    let mut app = App::with_config_path(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/ui.yaml"));
+    app.selected_detour = 1;
+
+    // The selected klipper detour is filtered out, so the last visible row is taken
+    app.update_filter(|f| f.query = "nginx".to_string());
+    assert_eq!(app.selected_detour, 0);
+    assert_eq!(app.detour_state.selected(), Some(0));
+
+    // Nothing visible: the selection points past the list so lookups find nothing
+    app.update_filter(|f| f.query = "zzz".to_string());
+    assert_eq!(app.selected_detour, app.detours.len());
+    assert_eq!(app.detour_state.selected(), None);
+
+    app.update_filter(ListFilter::clear);
+    assert_eq!(app.selected_detour, 1);
+    assert_eq!(app.detour_state.selected(), Some(1));
+}
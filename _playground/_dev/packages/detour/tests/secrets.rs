@@ -0,0 +1,146 @@
+// Secret placeholders in injection templates
+
+use detour::config::{InjectionEntry, SecretSettings};
+use detour::app::App;
+use detour::editor::PendingEdit;
+use detour::secrets::{default_output, redact_rendered, render, write_private, REDACTED};
+use std::os::unix::fs::PermissionsExt;
+
+fn scratch(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("detour-secrets-{}-{}", std::process::id(), name));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn env_and_command_placeholders_are_substituted() {
+    std::env::set_var("DETOUR_TEST_TOKEN", "abc123");
+    let rendered = render("token: {{env:DETOUR_TEST_TOKEN}}\nkey: {{cmd:printf 's3cret'}}\n", &SecretSettings::default()).unwrap();
+    assert_eq!(rendered.contents, "token: abc123\nkey: s3cret\n");
+}
+
+#[test]
+fn secrets_file_values_are_substituted() {
+    let file = scratch("file").join("secrets.yaml");
+    std::fs::write(&file, "api_key: xyz\nport: 8123\n").unwrap();
+    let settings = SecretSettings { file: Some(file.to_string_lossy().to_string()) };
+
+    let rendered = render("{{secret:api_key}}:{{secret:port}}", &settings).unwrap();
+    assert_eq!(rendered.contents, "xyz:8123");
+}
+
+#[test]
+fn unresolved_placeholders_are_errors() {
+    assert!(render("{{env:DETOUR_TEST_UNSET_VARIABLE}}", &SecretSettings::default()).is_err());
+    assert!(render("{{secret:api_key}}", &SecretSettings::default()).is_err());
+    assert!(render("{{cmd:exit 1}}", &SecretSettings::default()).is_err());
+}
+
+#[test]
+fn other_placeholders_are_kept() {
+    let rendered = render("name: {{hostname}} {{ states('sun') }}", &SecretSettings::default()).unwrap();
+    assert_eq!(rendered.contents, "name: {{hostname}} {{ states('sun') }}");
+}
+
+#[test]
+fn redaction_follows_the_template_not_current_values() {
+    let dir = scratch("stale");
+    let marker = dir.join("ran");
+    let template = format!(
+        "token: {{{{env:DETOUR_TEST_ROTATED}}}}\nkey: {{{{cmd:touch {}; printf new}}}}\nname: {{{{hostname}}}}\n",
+        marker.display()
+    );
+    std::env::set_var("DETOUR_TEST_ROTATED", "fresh-token");
+
+    // Rendered before both values rotated
+    let on_disk = "token: stale-token\nkey: old-otp-123\nname: {{hostname}}\n";
+    assert_eq!(
+        redact_rendered(&template, on_disk),
+        format!("token: {r}\nkey: {r}\nname: {{{{hostname}}}}\n", r = REDACTED)
+    );
+    assert!(!marker.exists(), "redaction ran the command");
+}
+
+#[test]
+fn adjacent_and_edge_placeholders_are_masked() {
+    assert_eq!(redact_rendered("{{env:A}}{{env:B}}", "user:pass"), REDACTED);
+    assert_eq!(redact_rendered("{{env:A}} tail", "value tail"), format!("{} tail", REDACTED));
+    assert_eq!(redact_rendered("plain\n", "plain\n"), "plain\n");
+}
+
+#[test]
+fn output_that_no_longer_matches_shows_only_template_lines() {
+    let template = "mqtt:\n  password: {{secret:mqtt}}\n  port: 1883\n";
+    let on_disk = "mqtt:\n  user: added-later\n  password: hunter2\n  port: 1883\n";
+    // The template changed since rendering, so the placeholder spans can't be trusted
+    let redacted = redact_rendered(template, on_disk);
+    assert!(!redacted.contains("hunter2"));
+    assert_eq!(redacted, format!("mqtt:\n{r}\n{r}\n  port: 1883", r = REDACTED));
+}
+
+#[test]
+fn rendered_diff_masks_a_stale_value_without_running_commands() {
+    let dir = scratch("diff");
+    let marker = dir.join("ran");
+    let include = dir.join("mqtt.yaml");
+    let output = dir.join("mqtt.rendered.yaml");
+    std::fs::write(&include, format!("password: {{{{cmd:touch {}; printf current}}}}\n", marker.display())).unwrap();
+    std::fs::write(&output, "password: rotated-away\n").unwrap();
+    let config = dir.join("detour.yaml");
+    std::fs::write(&config, format!(
+        "injections:\n  - id: mqtt\n    target: {}\n    include: {}\n    template: true\n    output: {}\n",
+        dir.join("configuration.yaml").display(), include.display(), output.display()
+    )).unwrap();
+
+    let mut app = App::with_config_path(&config.to_string_lossy());
+    app.show_rendered_diff();
+    let viewer = app.diff_viewer.expect("diff viewer");
+    assert_eq!(viewer.right_content, vec![format!("password: {}", REDACTED)]);
+    assert!(!marker.exists(), "opening the diff ran the command");
+}
+
+#[test]
+fn editing_an_active_template_re_renders_it() {
+    let dir = scratch("edit");
+    let include = dir.join("mqtt.yaml");
+    let output = dir.join("mqtt.rendered.yaml");
+    let target = dir.join("configuration.yaml");
+    std::fs::write(&output, "password: before\n").unwrap();
+    std::fs::write(&target, "mqtt: !include mqtt.rendered.yaml\n").unwrap();
+    let config = dir.join("detour.yaml");
+    std::fs::write(&config, format!(
+        "injections:\n  - id: mqtt\n    target: {}\n    include: {}\n    template: true\n    output: {}\n",
+        target.display(), include.display(), output.display()
+    )).unwrap();
+
+    // As if the editor had just saved the template
+    std::env::set_var("DETOUR_TEST_EDITED", "after");
+    std::fs::write(&include, "password: {{env:DETOUR_TEST_EDITED}}\n").unwrap();
+    let mut app = App::with_config_path(&config.to_string_lossy());
+    app.finish_edit(PendingEdit::new("mqtt".to_string(), include.to_string_lossy().to_string()), Ok(()));
+
+    assert_eq!(std::fs::read_to_string(&output).unwrap(), "password: after\n");
+}
+
+#[test]
+fn output_defaults_beside_the_target() {
+    assert_eq!(default_output("/config/configuration.yaml", "/home/pi/ha/mqtt.yaml"), "/config/mqtt.rendered.yaml");
+
+    let entry: InjectionEntry = serde_yaml::from_str("{target: /config/configuration.yaml, include: /home/pi/mqtt.yaml}").unwrap();
+    assert_eq!(entry.injected_file(), "/home/pi/mqtt.yaml");
+    let entry: InjectionEntry = serde_yaml::from_str("{target: /config/configuration.yaml, include: /home/pi/mqtt.yaml, template: true, output: /config/.private/mqtt.yaml}").unwrap();
+    assert_eq!(entry.injected_file(), "/config/.private/mqtt.yaml");
+}
+
+#[test]
+fn rendered_output_is_owner_only() {
+    let path = scratch("private").join("nested/out.yaml");
+    let path = path.to_string_lossy();
+    std::fs::create_dir_all(std::path::Path::new(&*path).parent().unwrap()).unwrap();
+    std::fs::write(&*path, "old").unwrap();
+    std::fs::set_permissions(&*path, std::fs::Permissions::from_mode(0o644)).unwrap();
+
+    write_private(&path, "new").unwrap();
+    assert_eq!(std::fs::read_to_string(&*path).unwrap(), "new");
+    assert_eq!(std::fs::metadata(&*path).unwrap().permissions().mode() & 0o777, 0o600);
+}
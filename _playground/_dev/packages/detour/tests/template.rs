@@ -0,0 +1,66 @@
+// Custom file generation: placeholders and origin headers
+
+use detour::config::TemplateSettings;
+use detour::template::{generate, header_comment, managed_path, render, FileSource, TemplateVars};
+
+fn vars(original: &str) -> TemplateVars {
+    TemplateVars {
+        hostname: "pi4".to_string(),
+        original: original.to_string(),
+        custom: "/home/pi/custom".to_string(),
+        date: "2024-05-01 12:00:00".to_string(),
+        checksum: "abc123".to_string(),
+        original_contents: "listen 80;\n".to_string(),
+    }
+}
+
+#[test]
+fn render_substitutes_every_known_placeholder() {
+    let template = "{{hostname}} {{original}} {{custom}} {{date}} {{checksum}}\n{{original_contents}}{{hostname}}";
+    assert_eq!(
+        render(template, &vars("/etc/nginx.conf")),
+        "pi4 /etc/nginx.conf /home/pi/custom 2024-05-01 12:00:00 abc123\nlisten 80;\npi4"
+    );
+}
+
+#[test]
+fn render_keeps_unknown_placeholders() {
+    assert_eq!(render("{{ user }} {{env:HOME}} {{hostname", &vars("/etc/a")), "{{ user }} {{env:HOME}} {{hostname");
+}
+
+#[test]
+fn header_uses_the_file_types_comment_syntax() {
+    let header = |original: &str| header_comment(&vars(original));
+    assert_eq!(
+        header("/etc/nginx/nginx.conf").unwrap(),
+        "# detour: custom copy of /etc/nginx/nginx.conf\n# created 2024-05-01 12:00:00 on pi4\n# original sha256 abc123\n"
+    );
+    assert!(header("/etc/app.ini").unwrap().starts_with("; detour: custom copy of /etc/app.ini\n"));
+    assert!(header("/src/main.rs").unwrap().starts_with("// detour: "));
+    assert!(header("/www/index.html").unwrap().starts_with("<!--\ndetour: ") && header("/www/index.html").unwrap().ends_with("\n-->\n"));
+    assert!(header("/www/site.css").unwrap().starts_with("/*\n * detour: "));
+    assert_eq!(header("/etc/app.json"), None);
+}
+
+#[test]
+fn header_goes_below_a_shebang() {
+    let settings = TemplateSettings { header: true, ..Default::default() };
+    let mut script = vars("/usr/local/bin/start.sh");
+    script.original_contents = "#!/bin/sh\necho hi\n".to_string();
+
+    let generated = generate(&FileSource::Original, &script, &settings).unwrap();
+    assert!(generated.starts_with("#!/bin/sh\n# detour: custom copy of /usr/local/bin/start.sh\n"), "{}", generated);
+    assert!(generated.ends_with("# original sha256 abc123\necho hi\n"));
+
+    // Without `header`, and for JSON, the body is untouched
+    assert_eq!(generate(&FileSource::Original, &script, &TemplateSettings::default()).unwrap(), "#!/bin/sh\necho hi\n");
+    assert_eq!(generate(&FileSource::Empty, &vars("/etc/a.json"), &settings).unwrap(), "");
+}
+
+#[test]
+fn managed_paths_mirror_the_original() {
+    let settings = TemplateSettings { managed_dir: Some("/srv/overlays".to_string()), ..Default::default() };
+    assert_eq!(managed_path(&settings, "/etc/nginx/nginx.conf").as_deref(), Some("/srv/overlays/etc/nginx/nginx.conf"));
+    assert_eq!(managed_path(&settings, "/"), None);
+    assert_eq!(managed_path(&TemplateSettings::default(), "/etc/a"), None);
+}
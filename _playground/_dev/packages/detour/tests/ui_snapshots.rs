@@ -0,0 +1,143 @@
+// Headless UI snapshots - scripted keys and mouse events through events::handle_event,
+// rendered to TestBackend and compared with tests/snapshots/*.txt
+//
+// Approve changes with: UPDATE_SNAPSHOTS=1 cargo test --test ui_snapshots
+
+use crossterm::event::{Event, KeyCode, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use detour::app::{ActiveColumn, App, ViewMode};
+use detour::{events, ui};
+use ratatui::layout::Rect;
+use tui_common::testing::ScratchDir;
+
+const WIDTH: u16 = 120;
+const HEIGHT: u16 = 32;
+
+tui_common::ui_test_harness!(App, handle: events::handle_event, draw: ui::ui, size: (WIDTH, HEIGHT), line: redact_ages);
+
+/// App over a private copy of the fixture - actions may write to the config
+fn fixture_app(name: &str) -> (App, ScratchDir) {
+    let fixture = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/ui.yaml");
+    let dir = ScratchDir::new("detour-ui", name);
+    let config = dir.path().join("detour.yaml");
+    std::fs::copy(fixture, &config).unwrap();
+    (App::with_config_path(&config.to_string_lossy()), dir)
+}
+
+fn mouse(app: &mut App, kind: MouseEventKind, rect: Rect) {
+    let event = MouseEvent { kind, column: rect.x + rect.width / 2, row: rect.y, modifiers: KeyModifiers::NONE };
+    events::handle_event(Event::Mouse(event), app);
+}
+
+fn click(app: &mut App, rect: Rect) {
+    mouse(app, MouseEventKind::Down(MouseButton::Left), rect);
+}
+
+fn type_text(app: &mut App, text: &str) {
+    keys(app, &text.chars().map(KeyCode::Char).collect::<Vec<_>>());
+}
+
+/// "20744d ago" → "****** ago" - ages depend on the clock, widths are kept
+fn redact_ages(line: &str) -> String {
+    let mut chars: Vec<char> = line.chars().collect();
+    for end in 0..chars.len() {
+        if !chars[end..].starts_with(&[' ', 'a', 'g', 'o']) || end < 2 || !"smhd".contains(chars[end - 1]) {
+            continue;
+        }
+        let mut start = end - 1;
+        while start > 0 && chars[start - 1].is_ascii_digit() {
+            start -= 1;
+        }
+        if start < end - 1 {
+            chars[start..end].iter_mut().for_each(|c| *c = '*');
+        }
+    }
+    chars.into_iter().collect()
+}
+
+#[test]
+fn detours_list() {
+    let (mut app, _dir) = fixture_app("detours_list");
+    assert_snapshot("detours_list", &render(&mut app));
+}
+
+#[test]
+fn mirrors_view() {
+    let (mut app, _dir) = fixture_app("mirrors_view");
+    keys(&mut app, &[KeyCode::Down, KeyCode::Down]);
+    assert_snapshot("mirrors_view", &render(&mut app));
+}
+
+#[test]
+fn detours_filtered() {
+    let (mut app, _dir) = fixture_app("detours_filtered");
+    keys(&mut app, &[KeyCode::Right, KeyCode::Right, KeyCode::Char('/')]);
+    type_text(&mut app, "klipper");
+    keys(&mut app, &[KeyCode::Enter]);
+    assert_snapshot("detours_filtered", &render(&mut app));
+}
+
+#[test]
+fn new_detour_form() {
+    let (mut app, _dir) = fixture_app("new_detour_form");
+    keys(&mut app, &[KeyCode::Char('n')]);
+    assert_snapshot("new_detour_form", &render(&mut app));
+}
+
+#[test]
+fn delete_confirmation() {
+    let (mut app, _dir) = fixture_app("delete_confirmation");
+    keys(&mut app, &[KeyCode::Right, KeyCode::Right, KeyCode::Delete]);
+    assert_snapshot("delete_confirmation", &render(&mut app));
+}
+
+#[test]
+fn click_selects_content_row() {
+    let (mut app, _dir) = fixture_app("click_selects_content_row");
+    render(&mut app);
+    let (_, row) = app.hit_areas.content_rows[1];
+    click(&mut app, row);
+    assert_eq!(app.active_column, ActiveColumn::Content);
+    assert_eq!(app.selected_detour, 1);
+}
+
+#[test]
+fn click_selects_view() {
+    let (mut app, _dir) = fixture_app("click_selects_view");
+    render(&mut app);
+    let (_, row) = app.hit_areas.view_rows[2];
+    click(&mut app, row);
+    assert_eq!(app.active_column, ActiveColumn::Views);
+    assert_eq!(app.view_mode, ViewMode::MirrorsList);
+}
+
+#[test]
+fn scroll_wheel_moves_list_under_pointer() {
+    let (mut app, _dir) = fixture_app("scroll_wheel_moves_list_under_pointer");
+    render(&mut app);
+    let content = app.hit_areas.content;
+    mouse(&mut app, MouseEventKind::ScrollDown, content);
+    assert_eq!(app.active_column, ActiveColumn::Content);
+    assert_eq!(app.selected_detour, 1);
+}
+
+#[test]
+fn popup_buttons_are_clickable() {
+    let (mut app, _dir) = fixture_app("popup_buttons_are_clickable");
+    keys(&mut app, &[KeyCode::Right, KeyCode::Right, KeyCode::Delete]);
+    let screen = render(&mut app);
+    assert!(app.popup.is_some());
+    let (_, no) = app.hit_areas.popup[1];
+    let line: String = screen.lines().nth(no.y as usize).unwrap().chars().skip(no.x as usize).take(6).collect();
+    assert_eq!(line, "[ No ]");
+    click(&mut app, no);
+    assert!(app.popup.is_none());
+    assert_eq!(app.detours.len(), 2);
+}
+
+#[test]
+fn editor_key_queues_the_custom_file() {
+    let (mut app, _dir) = fixture_app("editor_key_queues_the_custom_file");
+    keys(&mut app, &[KeyCode::Right, KeyCode::Right, KeyCode::Char('E')]);
+    let edit = app.pending_editor.as_ref().expect("no pending edit");
+    assert_eq!((edit.id.as_str(), edit.path.as_str()), ("d1a2b3", "/nonexistent/home/pi/nginx.conf"));
+}
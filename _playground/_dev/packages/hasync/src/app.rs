@@ -21,6 +21,23 @@ pub enum ViewMode {
     ScriptsList,
 }
 
+/// Direction of the dashboard sync waiting on the confirm popup
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SyncDirection {
+    YamlToJson,
+    /// YAML→JSON again after validation findings were shown and accepted
+    YamlToJsonForced,
+    JsonToYaml,
+}
+
+/// What a merge of the selected dashboard came to
+#[derive(Debug, Clone)]
+pub enum MergeOutcome {
+    InSync,
+    Merged(usize),
+    Conflicts(crate::merge::MergeResult),
+}
+
 #[derive(Debug, Clone)]
 pub struct SyncStatus {
     pub yaml_path: std::path::PathBuf,
@@ -74,6 +91,7 @@ pub struct App {
     // Modals (universal pattern)
     pub popup: Option<crate::popup::Popup>,
     pub file_browser: Option<crate::components::FileBrowser>,
+    pub pending_sync: Option<SyncDirection>,
     
     // hasync-specific data
     pub dashboards: Vec<DashboardInfo>,
@@ -82,6 +100,7 @@ pub struct App {
     pub selected_script: Option<usize>,
     pub sync_status: Option<SyncStatus>,
     pub config: AppConfig,
+    pub base_dir: PathBuf,
     
     // UI state (universal pattern)
     pub toasts: Vec<crate::components::Toast>,
@@ -97,13 +116,19 @@ pub struct App {
 impl App {
     pub fn new() -> Self {
         // Load configuration
-        let config = AppConfig::load().unwrap_or_default();
+        Self::with_config(AppConfig::load().unwrap_or_default())
+    }
+    
+    /// App over an already loaded config (fixtures in the UI snapshot tests)
+    pub fn with_config(config: AppConfig) -> Self {
+        let base_dir = crate::config::get_base_dir();
         
         // Load dashboards and calculate sync status
         let dashboards: Vec<DashboardInfo> = config.dashboards.iter().map(|d| {
             let yaml_mtime = crate::sync::get_file_mtime(&d.yaml_source);
             let json_mtime = crate::sync::get_file_mtime(&d.json_storage);
-            let status = crate::sync::detect_newer_file(&d.yaml_source, &d.json_storage);
+            let base_path = crate::sync::base_path(&base_dir, &d.dashboard_key);
+            let status = crate::sync::detect_dashboard_status(&d.yaml_source, &d.json_storage, &base_path);
             
             DashboardInfo {
                 name: d.name.clone(),
@@ -164,12 +189,14 @@ impl App {
             selected_action: Some(0),
             popup: None,
             file_browser: None,
+            pending_sync: None,
             dashboards,
             selected_dashboard: None,
             scripts,
             selected_script: None,
             sync_status: None,
             config,
+            base_dir,
             toasts: vec![],
             should_quit: false,
             logs: vec![],
@@ -184,7 +211,7 @@ impl App {
     
     pub fn get_current_actions(&self) -> Vec<String> {
         match self.view_mode {
-            ViewMode::DashboardList => vec!["List".to_string(), "Sync YAML→JSON".to_string(), "Sync JSON→YAML".to_string(), "Check Status".to_string(), "Show Diff".to_string()],
+            ViewMode::DashboardList => vec!["List".to_string(), "Sync YAML→JSON".to_string(), "Sync JSON→YAML".to_string(), "Merge".to_string(), "Check Status".to_string(), "Show Diff".to_string()],
             ViewMode::ScriptsList => vec!["List".to_string(), "Sync Scripts".to_string(), "Check Status".to_string()],
             ViewMode::SyncStatus => vec!["Status".to_string()],
             ViewMode::SyncHistory => vec!["History".to_string()],
@@ -216,10 +243,11 @@ impl App {
     
     /// Update sync status for a dashboard
     pub fn update_dashboard_status(&mut self, index: usize) {
-        if let Some(dashboard) = self.dashboards.get(index) {
+        if let (Some(dashboard), Some(dashboard_config)) = (self.dashboards.get(index), self.config.dashboards.get(index)) {
             let yaml_mtime = crate::sync::get_file_mtime(&dashboard.yaml_path);
             let json_mtime = crate::sync::get_file_mtime(&dashboard.json_path);
-            let status = crate::sync::detect_newer_file(&dashboard.yaml_path, &dashboard.json_path);
+            let base_path = crate::sync::base_path(&self.base_dir, &dashboard_config.dashboard_key);
+            let status = crate::sync::detect_dashboard_status(&dashboard.yaml_path, &dashboard.json_path, &base_path);
             
             if let Some(dashboard_info) = self.dashboards.get_mut(index) {
                 dashboard_info.sync_status.yaml_mtime = yaml_mtime;
@@ -238,8 +266,8 @@ impl App {
         }
     }
     
-    /// Sync YAML to JSON for selected dashboard
-    pub fn sync_yaml_to_json_selected(&mut self) -> Result<PathBuf, crate::sync::SyncError> {
+    /// Sync YAML to JSON for selected dashboard, `force` writes it despite validation findings
+    pub fn sync_yaml_to_json_selected(&mut self, force: bool) -> Result<PathBuf, crate::sync::SyncError> {
         if let Some(dashboard_config) = self.get_selected_dashboard_config() {
             let backup_path = crate::sync::sync_yaml_to_json(
                 &dashboard_config.yaml_source,
@@ -247,7 +275,9 @@ impl App {
                 &dashboard_config.dashboard_key,
                 &dashboard_config.dashboard_title,
                 &dashboard_config.dashboard_path,
+                force,
             )?;
+            self.record_base(dashboard_config)?;
             
             // Update sync status
             if let Some(index) = self.selected_dashboard {
@@ -260,6 +290,95 @@ impl App {
         }
     }
     
+    /// Sync JSON back to YAML for selected dashboard (edits made in the HA UI)
+    pub fn sync_json_to_yaml_selected(&mut self) -> Result<PathBuf, crate::sync::SyncError> {
+        if let Some(dashboard_config) = self.get_selected_dashboard_config() {
+            let backup_path = crate::sync::sync_json_to_yaml(
+                &dashboard_config.json_storage,
+                &dashboard_config.yaml_source,
+            )?;
+            self.record_base(dashboard_config)?;
+            
+            if let Some(index) = self.selected_dashboard {
+                self.update_dashboard_status(index);
+            }
+            
+            Ok(backup_path)
+        } else {
+            Err(crate::sync::SyncError::ValidationFailed("No dashboard selected".to_string()))
+        }
+    }
+    
+    /// Remember the config both sides now share as the dashboard's merge base
+    fn record_base(&self, dashboard_config: &crate::config::DashboardConfig) -> Result<(), crate::sync::SyncError> {
+        let base = crate::sync::SyncBase {
+            config: crate::sync::read_yaml_config(&dashboard_config.yaml_source)?,
+            yaml_hash: crate::sync::content_hash(&dashboard_config.yaml_source),
+            json_hash: crate::sync::content_hash(&dashboard_config.json_storage),
+        };
+        let base_path = crate::sync::base_path(&self.base_dir, &dashboard_config.dashboard_key);
+        crate::sync::save_base(&base_path, &base)
+    }
+    
+    /// Three-way merge of the selected dashboard's YAML (ours) and JSON (theirs)
+    pub fn merge_selected(&mut self) -> Result<MergeOutcome, crate::sync::SyncError> {
+        let dashboard_config = self.get_selected_dashboard_config().cloned()
+            .ok_or_else(|| crate::sync::SyncError::ValidationFailed("No dashboard selected".to_string()))?;
+        
+        let base_path = crate::sync::base_path(&self.base_dir, &dashboard_config.dashboard_key);
+        let base = crate::sync::load_base(&base_path).map_err(|_| crate::sync::SyncError::ValidationFailed(
+            format!("No merge base for {} yet - sync once in either direction first", dashboard_config.name)))?;
+        let ours = crate::sync::read_yaml_config(&dashboard_config.yaml_source)?;
+        let theirs = crate::sync::json_to_yaml(&dashboard_config.json_storage)?;
+        
+        if ours == theirs {
+            self.record_base(&dashboard_config)?;
+            return Ok(MergeOutcome::InSync);
+        }
+        
+        let result = crate::merge::merge(&base.config, &ours, &theirs);
+        if !result.conflicts.is_empty() {
+            if let Some(index) = self.selected_dashboard {
+                self.update_dashboard_status(index);
+            }
+            return Ok(MergeOutcome::Conflicts(result));
+        }
+        self.write_merged(&result.merged)?;
+        Ok(MergeOutcome::Merged(result.applied))
+    }
+    
+    /// Diff popup of the selected dashboard, `None` when YAML and JSON match
+    pub fn diff_selected(&self) -> Result<Option<crate::popup::Popup>, crate::sync::SyncError> {
+        let dashboard_config = self.get_selected_dashboard_config()
+            .ok_or_else(|| crate::sync::SyncError::ValidationFailed("No dashboard selected".to_string()))?;
+        let yaml = crate::sync::read_yaml_config(&dashboard_config.yaml_source)?;
+        let json = crate::sync::json_to_yaml(&dashboard_config.json_storage)?;
+        
+        // JSON is the old side: this is what a YAML→JSON sync would change
+        let changes = crate::diff::diff(&json, &yaml);
+        if changes.is_empty() {
+            return Ok(None);
+        }
+        let lines = crate::diff::line_diff(&crate::diff::normalized_yaml(&json), &crate::diff::normalized_yaml(&yaml));
+        Ok(Some(crate::popup::Popup::diff(format!("Diff {}", dashboard_config.name), changes, lines)))
+    }
+    
+    /// Write a merged config to both sides of the selected dashboard (with backups)
+    pub fn write_merged(&mut self, merged: &serde_yaml::Value) -> Result<(), crate::sync::SyncError> {
+        let dashboard_config = self.get_selected_dashboard_config().cloned()
+            .ok_or_else(|| crate::sync::SyncError::ValidationFailed("No dashboard selected".to_string()))?;
+        // Refuse before the YAML is touched, the JSON sync would refuse afterwards
+        let findings = serde_json::to_value(merged)
+            .map(|config| crate::validation::check_config(&config))
+            .unwrap_or_default();
+        if !findings.is_empty() {
+            return Err(crate::sync::SyncError::Invalid(findings));
+        }
+        crate::sync::write_yaml_config(&dashboard_config.yaml_source, merged)?;
+        self.sync_yaml_to_json_selected(false)?;
+        Ok(())
+    }
+    
     /// Update sync status for a script
     pub fn update_script_status(&mut self, index: usize) {
         if let Some(script) = self.scripts.get(index) {
@@ -78,3 +78,9 @@ pub fn get_config_path() -> PathBuf {
         .join("config.yaml")
 }
 
+
+/// Last-synced dashboard configs, the base for three-way merges
+pub fn get_base_dir() -> PathBuf {
+    get_config_path()
+        .with_file_name("base")
+}
@@ -0,0 +1,293 @@
+// Structural diffs of dashboard configs - views, cards and keys addressed by path
+
+use serde_yaml::{Mapping, Value};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+/// Location of a node inside a dashboard config, shown as `views[2].cards[4].entity`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodePath(pub Vec<Segment>);
+
+impl NodePath {
+    pub fn root() -> Self {
+        NodePath(vec![])
+    }
+
+    pub fn key(&self, key: impl Into<String>) -> Self {
+        let mut segments = self.0.clone();
+        segments.push(Segment::Key(key.into()));
+        NodePath(segments)
+    }
+
+    pub fn index(&self, index: usize) -> Self {
+        let mut segments = self.0.clone();
+        segments.push(Segment::Index(index));
+        NodePath(segments)
+    }
+
+    /// Whether `self` is `ancestor` or lies below it
+    pub fn starts_with(&self, ancestor: &NodePath) -> bool {
+        self.0.starts_with(&ancestor.0)
+    }
+
+    pub fn parent(&self) -> Option<NodePath> {
+        self.0.split_last().map(|(_, parent)| NodePath(parent.to_vec()))
+    }
+
+    pub fn last(&self) -> Option<&Segment> {
+        self.0.last()
+    }
+}
+
+impl fmt::Display for NodePath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.is_empty() {
+            return write!(f, "(root)");
+        }
+        for (i, segment) in self.0.iter().enumerate() {
+            match segment {
+                Segment::Key(key) if i == 0 => write!(f, "{}", key)?,
+                Segment::Key(key) => write!(f, ".{}", key)?,
+                Segment::Index(index) => write!(f, "[{}]", index)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    pub path: NodePath,
+    pub kind: ChangeKind,
+    pub old: Option<Value>,
+    pub new: Option<Value>,
+}
+
+impl Change {
+    /// Element appended to or dropped from the end of a list
+    pub fn resizes_list(&self) -> bool {
+        self.kind != ChangeKind::Changed && matches!(self.path.last(), Some(Segment::Index(_)))
+    }
+}
+
+/// Mapping keys as path segments - dashboards only use string keys in practice
+pub fn key_name(key: &Value) -> String {
+    match key {
+        Value::String(s) => s.clone(),
+        other => serde_yaml::to_string(other).unwrap_or_default().trim().to_string(),
+    }
+}
+
+fn entry<'a>(map: &'a Mapping, name: &str) -> Option<(&'a Value, &'a Value)> {
+    map.iter().find(|(key, _)| key_name(key) == name)
+}
+
+/// Changes turning `old` into `new`, deepest first differing node per branch
+///
+/// Lists are compared by position, so an insertion in the middle of `cards` shows
+/// up as changes to every later card plus one added at the end.
+pub fn diff(old: &Value, new: &Value) -> Vec<Change> {
+    let mut changes = Vec::new();
+    diff_into(&NodePath::root(), old, new, &mut changes);
+    changes
+}
+
+fn diff_into(path: &NodePath, old: &Value, new: &Value, changes: &mut Vec<Change>) {
+    if old == new {
+        return;
+    }
+    match (old, new) {
+        (Value::Mapping(old_map), Value::Mapping(new_map)) => {
+            for (key, old_value) in old_map {
+                let name = key_name(key);
+                match entry(new_map, &name) {
+                    Some((_, new_value)) => diff_into(&path.key(name), old_value, new_value, changes),
+                    None => changes.push(Change {
+                        path: path.key(name),
+                        kind: ChangeKind::Removed,
+                        old: Some(old_value.clone()),
+                        new: None,
+                    }),
+                }
+            }
+            for (key, new_value) in new_map {
+                let name = key_name(key);
+                if entry(old_map, &name).is_none() {
+                    changes.push(Change {
+                        path: path.key(name),
+                        kind: ChangeKind::Added,
+                        old: None,
+                        new: Some(new_value.clone()),
+                    });
+                }
+            }
+        }
+        (Value::Sequence(old_items), Value::Sequence(new_items)) => {
+            for i in 0..old_items.len().max(new_items.len()) {
+                match (old_items.get(i), new_items.get(i)) {
+                    (Some(old_item), Some(new_item)) => diff_into(&path.index(i), old_item, new_item, changes),
+                    (Some(old_item), None) => changes.push(Change {
+                        path: path.index(i),
+                        kind: ChangeKind::Removed,
+                        old: Some(old_item.clone()),
+                        new: None,
+                    }),
+                    (None, Some(new_item)) => changes.push(Change {
+                        path: path.index(i),
+                        kind: ChangeKind::Added,
+                        old: None,
+                        new: Some(new_item.clone()),
+                    }),
+                    (None, None) => {}
+                }
+            }
+        }
+        _ => changes.push(Change {
+            path: path.clone(),
+            kind: ChangeKind::Changed,
+            old: Some(old.clone()),
+            new: Some(new.clone()),
+        }),
+    }
+}
+
+/// Node at `path`, if there is one
+pub fn get<'a>(value: &'a Value, path: &NodePath) -> Option<&'a Value> {
+    path.0.iter().try_fold(value, |node, segment| match (node, segment) {
+        (Value::Mapping(map), Segment::Key(name)) => entry(map, name).map(|(_, v)| v),
+        (Value::Sequence(items), Segment::Index(i)) => items.get(*i),
+        _ => None,
+    })
+}
+
+/// Put `new` at `path` (`None` removes the node); returns false when the parent is missing
+///
+/// An index one past the end of a list appends, keys keep their position in the mapping.
+pub fn set(value: &mut Value, path: &NodePath, new: Option<Value>) -> bool {
+    let Some((last, parents)) = path.0.split_last() else {
+        *value = new.unwrap_or(Value::Null);
+        return true;
+    };
+    let mut node = value;
+    for segment in parents {
+        let next = match (node, segment) {
+            (Value::Mapping(map), Segment::Key(name)) => {
+                let key = entry(map, name).map(|(k, _)| k.clone()).unwrap_or_else(|| Value::String(name.clone()));
+                map.get_mut(&key)
+            }
+            (Value::Sequence(items), Segment::Index(i)) => items.get_mut(*i),
+            _ => None,
+        };
+        match next {
+            Some(next) => node = next,
+            None => return false,
+        }
+    }
+    match (node, last) {
+        (Value::Mapping(map), Segment::Key(name)) => {
+            let key = entry(map, name).map(|(k, _)| k.clone()).unwrap_or_else(|| Value::String(name.clone()));
+            match new {
+                Some(new) => {
+                    // insert() keeps an existing key where it was
+                    map.insert(key, new);
+                }
+                None => {
+                    map.shift_remove(&key);
+                }
+            }
+            true
+        }
+        (Value::Sequence(items), Segment::Index(i)) => {
+            match new {
+                Some(new) if *i < items.len() => items[*i] = new,
+                Some(new) if *i == items.len() => items.push(new),
+                Some(_) => return false,
+                None if *i < items.len() => {
+                    items.remove(*i);
+                }
+                None => {}
+            }
+            true
+        }
+        _ => false,
+    }
+}
+
+/// One-line rendering of a node for lists and conflict screens
+pub fn inline(value: Option<&Value>) -> String {
+    match value {
+        None => "(absent)".to_string(),
+        Some(value) => serde_json::to_string(value)
+            .unwrap_or_else(|_| serde_yaml::to_string(value).unwrap_or_default().trim().replace('\n', " ")),
+    }
+}
+
+/// Config as YAML with keys sorted, so key order and formatting never show up in a line diff
+pub fn normalized_yaml(config: &Value) -> String {
+    match serde_json::to_value(config) {
+        Ok(sorted) => serde_yaml::to_string(&sorted).unwrap_or_default(),
+        Err(_) => serde_yaml::to_string(config).unwrap_or_default(),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiffLine {
+    Same(String),
+    Removed(String),
+    Added(String),
+}
+
+/// Longest-common-subsequence line diff of two texts
+pub fn line_diff(old: &str, new: &str) -> Vec<DiffLine> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // Only the differing middle goes through the O(n·m) table
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..].iter().rev().zip(new[prefix..].iter().rev()).take_while(|(a, b)| a == b).count();
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    // lcs[i][j] = common lines of old_mid[i..] and new_mid[j..]
+    let mut lcs = vec![vec![0u32; new_mid.len() + 1]; old_mid.len() + 1];
+    for i in (0..old_mid.len()).rev() {
+        for j in (0..new_mid.len()).rev() {
+            lcs[i][j] = if old_mid[i] == new_mid[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines: Vec<DiffLine> = old[..prefix].iter().map(|l| DiffLine::Same(l.to_string())).collect();
+    let (mut i, mut j) = (0, 0);
+    while i < old_mid.len() || j < new_mid.len() {
+        if i < old_mid.len() && j < new_mid.len() && old_mid[i] == new_mid[j] {
+            lines.push(DiffLine::Same(old_mid[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if i < old_mid.len() && (j == new_mid.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            // Removals before additions, like diff -u
+            lines.push(DiffLine::Removed(old_mid[i].to_string()));
+            i += 1;
+        } else {
+            lines.push(DiffLine::Added(new_mid[j].to_string()));
+            j += 1;
+        }
+    }
+    lines.extend(old[old.len() - suffix..].iter().map(|l| DiffLine::Same(l.to_string())));
+    lines
+}
@@ -1,8 +1,19 @@
 // Event handling
 
-use crossterm::event::{KeyEvent, KeyCode, KeyModifiers};
+use crossterm::event::{Event, KeyEvent, KeyCode, KeyModifiers};
 use crate::app::App;
 
+/// Dispatch one terminal event - the main loop and the UI snapshot tests feed this
+pub fn handle_event(event: Event, app: &mut App) {
+    if let Event::Key(key) = event {
+        if key.code == KeyCode::Char('q') || key.code == KeyCode::Char('Q') {
+            app.should_quit = true;
+        } else {
+            handle_key_event(key, app);
+        }
+    }
+}
+
 pub fn handle_key_event(key: KeyEvent, app: &mut App) {
     // Auto-dismiss toasts (2.5 seconds)
     use std::time::{SystemTime, UNIX_EPOCH};
@@ -81,12 +92,20 @@ fn handle_popup_keys(key: KeyEvent, app: &mut App) {
                         let selected_yes = *selected == 0;
                         let view_mode = app.view_mode;
                         app.popup = None;
+                        if !selected_yes {
+                            app.pending_sync = None;
+                        }
                         
                         if selected_yes {
                             // Execute sync based on view mode
                             match view_mode {
                                 crate::app::ViewMode::DashboardList => {
-                                    match app.sync_yaml_to_json_selected() {
+                                    let result = match app.pending_sync.take() {
+                                        Some(crate::app::SyncDirection::JsonToYaml) => app.sync_json_to_yaml_selected(),
+                                        Some(crate::app::SyncDirection::YamlToJsonForced) => app.sync_yaml_to_json_selected(true),
+                                        _ => app.sync_yaml_to_json_selected(false),
+                                    };
+                                    match result {
                                         Ok(backup_path) => {
                                             if backup_path.exists() {
                                                 app.add_toast(
@@ -100,6 +119,19 @@ fn handle_popup_keys(key: KeyEvent, app: &mut App) {
                                                 );
                                             }
                                         }
+                                        Err(crate::sync::SyncError::Invalid(findings)) => {
+                                            // Nothing was written - offer to write it anyway
+                                            let mut message = format!("{} problem(s) found:\n", findings.len());
+                                            for finding in findings.iter().take(8) {
+                                                message.push_str(&format!("{}\n", finding));
+                                            }
+                                            if findings.len() > 8 {
+                                                message.push_str(&format!("... and {} more\n", findings.len() - 8));
+                                            }
+                                            message.push_str("\nWrite the JSON anyway?");
+                                            app.pending_sync = Some(crate::app::SyncDirection::YamlToJsonForced);
+                                            app.popup = Some(crate::popup::Popup::confirm("Invalid Dashboard", message));
+                                        }
                                         Err(e) => {
                                             app.add_toast(
                                                 format!("Sync failed: {}", e),
@@ -138,6 +170,64 @@ fn handle_popup_keys(key: KeyEvent, app: &mut App) {
                     KeyCode::Esc => {
                         // Cancel (defaults to No)
                         app.popup = None;
+                        app.pending_sync = None;
+                    }
+                    _ => {}
+                }
+            }
+            crate::popup::Popup::Diff { .. } => {
+                let len = popup.diff_len();
+                if let crate::popup::Popup::Diff { show_lines, scroll, .. } = popup {
+                    match key.code {
+                        KeyCode::Up | KeyCode::Char('k') => *scroll = scroll.saturating_sub(1),
+                        KeyCode::Down | KeyCode::Char('j') => *scroll = (*scroll + 1).min(len.saturating_sub(1)),
+                        KeyCode::PageUp => *scroll = scroll.saturating_sub(10),
+                        KeyCode::PageDown => *scroll = (*scroll + 10).min(len.saturating_sub(1)),
+                        KeyCode::Home => *scroll = 0,
+                        KeyCode::Tab => {
+                            *show_lines = !*show_lines;
+                            *scroll = 0;
+                        }
+                        KeyCode::Enter | KeyCode::Esc => app.popup = None,
+                        _ => {}
+                    }
+                }
+            }
+            crate::popup::Popup::Merge { result, choices, selected, .. } => {
+                match key.code {
+                    KeyCode::Up | KeyCode::Char('k') => {
+                        *selected = selected.saturating_sub(1);
+                    }
+                    KeyCode::Down | KeyCode::Char('j') => {
+                        *selected = (*selected + 1).min(choices.len().saturating_sub(1));
+                    }
+                    KeyCode::Left | KeyCode::Char('o') => {
+                        if let Some(choice) = choices.get_mut(*selected) {
+                            *choice = crate::merge::Resolution::Ours;
+                        }
+                    }
+                    KeyCode::Right | KeyCode::Char('t') => {
+                        if let Some(choice) = choices.get_mut(*selected) {
+                            *choice = crate::merge::Resolution::Theirs;
+                        }
+                    }
+                    KeyCode::Enter => {
+                        let merged = result.resolve(choices);
+                        let resolved = choices.len();
+                        app.popup = None;
+                        match app.write_merged(&merged) {
+                            Ok(()) => app.add_toast(
+                                format!("Merged! {} conflict(s) resolved", resolved),
+                                crate::components::ToastType::Success,
+                            ),
+                            Err(e) => app.add_toast(
+                                format!("Merge failed: {}", e),
+                                crate::components::ToastType::Error,
+                            ),
+                        }
+                    }
+                    KeyCode::Esc => {
+                        app.popup = None;
                     }
                     _ => {}
                 }
@@ -352,11 +442,61 @@ impl App {
                         match (action.as_str(), self.view_mode) {
                             ("Sync YAML→JSON", crate::app::ViewMode::DashboardList) => {
                                 // Show confirmation dialog for dashboard sync
+                                self.pending_sync = Some(crate::app::SyncDirection::YamlToJson);
                                 self.popup = Some(crate::popup::Popup::confirm(
                                     "Confirm Sync",
                                     "Sync YAML to JSON? This will overwrite the JSON file.",
                                 ));
                             }
+                            ("Sync JSON→YAML", crate::app::ViewMode::DashboardList) => {
+                                // Warn when the YAML was edited since the last sync
+                                let local_edits = self.get_selected_dashboard_config()
+                                    .is_some_and(|d| crate::sync::yaml_has_local_edits(
+                                        &d.yaml_source,
+                                        &d.json_storage,
+                                        &crate::sync::base_path(&self.base_dir, &d.dashboard_key),
+                                    ));
+                                let message = if local_edits {
+                                    "The YAML has local edits that are not in the JSON and will be lost (a backup is kept).\nSync JSON to YAML anyway?"
+                                } else {
+                                    "Sync JSON to YAML? This will overwrite the YAML file."
+                                };
+                                self.pending_sync = Some(crate::app::SyncDirection::JsonToYaml);
+                                self.popup = Some(crate::popup::Popup::confirm("Confirm Sync", message));
+                            }
+                            ("Merge", crate::app::ViewMode::DashboardList) => {
+                                match self.merge_selected() {
+                                    Ok(crate::app::MergeOutcome::InSync) => self.add_toast(
+                                        "Already in sync".to_string(),
+                                        crate::components::ToastType::Info,
+                                    ),
+                                    Ok(crate::app::MergeOutcome::Merged(changes)) => self.add_toast(
+                                        format!("Merged {} change(s)", changes),
+                                        crate::components::ToastType::Success,
+                                    ),
+                                    Ok(crate::app::MergeOutcome::Conflicts(result)) => {
+                                        let name = self.get_selected_dashboard_config()
+                                            .map(|d| d.name.clone())
+                                            .unwrap_or_default();
+                                        self.popup = Some(crate::popup::Popup::merge(format!("Merge {}", name), result));
+                                    }
+                                    Err(e) => {
+                                        self.popup = Some(crate::popup::Popup::error("Merge Failed", e.to_string()));
+                                    }
+                                }
+                            }
+                            ("Show Diff", crate::app::ViewMode::DashboardList) => {
+                                match self.diff_selected() {
+                                    Ok(Some(popup)) => self.popup = Some(popup),
+                                    Ok(None) => self.add_toast(
+                                        "YAML and JSON match".to_string(),
+                                        crate::components::ToastType::Info,
+                                    ),
+                                    Err(e) => {
+                                        self.popup = Some(crate::popup::Popup::error("Diff Failed", e.to_string()));
+                                    }
+                                }
+                            }
                             ("Sync Scripts", crate::app::ViewMode::ScriptsList) => {
                                 // Show confirmation dialog for scripts sync
                                 self.popup = Some(crate::popup::Popup::confirm(
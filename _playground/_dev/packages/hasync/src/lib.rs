@@ -11,6 +11,8 @@ pub mod popup;
 pub mod sync;
 pub mod config;
 pub mod validation;
+pub mod diff;
+pub mod merge;
 
 // Component modules
 pub mod components;
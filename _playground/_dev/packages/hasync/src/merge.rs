@@ -0,0 +1,150 @@
+// Three-way merge of a dashboard edited in both the YAML (ours) and the HA UI (theirs)
+
+use crate::diff::{self, Change, ChangeKind, NodePath, Segment};
+use serde_yaml::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Resolution {
+    /// Keep the YAML side
+    Ours,
+    /// Keep the JSON (HA UI) side
+    Theirs,
+}
+
+/// Node both sides changed differently since the last sync
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conflict {
+    pub path: NodePath,
+    pub base: Option<Value>,
+    pub ours: Option<Value>,
+    pub theirs: Option<Value>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MergeResult {
+    /// Base with every non-conflicting change applied, conflicts provisionally ours
+    pub merged: Value,
+    pub conflicts: Vec<Conflict>,
+    /// Changes taken over automatically
+    pub applied: usize,
+}
+
+impl MergeResult {
+    /// Merged config once every conflict has a side picked
+    ///
+    /// Applied from the last path back, like the removals in `merge`, so dropping a
+    /// list element doesn't shift the ones still to be resolved.
+    pub fn resolve(&self, choices: &[Resolution]) -> Value {
+        let mut merged = self.merged.clone();
+        let mut picked: Vec<(&Conflict, &Resolution)> = self.conflicts.iter().zip(choices).collect();
+        picked.sort_by(|a, b| b.0.path.cmp(&a.0.path));
+        for (conflict, choice) in picked {
+            let value = match choice {
+                Resolution::Ours => conflict.ours.clone(),
+                Resolution::Theirs => conflict.theirs.clone(),
+            };
+            diff::set(&mut merged, &conflict.path, value);
+        }
+        merged
+    }
+}
+
+/// Smallest node containing both changes, when they touch the same part of the tree
+///
+/// Changes to a node and to something below it overlap at the node; appending to or
+/// dropping from the same list on both sides overlaps at the list, since the
+/// positions would shift under each other.
+fn overlap(ours: &Change, theirs: &Change) -> Option<NodePath> {
+    if ours.path.starts_with(&theirs.path) {
+        Some(theirs.path.clone())
+    } else if theirs.path.starts_with(&ours.path) {
+        Some(ours.path.clone())
+    } else if ours.resizes_list() && theirs.resizes_list() && ours.path.parent() == theirs.path.parent() {
+        ours.path.parent()
+    } else {
+        None
+    }
+}
+
+/// Merge `ours` and `theirs` against their common `base`
+pub fn merge(base: &Value, ours: &Value, theirs: &Value) -> MergeResult {
+    let our_changes = diff::diff(base, ours);
+    let their_changes = diff::diff(base, theirs);
+
+    // Overlapping edits either agree (same subtree on both sides) or conflict
+    let mut agreed: Vec<NodePath> = Vec::new();
+    let mut conflicts: Vec<NodePath> = Vec::new();
+    for our_change in &our_changes {
+        for their_change in &their_changes {
+            let Some(scope) = overlap(our_change, their_change) else {
+                continue;
+            };
+            if diff::get(ours, &scope) == diff::get(theirs, &scope) {
+                agreed.push(scope);
+            } else {
+                conflicts.push(scope);
+            }
+        }
+    }
+
+    // Several elements of one list removed on a side would shift under each other
+    // while being resolved one by one, so they become a single conflict at the list
+    conflicts.sort();
+    conflicts.dedup();
+    let shrinks = |path: &NodePath| matches!(path.last(), Some(Segment::Index(_)))
+        && (diff::get(ours, path).is_none() || diff::get(theirs, path).is_none());
+    let shrunk_lists: Vec<NodePath> = conflicts.iter()
+        .filter(|path| shrinks(path))
+        .filter_map(NodePath::parent)
+        .collect();
+    for conflict in conflicts.iter_mut().filter(|path| shrinks(path)) {
+        if let Some(list) = conflict.parent().filter(|list| shrunk_lists.iter().filter(|l| *l == list).count() > 1) {
+            *conflict = list;
+        }
+    }
+
+    // Keep only the outermost conflicting nodes
+    conflicts.sort();
+    conflicts.dedup();
+    let outermost: Vec<NodePath> = conflicts.iter()
+        .filter(|path| !conflicts.iter().any(|other| other != *path && path.starts_with(other)))
+        .cloned()
+        .collect();
+    let in_conflict = |path: &NodePath| outermost.iter().any(|scope| path.starts_with(scope));
+
+    // Theirs is redundant wherever both sides made the same edit
+    let mut pending: Vec<&Change> = our_changes.iter()
+        .filter(|change| !in_conflict(&change.path))
+        .chain(their_changes.iter()
+            .filter(|change| !in_conflict(&change.path))
+            .filter(|change| !agreed.iter().any(|scope| change.path.starts_with(scope))))
+        .collect();
+
+    // Appends in order first, then list removals from the back so indices stay valid
+    pending.sort_by(|a, b| {
+        let a_removal = a.kind == ChangeKind::Removed;
+        let b_removal = b.kind == ChangeKind::Removed;
+        a_removal.cmp(&b_removal).then_with(|| {
+            if a_removal { b.path.cmp(&a.path) } else { a.path.cmp(&b.path) }
+        })
+    });
+
+    let mut merged = base.clone();
+    for change in &pending {
+        diff::set(&mut merged, &change.path, change.new.clone());
+    }
+
+    let conflicts: Vec<Conflict> = outermost.into_iter()
+        .map(|path| Conflict {
+            base: diff::get(base, &path).cloned(),
+            ours: diff::get(ours, &path).cloned(),
+            theirs: diff::get(theirs, &path).cloned(),
+            path,
+        })
+        .collect();
+    for conflict in &conflicts {
+        diff::set(&mut merged, &conflict.path, conflict.ours.clone());
+    }
+
+    MergeResult { merged, conflicts, applied: pending.len() }
+}
@@ -8,6 +8,8 @@ use ratatui::{
     Frame,
 };
 use std::time::Instant;
+use crate::diff::{Change, ChangeKind, DiffLine};
+use crate::merge::{MergeResult, Resolution};
 
 fn hex_color(hex: u32) -> Color {
     Color::Rgb(
@@ -72,6 +74,21 @@ pub enum Popup {
         message: String,
         shown_at: Instant,  // For auto-dismiss
     },
+    /// YAML source against JSON storage, by node path or as a line diff
+    Diff {
+        title: String,
+        changes: Vec<Change>,
+        lines: Vec<DiffLine>,
+        show_lines: bool,
+        scroll: usize,
+    },
+    /// Conflict resolution screen, one ours/theirs pick per conflicting node
+    Merge {
+        title: String,
+        result: MergeResult,
+        choices: Vec<Resolution>,
+        selected: usize,
+    },
 }
 
 impl Popup {
@@ -106,6 +123,33 @@ impl Popup {
             shown_at: Instant::now(),
         }
     }
+    
+    pub fn diff(title: impl Into<String>, changes: Vec<Change>, lines: Vec<DiffLine>) -> Self {
+        Popup::Diff {
+            title: title.into(),
+            changes,
+            lines,
+            show_lines: false,
+            scroll: 0,
+        }
+    }
+    
+    /// Rows the diff popup can scroll through in its current mode
+    pub fn diff_len(&self) -> usize {
+        match self {
+            Popup::Diff { changes, lines, show_lines, .. } => if *show_lines { lines.len() } else { changes.len() },
+            _ => 0,
+        }
+    }
+    
+    pub fn merge(title: impl Into<String>, result: MergeResult) -> Self {
+        Popup::Merge {
+            title: title.into(),
+            choices: vec![Resolution::Ours; result.conflicts.len()],
+            result,
+            selected: 0,
+        }
+    }
 }
 
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
@@ -136,6 +180,8 @@ pub fn draw_popup(f: &mut Frame, area: Rect, popup: &Popup) {
         Popup::Input { title, prompt, input, cursor_pos } => draw_input(f, area, title, prompt, input, *cursor_pos),
         Popup::Error { title, message } => draw_error(f, area, title, message),
         Popup::Info { title, message, .. } => draw_info(f, area, title, message),
+        Popup::Diff { title, changes, lines, show_lines, scroll } => draw_diff(f, area, title, changes, lines, *show_lines, *scroll),
+        Popup::Merge { title, result, choices, selected } => draw_merge(f, area, title, result, choices, *selected),
     }
 }
 
@@ -304,3 +350,163 @@ fn draw_info(f: &mut Frame, area: Rect, title: &str, message: &str) {
     });
 }
 
+
+/// Working area for the full-screen popups, between the title bar and the status lines
+fn screen_rect(area: Rect) -> Rect {
+    Rect {
+        x: area.x + 1,
+        y: area.y + 3,
+        width: area.width.saturating_sub(2),
+        height: area.height.saturating_sub(8),
+    }
+}
+
+fn draw_diff(f: &mut Frame, area: Rect, title: &str, changes: &[Change], lines: &[DiffLine], show_lines: bool, scroll: usize) {
+    let popup_area = screen_rect(area);
+    f.render_widget(Clear, popup_area);
+    
+    let block = Block::default()
+        .title(format!(" {} ", title))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(Color::White));
+    
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+    
+    let muted = Style::default().fg(hex_color(0x888888));
+    let count = |kind: ChangeKind| changes.iter().filter(|c| c.kind == kind).count();
+    let summary = format!(
+        "{} changed, {} added, {} removed   - JSON (.storage)  + YAML (source)   {}",
+        count(ChangeKind::Changed),
+        count(ChangeKind::Added),
+        count(ChangeKind::Removed),
+        if show_lines { "[normalized YAML]" } else { "[by path]" },
+    );
+    f.render_widget(Paragraph::new(summary).style(muted), Rect {
+        x: inner.x + 1,
+        y: inner.y + 1,
+        width: inner.width.saturating_sub(2),
+        height: 1,
+    });
+    
+    let body = Rect {
+        x: inner.x + 1,
+        y: inner.y + 3,
+        width: inner.width.saturating_sub(2),
+        height: inner.height.saturating_sub(5),
+    };
+    let width = body.width as usize;
+    let clip = |text: String| -> String { text.chars().take(width).collect() };
+    let rows: Vec<Line> = if show_lines {
+        lines.iter().skip(scroll).take(body.height as usize).map(|line| match line {
+            DiffLine::Same(text) => Line::from(Span::styled(clip(format!("  {}", text)), muted)),
+            DiffLine::Removed(text) => Line::from(Span::styled(clip(format!("- {}", text)), Style::default().fg(Color::Red))),
+            DiffLine::Added(text) => Line::from(Span::styled(clip(format!("+ {}", text)), Style::default().fg(Color::Green))),
+        }).collect()
+    } else {
+        changes.iter().skip(scroll).take(body.height as usize).map(|change| {
+            let (marker, color, detail) = match change.kind {
+                ChangeKind::Added => ("+", Color::Green, crate::diff::inline(change.new.as_ref())),
+                ChangeKind::Removed => ("-", Color::Red, crate::diff::inline(change.old.as_ref())),
+                ChangeKind::Changed => ("~", Color::Yellow, format!("{} → {}",
+                    crate::diff::inline(change.old.as_ref()), crate::diff::inline(change.new.as_ref()))),
+            };
+            let path = change.path.to_string();
+            let detail: String = detail.chars().take(width.saturating_sub(path.len() + 5)).collect();
+            Line::from(vec![
+                Span::styled(format!("{} ", marker), Style::default().fg(color)),
+                Span::styled(path, Style::default().fg(Color::White)),
+                Span::raw("   "),
+                Span::styled(detail, muted),
+            ])
+        }).collect()
+    };
+    f.render_widget(Paragraph::new(rows), body);
+    
+    let help = "[↑↓/PgUp/PgDn] Scroll  [Tab] By path / Lines  [Esc] Close";
+    f.render_widget(Paragraph::new(help).style(muted), Rect {
+        x: inner.x + 1,
+        y: inner.y + inner.height.saturating_sub(1),
+        width: inner.width.saturating_sub(2),
+        height: 1,
+    });
+}
+
+fn draw_merge(f: &mut Frame, area: Rect, title: &str, result: &MergeResult, choices: &[Resolution], selected: usize) {
+    let popup_area = screen_rect(area);
+    f.render_widget(Clear, popup_area);
+    
+    let block = Block::default()
+        .title(format!(" {} ", title))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(Color::White));
+    
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+    
+    let width = inner.width.saturating_sub(2) as usize;
+    let muted = Style::default().fg(hex_color(0x888888));
+    let mut lines: Vec<Line> = vec![
+        Line::from(Span::styled(
+            format!("{} change(s) merged automatically, {} conflict(s) to resolve", result.applied, result.conflicts.len()),
+            muted,
+        )),
+        Line::from(""),
+    ];
+    
+    // Conflict list
+    for (i, (conflict, choice)) in result.conflicts.iter().zip(choices).enumerate() {
+        let (label, color) = match choice {
+            Resolution::Ours => ("ours  ", Color::Cyan),
+            Resolution::Theirs => ("theirs", Color::Yellow),
+        };
+        let row_style = if i == selected {
+            Style::default().fg(Color::Cyan).bg(hex_color(0x1A2A2A))
+        } else {
+            Style::default().fg(Color::White)
+        };
+        lines.push(Line::from(vec![
+            Span::styled(if i == selected { " ► " } else { "   " }, row_style),
+            Span::styled(format!("[{}] ", label), Style::default().fg(color)),
+            Span::styled(conflict.path.to_string(), row_style),
+        ]));
+    }
+    
+    // Both sides of the selected conflict
+    if let Some(conflict) = result.conflicts.get(selected) {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled("─".repeat(width), Style::default().fg(hex_color(0x333333)))));
+        let sides = [
+            ("base          ", conflict.base.as_ref(), hex_color(0x666666)),
+            ("ours (YAML)   ", conflict.ours.as_ref(), Color::Cyan),
+            ("theirs (JSON) ", conflict.theirs.as_ref(), Color::Yellow),
+        ];
+        for (label, value, color) in sides {
+            let text = crate::diff::inline(value);
+            let wrapped = wrap_text(&text, width.saturating_sub(label.len()).max(10));
+            for (n, part) in wrapped.iter().take(4).enumerate() {
+                lines.push(Line::from(vec![
+                    Span::styled(if n == 0 { label.to_string() } else { " ".repeat(label.len()) }, muted),
+                    Span::styled(part.clone(), Style::default().fg(color)),
+                ]));
+            }
+        }
+    }
+    
+    f.render_widget(Paragraph::new(lines), Rect {
+        x: inner.x + 1,
+        y: inner.y + 1,
+        width: inner.width.saturating_sub(2),
+        height: inner.height.saturating_sub(3),
+    });
+    
+    let help = "[↑↓] Conflict  [←/o] Ours (YAML)  [→/t] Theirs (JSON)  [Enter] Apply  [Esc] Cancel";
+    f.render_widget(Paragraph::new(help).style(muted), Rect {
+        x: inner.x + 1,
+        y: inner.y + inner.height.saturating_sub(1),
+        width: inner.width.saturating_sub(2),
+        height: 1,
+    });
+}
@@ -1,5 +1,6 @@
 // Sync operations (YAML ↔ JSON)
 
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::path::{Path, PathBuf};
 use std::time::SystemTime;
@@ -21,16 +22,32 @@ pub enum SyncError {
     PermissionDenied,
     #[error("Validation failed: {0}")]
     ValidationFailed(String),
+    #[error("Dashboard is invalid: {}", crate::validation::describe(.0))]
+    Invalid(Vec<crate::validation::Finding>),
 }
 
+/// Sync state of a YAML/JSON pair (source/destination for scripts)
 #[derive(Debug, Clone, PartialEq)]
 pub enum FileStatus {
-    YamlNewer,
-    JsonNewer,
-    Synced,
+    InSync,
+    YamlChanged,
+    JsonChanged,
+    BothChanged,
+    Missing,
     Error(String),
 }
 
+/// Last-synced state of a dashboard: the merge base, and hashes of both files as
+/// written so unchanged files are recognised without parsing them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncBase {
+    pub config: serde_yaml::Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub yaml_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub json_hash: Option<String>,
+}
+
 pub fn yaml_to_json(yaml_path: &Path, dashboard_key: &str, dashboard_title: &str, dashboard_path: &str) -> Result<Value, SyncError> {
     // Read YAML file
     if !yaml_path.exists() {
@@ -59,11 +76,104 @@ pub fn yaml_to_json(yaml_path: &Path, dashboard_key: &str, dashboard_title: &str
     Ok(json_value)
 }
 
-pub fn json_to_yaml(_json_path: &Path) -> Result<Value, SyncError> {
-    // TODO: Implement JSON to YAML conversion
-    Err(SyncError::InvalidJson("Not implemented".to_string()))
+/// `data.config` of a `.storage/lovelace.<key>` file, keys kept in storage order
+pub fn json_to_yaml(json_path: &Path) -> Result<serde_yaml::Value, SyncError> {
+    if !json_path.exists() {
+        return Err(SyncError::FileNotFound(json_path.to_path_buf()));
+    }
+    
+    let content = std::fs::read_to_string(json_path)
+        .map_err(|e| SyncError::InvalidJson(format!("Failed to read JSON file: {}", e)))?;
+    
+    // serde_yaml's mapping is ordered, so views/cards come out the way HA stored them
+    let storage: serde_yaml::Value = serde_json::from_str(&content)
+        .map_err(|e| SyncError::InvalidJson(format!("Failed to parse JSON: {}", e)))?;
+    
+    storage.get("data")
+        .and_then(|data| data.get("config"))
+        .cloned()
+        .ok_or_else(|| SyncError::InvalidJson(format!("{} has no data.config", json_path.display())))
+}
+
+/// Dashboard config of the YAML source, keys kept in file order
+pub fn read_yaml_config(yaml_path: &Path) -> Result<serde_yaml::Value, SyncError> {
+    if !yaml_path.exists() {
+        return Err(SyncError::FileNotFound(yaml_path.to_path_buf()));
+    }
+    let content = std::fs::read_to_string(yaml_path)
+        .map_err(|e| SyncError::InvalidYaml(format!("Failed to read YAML file: {}", e)))?;
+    serde_yaml::from_str(&content)
+        .map_err(|e| SyncError::InvalidYaml(format!("Failed to parse YAML: {}", e)))
+}
+
+/// Whether syncing JSON→YAML would throw away YAML edits made since the last sync
+pub fn yaml_has_local_edits(yaml_path: &Path, json_path: &Path, base_path: &Path) -> bool {
+    if !yaml_path.exists() {
+        return false;
+    }
+    match detect_dashboard_status(yaml_path, json_path, base_path) {
+        FileStatus::YamlChanged | FileStatus::BothChanged => true,
+        // Unparseable YAML is somebody's work in progress
+        FileStatus::Error(_) => read_yaml_config(yaml_path).is_err(),
+        _ => false,
+    }
+}
+
+/// FNV-1a of a file's bytes - stable across builds, unlike `DefaultHasher`
+pub fn content_hash(path: &Path) -> Option<String> {
+    let bytes = std::fs::read(path).ok()?;
+    let hash = bytes.iter().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    });
+    Some(format!("{:016x}", hash))
+}
+
+/// Compare the YAML config with the JSON's `data.config` by content
+///
+/// Files whose hashes still match the last sync are taken as unchanged without
+/// parsing. Otherwise both configs are compared semantically, and against the
+/// merge base to tell which side moved; without a base the newer file is blamed.
+pub fn detect_dashboard_status(yaml_path: &Path, json_path: &Path, base_path: &Path) -> FileStatus {
+    if !yaml_path.exists() || !json_path.exists() {
+        return FileStatus::Missing;
+    }
+    
+    let base = load_base(base_path).ok();
+    if let Some(base) = &base {
+        let hashes_match = base.yaml_hash.is_some() && base.yaml_hash == content_hash(yaml_path)
+            && base.json_hash.is_some() && base.json_hash == content_hash(json_path);
+        if hashes_match {
+            return FileStatus::InSync;
+        }
+    }
+    
+    let yaml_config = match read_yaml_config(yaml_path) {
+        Ok(config) => config,
+        Err(e) => return FileStatus::Error(e.to_string()),
+    };
+    let json_config = match json_to_yaml(json_path) {
+        Ok(config) => config,
+        Err(e) => return FileStatus::Error(e.to_string()),
+    };
+    if yaml_config == json_config {
+        return FileStatus::InSync;
+    }
+    
+    match base {
+        Some(base) => match (yaml_config != base.config, json_config != base.config) {
+            (true, true) => FileStatus::BothChanged,
+            (true, false) => FileStatus::YamlChanged,
+            (false, true) => FileStatus::JsonChanged,
+            (false, false) => FileStatus::InSync,
+        },
+        None => match detect_newer_file(yaml_path, json_path) {
+            FileStatus::InSync => FileStatus::BothChanged,
+            status => status,
+        },
+    }
 }
 
+/// mtime-only guess at which side changed, `InSync` when both are equally old
 pub fn detect_newer_file(yaml_path: &Path, json_path: &Path) -> FileStatus {
     let yaml_mtime = get_file_mtime(yaml_path);
     let json_mtime = get_file_mtime(json_path);
@@ -71,14 +181,12 @@ pub fn detect_newer_file(yaml_path: &Path, json_path: &Path) -> FileStatus {
     match (yaml_mtime, json_mtime) {
         (Some(yaml_time), Some(json_time)) => {
             match yaml_time.cmp(&json_time) {
-                std::cmp::Ordering::Greater => FileStatus::YamlNewer,
-                std::cmp::Ordering::Less => FileStatus::JsonNewer,
-                std::cmp::Ordering::Equal => FileStatus::Synced,
+                std::cmp::Ordering::Greater => FileStatus::YamlChanged,
+                std::cmp::Ordering::Less => FileStatus::JsonChanged,
+                std::cmp::Ordering::Equal => FileStatus::InSync,
             }
         }
-        (Some(_), None) => FileStatus::YamlNewer,  // YAML exists, JSON doesn't
-        (None, Some(_)) => FileStatus::JsonNewer,  // JSON exists, YAML doesn't
-        (None, None) => FileStatus::Error("Neither file exists".to_string()),
+        _ => FileStatus::Missing,
     }
 }
 
@@ -98,18 +206,34 @@ pub fn create_backup(file_path: &Path) -> Result<PathBuf, SyncError> {
     Ok(backup_path)
 }
 
+/// Write the YAML dashboard into `.storage`, refusing output that fails validation
+/// unless `force` is set
 pub fn sync_yaml_to_json(
     yaml_path: &Path,
     json_path: &Path,
     dashboard_key: &str,
     dashboard_title: &str,
     dashboard_path: &str,
+    force: bool,
 ) -> Result<PathBuf, SyncError> {
     // Validate YAML file exists
     if !yaml_path.exists() {
         return Err(SyncError::FileNotFound(yaml_path.to_path_buf()));
     }
     
+    // Convert YAML to JSON
+    let json_value = yaml_to_json(yaml_path, dashboard_key, dashboard_title, dashboard_path)?;
+    
+    // Check what HA would load before anything is touched
+    if !force {
+        let mut findings = crate::validation::check_envelope(&json_value);
+        if !findings.is_empty() {
+            let yaml_text = std::fs::read_to_string(yaml_path).unwrap_or_default();
+            crate::validation::add_lines(&mut findings, &yaml_text, &crate::validation::config_path());
+            return Err(SyncError::Invalid(findings));
+        }
+    }
+    
     // Create backup of JSON file if it exists
     let backup_path = if json_path.exists() {
         create_backup(json_path)?
@@ -118,9 +242,6 @@ pub fn sync_yaml_to_json(
         PathBuf::new()
     };
     
-    // Convert YAML to JSON
-    let json_value = yaml_to_json(yaml_path, dashboard_key, dashboard_title, dashboard_path)?;
-    
     // Create parent directory if it doesn't exist
     if let Some(parent) = json_path.parent() {
         std::fs::create_dir_all(parent)
@@ -137,9 +258,60 @@ pub fn sync_yaml_to_json(
     Ok(backup_path)
 }
 
-pub fn sync_json_to_yaml(_json_path: &Path, _yaml_path: &Path) -> Result<(), SyncError> {
-    // TODO: Implement JSON to YAML sync
-    Err(SyncError::WriteFailed("Not implemented".to_string()))
+pub fn sync_json_to_yaml(json_path: &Path, yaml_path: &Path) -> Result<PathBuf, SyncError> {
+    // Extract before touching anything, a broken .storage file must not cost us the YAML
+    let config = json_to_yaml(json_path)?;
+    write_yaml_config(yaml_path, &config)
+}
+
+/// Write a dashboard config as YAML, backing up the file it replaces
+pub fn write_yaml_config(yaml_path: &Path, config: &serde_yaml::Value) -> Result<PathBuf, SyncError> {
+    let yaml_content = serde_yaml::to_string(config)
+        .map_err(|e| SyncError::WriteFailed(format!("Failed to serialize YAML: {}", e)))?;
+    
+    // Create backup of YAML file if it exists
+    let backup_path = if yaml_path.exists() {
+        create_backup(yaml_path)?
+    } else {
+        PathBuf::new()
+    };
+    
+    if let Some(parent) = yaml_path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| SyncError::WriteFailed(format!("Failed to create directory: {}", e)))?;
+    }
+    
+    std::fs::write(yaml_path, yaml_content)
+        .map_err(|e| SyncError::WriteFailed(format!("Failed to write YAML file: {}", e)))?;
+    
+    Ok(backup_path)
+}
+
+/// Merge base of a dashboard inside `base_dir`
+pub fn base_path(base_dir: &Path, dashboard_key: &str) -> PathBuf {
+    base_dir.join(format!("{}.json", dashboard_key))
+}
+
+/// Remember what both sides agreed on after a sync
+pub fn save_base(base_path: &Path, base: &SyncBase) -> Result<(), SyncError> {
+    if let Some(parent) = base_path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| SyncError::WriteFailed(format!("Failed to create directory: {}", e)))?;
+    }
+    let content = serde_json::to_string_pretty(base)
+        .map_err(|e| SyncError::WriteFailed(format!("Failed to serialize merge base: {}", e)))?;
+    std::fs::write(base_path, content)
+        .map_err(|e| SyncError::WriteFailed(format!("Failed to write merge base: {}", e)))
+}
+
+pub fn load_base(base_path: &Path) -> Result<SyncBase, SyncError> {
+    if !base_path.exists() {
+        return Err(SyncError::FileNotFound(base_path.to_path_buf()));
+    }
+    let content = std::fs::read_to_string(base_path)
+        .map_err(|e| SyncError::InvalidJson(format!("Failed to read merge base: {}", e)))?;
+    serde_json::from_str(&content)
+        .map_err(|e| SyncError::InvalidJson(format!("Failed to parse merge base: {}", e)))
 }
 
 pub fn get_file_mtime(path: &Path) -> Option<SystemTime> {
@@ -238,7 +410,7 @@ pub fn detect_scripts_sync_needed(source: &Path, destination: &Path) -> FileStat
     
     // Check if destination exists
     if !destination.exists() {
-        return FileStatus::YamlNewer;  // Source exists, destination doesn't - needs sync
+        return FileStatus::YamlChanged;  // Source exists, destination doesn't - needs sync
     }
     
     // Compare modification times of directories
@@ -248,14 +420,14 @@ pub fn detect_scripts_sync_needed(source: &Path, destination: &Path) -> FileStat
     match (source_mtime, dest_mtime) {
         (Some(src_time), Some(dst_time)) => {
             match src_time.cmp(&dst_time) {
-                std::cmp::Ordering::Greater => FileStatus::YamlNewer,
-                std::cmp::Ordering::Less => FileStatus::JsonNewer,
-                std::cmp::Ordering::Equal => FileStatus::Synced,
+                std::cmp::Ordering::Greater => FileStatus::YamlChanged,
+                std::cmp::Ordering::Less => FileStatus::JsonChanged,
+                std::cmp::Ordering::Equal => FileStatus::InSync,
             }
         }
-        (Some(_), None) => FileStatus::YamlNewer,
-        (None, Some(_)) => FileStatus::JsonNewer,
-        (None, None) => FileStatus::Synced,
+        (Some(_), None) => FileStatus::YamlChanged,
+        (None, Some(_)) => FileStatus::JsonChanged,
+        (None, None) => FileStatus::InSync,
     }
 }
 
@@ -159,10 +159,10 @@ fn draw_title(f: &mut Frame, area: Rect, app: &App) {
     let dashboards_count = app.dashboards.len();
     let scripts_count = app.scripts.len();
     let synced_dashboards = app.dashboards.iter().filter(|d| {
-        matches!(d.sync_status.status, crate::sync::FileStatus::Synced)
+        matches!(d.sync_status.status, crate::sync::FileStatus::InSync)
     }).count();
     let synced_scripts = app.scripts.iter().filter(|s| {
-        matches!(s.sync_status.status, crate::sync::FileStatus::Synced)
+        matches!(s.sync_status.status, crate::sync::FileStatus::InSync)
     }).count();
     
     let title_text = format!(
@@ -315,7 +315,7 @@ fn draw_action_column(f: &mut Frame, area: Rect, app: &mut App) {
     let max_width = actions.iter().map(|a| a.len()).max().unwrap_or(15);
     let items: Vec<ListItem> = actions.iter().map(|action| {
         let has_subpanel = matches!(action.as_str(),
-            "List" | "Sync YAML→JSON" | "Sync JSON→YAML" | "Check Status" | "Show Diff"
+            "List" | "Sync YAML→JSON" | "Sync JSON→YAML" | "Merge" | "Check Status" | "Show Diff"
         );
         let padding = max_width - action.len();
         let display = if has_subpanel {
@@ -487,9 +487,11 @@ fn draw_dashboard_list(f: &mut Frame, area: Rect, app: &mut App, modal_visible:
     } else {
         app.dashboards.iter().map(|dashboard| {
             let status_text = match &dashboard.sync_status.status {
-                crate::sync::FileStatus::Synced => "Synced",
-                crate::sync::FileStatus::YamlNewer => "YAML newer",
-                crate::sync::FileStatus::JsonNewer => "JSON newer",
+                crate::sync::FileStatus::InSync => "In sync",
+                crate::sync::FileStatus::YamlChanged => "YAML changed",
+                crate::sync::FileStatus::JsonChanged => "JSON changed",
+                crate::sync::FileStatus::BothChanged => "Both changed",
+                crate::sync::FileStatus::Missing => "Missing",
                 crate::sync::FileStatus::Error(_) => "Error",
             };
             
@@ -510,8 +512,10 @@ fn draw_dashboard_list(f: &mut Frame, area: Rect, app: &mut App, modal_visible:
                 .unwrap_or_else(|| "N/A".to_string());
             
             let status_icon = match &dashboard.sync_status.status {
-                crate::sync::FileStatus::Synced => Some("✓".to_string()),
-                crate::sync::FileStatus::YamlNewer | crate::sync::FileStatus::JsonNewer => Some("⚠".to_string()),
+                crate::sync::FileStatus::InSync => Some("✓".to_string()),
+                crate::sync::FileStatus::YamlChanged | crate::sync::FileStatus::JsonChanged
+                    | crate::sync::FileStatus::BothChanged => Some("⚠".to_string()),
+                crate::sync::FileStatus::Missing => Some("✘".to_string()),
                 crate::sync::FileStatus::Error(_) => None,
             };
             
@@ -808,9 +812,11 @@ fn draw_sync_status(f: &mut Frame, area: Rect, app: &App, modal_visible: bool) {
                     Span::styled("Status: ", Style::default().fg(hex_color(0x888888))),
                     Span::styled(
                         match &dashboard.sync_status.status {
-                            crate::sync::FileStatus::Synced => "Synced",
-                            crate::sync::FileStatus::YamlNewer => "YAML newer",
-                            crate::sync::FileStatus::JsonNewer => "JSON newer",
+                            crate::sync::FileStatus::InSync => "In sync",
+                            crate::sync::FileStatus::YamlChanged => "YAML changed",
+                            crate::sync::FileStatus::JsonChanged => "JSON changed",
+                            crate::sync::FileStatus::BothChanged => "Both changed - merge",
+                            crate::sync::FileStatus::Missing => "Missing",
                             crate::sync::FileStatus::Error(_) => "Error",
                         },
                         Style::default().fg(Color::Green)
@@ -1,6 +1,9 @@
 // Validation logic
 
+use crate::diff::{NodePath, Segment};
 use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
 use std::path::Path;
 use thiserror::Error;
 
@@ -12,23 +15,274 @@ pub enum ValidationError {
     InvalidJson(String),
     #[error("File not found: {0:?}")]
     FileNotFound(std::path::PathBuf),
-    #[error("Invalid dashboard structure: {0}")]
-    InvalidDashboardStructure(String),
+    #[error("Invalid dashboard structure: {}", describe(.0))]
+    InvalidDashboardStructure(Vec<Finding>),
 }
 
-pub fn validate_yaml(_path: &Path) -> Result<(), ValidationError> {
-    // TODO: Implement YAML validation
-    Ok(())
+/// One problem with a dashboard, at a node of the config
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding {
+    pub path: NodePath,
+    pub message: String,
+    /// 1-based line in the YAML source, when the node could be found there
+    pub line: Option<usize>,
 }
 
-pub fn validate_json(_path: &Path) -> Result<(), ValidationError> {
-    // TODO: Implement JSON validation
-    Ok(())
+impl Finding {
+    fn new(path: NodePath, message: impl Into<String>) -> Self {
+        Finding { path, message: message.into(), line: None }
+    }
 }
 
-pub fn validate_dashboard_structure(_json: &Value) -> Result<(), ValidationError> {
-    // TODO: Implement dashboard structure validation
-    // Check for required fields: version, key, data, data.config, data.title, data.url_path
-    Ok(())
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(line) = self.line {
+            write!(f, "line {}: ", line)?;
+        }
+        write!(f, "{}: {}", self.path, self.message)
+    }
 }
 
+/// Findings as one `; `-separated line
+pub fn describe(findings: &[Finding]) -> String {
+    findings.iter().map(|finding| finding.to_string()).collect::<Vec<_>>().join("; ")
+}
+
+/// Path of the dashboard config inside a `.storage/lovelace.<key>` file
+pub fn config_path() -> NodePath {
+    NodePath::root().key("data").key("config")
+}
+
+/// Required envelope fields, then the config under `data.config`
+pub fn check_envelope(json: &Value) -> Vec<Finding> {
+    let root = NodePath::root();
+    let mut findings = Vec::new();
+    if !json.is_object() {
+        findings.push(Finding::new(root, "storage file must be an object"));
+        return findings;
+    }
+    if !json.get("version").is_some_and(Value::is_number) {
+        findings.push(Finding::new(root.key("version"), "missing or not a number"));
+    }
+    if !json.get("key").is_some_and(Value::is_string) {
+        findings.push(Finding::new(root.key("key"), "missing or not a string"));
+    }
+    let Some(data) = json.get("data").filter(|data| data.is_object()) else {
+        findings.push(Finding::new(root.key("data"), "missing or not an object"));
+        return findings;
+    };
+    for field in ["title", "url_path"] {
+        if !data.get(field).is_some_and(Value::is_string) {
+            findings.push(Finding::new(root.key("data").key(field), "missing or not a string"));
+        }
+    }
+    match data.get("config") {
+        Some(config) => findings.extend(check_config_at(config, &config_path())),
+        None => findings.push(Finding::new(config_path(), "missing")),
+    }
+    findings
+}
+
+/// Lovelace shape of a dashboard config: views are objects with unique paths,
+/// every card (nested ones included) has a `type`
+pub fn check_config(config: &Value) -> Vec<Finding> {
+    check_config_at(config, &NodePath::root())
+}
+
+fn check_config_at(config: &Value, at: &NodePath) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let Some(config) = config.as_object() else {
+        findings.push(Finding::new(at.clone(), "config must be an object"));
+        return findings;
+    };
+    let views = match config.get("views") {
+        Some(Value::Array(views)) => views,
+        Some(_) => {
+            findings.push(Finding::new(at.key("views"), "must be a list"));
+            return findings;
+        }
+        // Strategy dashboards are generated by HA and have no views
+        None if config.contains_key("strategy") => return findings,
+        None => {
+            findings.push(Finding::new(at.key("views"), "missing"));
+            return findings;
+        }
+    };
+
+    let mut view_paths: HashMap<&str, usize> = HashMap::new();
+    for (i, view) in views.iter().enumerate() {
+        let view_at = at.key("views").index(i);
+        let Some(view) = view.as_object() else {
+            findings.push(Finding::new(view_at, "view must be an object"));
+            continue;
+        };
+        if let Some(path) = view.get("path").and_then(Value::as_str) {
+            if let Some(first) = view_paths.insert(path, i) {
+                findings.push(Finding::new(view_at.key("path"),
+                    format!("duplicate view path '{}' (also views[{}])", path, first)));
+            }
+        }
+        check_cards(view.get("cards"), &view_at.key("cards"), &mut findings);
+        match view.get("sections") {
+            Some(Value::Array(sections)) => {
+                for (j, section) in sections.iter().enumerate() {
+                    let section_at = view_at.key("sections").index(j);
+                    check_cards(section.get("cards"), &section_at.key("cards"), &mut findings);
+                }
+            }
+            Some(_) => findings.push(Finding::new(view_at.key("sections"), "must be a list")),
+            None => {}
+        }
+    }
+    findings
+}
+
+fn check_cards(cards: Option<&Value>, at: &NodePath, findings: &mut Vec<Finding>) {
+    match cards {
+        None => {}
+        Some(Value::Array(cards)) => {
+            for (i, card) in cards.iter().enumerate() {
+                check_card(card, &at.index(i), findings);
+            }
+        }
+        Some(_) => findings.push(Finding::new(at.clone(), "must be a list")),
+    }
+}
+
+fn check_card(card: &Value, at: &NodePath, findings: &mut Vec<Finding>) {
+    let Some(card) = card.as_object() else {
+        findings.push(Finding::new(at.clone(), "card must be an object"));
+        return;
+    };
+    match card.get("type") {
+        Some(Value::String(card_type)) if !card_type.is_empty() => {}
+        Some(_) => findings.push(Finding::new(at.key("type"), "card type must be a non-empty string")),
+        None => findings.push(Finding::new(at.clone(), "card has no type")),
+    }
+    // Stacks and grids hold `cards`, conditional and similar wrappers one `card`
+    check_cards(card.get("cards"), &at.key("cards"), findings);
+    if let Some(inner) = card.get("card") {
+        check_card(inner, &at.key("card"), findings);
+    }
+}
+
+/// Fill in YAML line numbers for findings below `config_at`
+pub fn add_lines(findings: &mut [Finding], yaml_text: &str, config_at: &NodePath) {
+    for finding in findings.iter_mut() {
+        if finding.path.starts_with(config_at) {
+            let relative = NodePath(finding.path.0[config_at.0.len()..].to_vec());
+            finding.line = locate(yaml_text, &relative);
+        }
+    }
+}
+
+/// A YAML line: indentation, column after any `- ` markers, the text from there,
+/// and whether it opens a sequence item
+struct YamlLine<'a> {
+    indent: usize,
+    column: usize,
+    rest: &'a str,
+    item: bool,
+}
+
+fn yaml_line(line: &str) -> Option<YamlLine<'_>> {
+    let trimmed = line.trim_start();
+    if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with("---") {
+        return None;
+    }
+    let indent = line.len() - trimmed.len();
+    let mut rest = trimmed;
+    while let Some(after) = rest.strip_prefix("- ").or_else(|| (rest == "-").then_some("")) {
+        rest = after.trim_start();
+    }
+    Some(YamlLine { indent, column: line.len() - rest.len(), rest, item: rest.len() < trimmed.len() })
+}
+
+fn is_key(rest: &str, name: &str) -> bool {
+    [name.to_string(), format!("\"{}\"", name), format!("'{}'", name)]
+        .iter()
+        .any(|key| rest.strip_prefix(key.as_str()).is_some_and(|after| after.starts_with(':')))
+}
+
+/// 1-based line of the node at `path` in block-style YAML
+///
+/// Best effort: flow collections (`cards: [...]`) are not descended into, the
+/// deepest node found on the way is reported instead.
+pub fn locate(yaml_text: &str, path: &NodePath) -> Option<usize> {
+    let lines: Vec<Option<YamlLine>> = yaml_text.lines().map(yaml_line).collect();
+    let (mut start, mut end) = (0, lines.len());
+    let mut found = None;
+
+    for segment in &path.0 {
+        let Some(first) = lines[start..end].iter().flatten().next() else {
+            break;
+        };
+        let (first_indent, first_column) = (first.indent, first.column);
+        match segment {
+            Segment::Key(name) => {
+                let Some(line) = (start..end).find(|&l| lines[l].as_ref()
+                    .is_some_and(|y| y.column == first_column && is_key(y.rest, name))) else {
+                    break;
+                };
+                // The value block, which may be a sequence at the key's own indentation
+                end = (line + 1..end).find(|&l| lines[l].as_ref()
+                    .is_some_and(|y| y.indent < first_column || (y.indent == first_column && !y.item)))
+                    .unwrap_or(end);
+                start = line + 1;
+                found = Some(line + 1);
+            }
+            Segment::Index(index) => {
+                let Some(line) = (start..end)
+                    .filter(|&l| lines[l].as_ref().is_some_and(|y| y.item && y.indent == first_indent))
+                    .nth(*index) else {
+                    break;
+                };
+                // An item's first key shares the line with its `- `
+                end = (line + 1..end).find(|&l| lines[l].as_ref().is_some_and(|y| y.indent <= first_indent))
+                    .unwrap_or(end);
+                start = line;
+                found = Some(line + 1);
+            }
+        }
+    }
+    found
+}
+
+/// Parse a YAML dashboard and check its shape, findings carry line numbers
+pub fn validate_yaml(path: &Path) -> Result<(), ValidationError> {
+    if !path.exists() {
+        return Err(ValidationError::FileNotFound(path.to_path_buf()));
+    }
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| ValidationError::InvalidYaml(format!("Failed to read YAML file: {}", e)))?;
+    let config: Value = serde_yaml::from_str(&text)
+        .map_err(|e| ValidationError::InvalidYaml(e.to_string()))?;
+    let mut findings = check_config(&config);
+    add_lines(&mut findings, &text, &NodePath::root());
+    if findings.is_empty() {
+        Ok(())
+    } else {
+        Err(ValidationError::InvalidDashboardStructure(findings))
+    }
+}
+
+/// Parse a `.storage/lovelace.<key>` file and check its envelope and config
+pub fn validate_json(path: &Path) -> Result<(), ValidationError> {
+    if !path.exists() {
+        return Err(ValidationError::FileNotFound(path.to_path_buf()));
+    }
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| ValidationError::InvalidJson(format!("Failed to read JSON file: {}", e)))?;
+    let json: Value = serde_json::from_str(&text)
+        .map_err(|e| ValidationError::InvalidJson(e.to_string()))?;
+    validate_dashboard_structure(&json)
+}
+
+pub fn validate_dashboard_structure(json: &Value) -> Result<(), ValidationError> {
+    let findings = check_envelope(json);
+    if findings.is_empty() {
+        Ok(())
+    } else {
+        Err(ValidationError::InvalidDashboardStructure(findings))
+    }
+}
@@ -0,0 +1,43 @@
+// Dashboard diffs - node paths for views/cards/keys and line diffs of normalized YAML
+
+use hasync::diff::{self, DiffLine};
+use serde_yaml::Value;
+
+fn yaml(text: &str) -> Value {
+    serde_yaml::from_str(text).unwrap()
+}
+
+#[test]
+fn changes_are_addressed_by_view_and_card() {
+    let old = yaml("views:\n  - cards: [{type: clock}]\n  - cards: [{type: light, entity: light.a}]\n");
+    let new = yaml("views:\n  - cards: [{type: clock}]\n  - cards: [{type: light, entity: light.b}, {type: map}]\n  - title: New\n");
+    let paths: Vec<String> = diff::diff(&old, &new).iter().map(|c| c.path.to_string()).collect();
+    assert_eq!(paths, vec!["views[1].cards[0].entity", "views[1].cards[1]", "views[2]"]);
+}
+
+#[test]
+fn key_order_does_not_count_as_a_difference() {
+    let a = yaml("title: Home\nviews: []\n");
+    let b = yaml("views: []\ntitle: Home\n");
+    assert!(diff::diff(&a, &b).is_empty());
+    assert_eq!(diff::normalized_yaml(&a), diff::normalized_yaml(&b));
+}
+
+#[test]
+fn line_diff_keeps_common_lines_and_marks_edits() {
+    let lines = diff::line_diff("a\nb\nc\nd\n", "a\nc\nx\nd\n");
+    assert_eq!(lines, vec![
+        DiffLine::Same("a".to_string()),
+        DiffLine::Removed("b".to_string()),
+        DiffLine::Same("c".to_string()),
+        DiffLine::Added("x".to_string()),
+        DiffLine::Same("d".to_string()),
+    ]);
+}
+
+#[test]
+fn line_diff_of_identical_texts_has_no_edits() {
+    let lines = diff::line_diff("a\nb\n", "a\nb\n");
+    assert!(lines.iter().all(|line| matches!(line, DiffLine::Same(_))));
+    assert_eq!(lines.len(), 2);
+}
@@ -0,0 +1,163 @@
+// Three-way dashboard merges - structural diffs against the last-synced base
+
+use hasync::diff::{self, ChangeKind, NodePath};
+use hasync::merge::{self, Resolution};
+use serde_yaml::Value;
+
+fn yaml(text: &str) -> Value {
+    serde_yaml::from_str(text).unwrap()
+}
+
+const BASE: &str = r#"
+title: Home
+views:
+  - title: Living
+    path: living
+    cards:
+      - type: entities
+        entities: [light.sofa]
+      - type: weather-forecast
+        entity: weather.home
+  - title: Kitchen
+    path: kitchen
+    cards: []
+"#;
+
+#[test]
+fn diff_paths_name_views_cards_and_keys() {
+    let changed = yaml(&BASE.replace("weather.home", "weather.roof").replace("cards: []", "cards: [{type: clock}]"));
+    let changes = diff::diff(&yaml(BASE), &changed);
+    let paths: Vec<String> = changes.iter().map(|c| format!("{:?} {}", c.kind, c.path)).collect();
+    assert_eq!(paths, vec![
+        "Changed views[0].cards[1].entity",
+        "Added views[1].cards[0]",
+    ]);
+    assert_eq!(changes[0].kind, ChangeKind::Changed);
+}
+
+#[test]
+fn independent_edits_merge_without_conflicts() {
+    let base = yaml(BASE);
+    let ours = yaml(&BASE.replace("title: Home", "title: House"));
+    let theirs = yaml(&BASE.replace("weather.home", "weather.roof"));
+
+    let result = merge::merge(&base, &ours, &theirs);
+    assert!(result.conflicts.is_empty());
+    assert_eq!(result.applied, 2);
+    assert_eq!(result.merged, yaml(&BASE.replace("title: Home", "title: House").replace("weather.home", "weather.roof")));
+}
+
+#[test]
+fn appending_a_card_does_not_clash_with_editing_another() {
+    let base = yaml(BASE);
+    let ours = yaml(&BASE.replace("light.sofa", "light.lamp"));
+    let theirs = yaml(&BASE.replace("        entity: weather.home", "        entity: weather.home\n      - type: clock"));
+
+    let result = merge::merge(&base, &ours, &theirs);
+    assert!(result.conflicts.is_empty());
+    assert_eq!(diff::get(&result.merged, &NodePath::root().key("views").index(0).key("cards").index(2).key("type")),
+        Some(&Value::String("clock".to_string())));
+    assert_eq!(diff::inline(diff::get(&result.merged, &NodePath::root().key("views").index(0).key("cards").index(0).key("entities"))),
+        r#"["light.lamp"]"#);
+}
+
+#[test]
+fn same_edit_on_both_sides_is_not_a_conflict() {
+    let base = yaml(BASE);
+    let both = yaml(&BASE.replace("cards: []", "cards: [{type: clock}]"));
+
+    let result = merge::merge(&base, &both, &both);
+    assert!(result.conflicts.is_empty());
+    assert_eq!(result.merged, both);
+}
+
+#[test]
+fn diverging_edits_conflict_at_the_node() {
+    let base = yaml(BASE);
+    let ours = yaml(&BASE.replace("weather.home", "weather.yaml"));
+    let theirs = yaml(&BASE.replace("weather.home", "weather.ui").replace("title: Home", "title: House"));
+
+    let result = merge::merge(&base, &ours, &theirs);
+    assert_eq!(result.applied, 1);
+    assert_eq!(result.conflicts.len(), 1);
+    let conflict = &result.conflicts[0];
+    assert_eq!(conflict.path.to_string(), "views[0].cards[1].entity");
+    assert_eq!(conflict.base, Some(Value::String("weather.home".to_string())));
+
+    assert_eq!(result.resolve(&[Resolution::Ours]),
+        yaml(&BASE.replace("weather.home", "weather.yaml").replace("title: Home", "title: House")));
+    assert_eq!(result.resolve(&[Resolution::Theirs]), theirs);
+}
+
+#[test]
+fn removing_a_card_the_other_side_edited_conflicts() {
+    let base = yaml(BASE);
+    let ours = yaml(&BASE.replace("      - type: weather-forecast\n        entity: weather.home\n", ""));
+    let theirs = yaml(&BASE.replace("weather.home", "weather.roof"));
+
+    let result = merge::merge(&base, &ours, &theirs);
+    assert_eq!(result.conflicts.len(), 1);
+    assert_eq!(result.conflicts[0].path.to_string(), "views[0].cards[1]");
+    assert_eq!(result.conflicts[0].ours, None);
+    assert_eq!(result.resolve(&[Resolution::Ours]), ours);
+}
+
+#[test]
+fn different_appends_to_the_same_list_conflict() {
+    let base = yaml(BASE);
+    let ours = yaml(&BASE.replace("cards: []", "cards: [{type: clock}]"));
+    let theirs = yaml(&BASE.replace("cards: []", "cards: [{type: map}]"));
+
+    let result = merge::merge(&base, &ours, &theirs);
+    assert_eq!(result.conflicts.len(), 1);
+    assert_eq!(result.conflicts[0].path.to_string(), "views[1].cards[0]");
+}
+
+#[test]
+fn merged_yaml_keeps_key_order() {
+    let base = yaml(BASE);
+    let ours = yaml(&BASE.replace("title: Kitchen", "title: Cooking"));
+    let result = merge::merge(&base, &ours, &base);
+    let text = serde_yaml::to_string(&result.merged).unwrap();
+    assert!(text.find("title: Cooking").unwrap() < text.find("path: kitchen").unwrap());
+}
+
+const FOUR_CARDS: &str = r#"
+views:
+  - path: home
+    cards:
+      - {type: a}
+      - {type: b}
+      - {type: c}
+      - {type: d}
+"#;
+
+#[test]
+fn removing_several_edited_cards_is_one_conflict_at_the_list() {
+    let base = yaml(FOUR_CARDS);
+    let ours = yaml(&FOUR_CARDS.replace("{type: c}", "{type: c, title: C}").replace("{type: d}", "{type: d, title: D}"));
+    let theirs = yaml(&FOUR_CARDS.replace("      - {type: c}\n      - {type: d}\n", ""));
+
+    let result = merge::merge(&base, &ours, &theirs);
+    assert_eq!(result.conflicts.len(), 1);
+    assert_eq!(result.conflicts[0].path.to_string(), "views[0].cards");
+
+    // Picking the deletion drops both cards
+    assert_eq!(result.resolve(&[Resolution::Theirs]), theirs);
+    assert_eq!(result.resolve(&[Resolution::Ours]), ours);
+}
+
+#[test]
+fn resolutions_apply_from_the_back_of_a_list() {
+    let base = yaml(FOUR_CARDS);
+    let conflict_at = |index: usize| merge::Conflict {
+        path: NodePath::root().key("views").index(0).key("cards").index(index),
+        base: None,
+        ours: None,
+        theirs: None,
+    };
+    // Resolutions picking removals at [2] and [3], listed in ascending order
+    let result = merge::MergeResult { merged: base.clone(), conflicts: vec![conflict_at(2), conflict_at(3)], applied: 0 };
+    let resolved = result.resolve(&[Resolution::Theirs, Resolution::Theirs]);
+    assert_eq!(resolved, yaml(&FOUR_CARDS.replace("      - {type: c}\n      - {type: d}\n", "")));
+}
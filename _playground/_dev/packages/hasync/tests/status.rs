@@ -0,0 +1,105 @@
+// Content-based dashboard status - hashes of the last sync, then semantic comparison
+
+use hasync::sync::{self, FileStatus, SyncBase};
+use std::path::{Path, PathBuf};
+
+const YAML: &str = "title: Home\nviews:\n  - title: Living\n    cards:\n      - type: clock\n";
+
+struct Dashboard {
+    yaml: PathBuf,
+    json: PathBuf,
+    base: PathBuf,
+}
+
+impl Dashboard {
+    /// Dashboard synced YAML→JSON, with its base recorded the way the app does
+    fn synced(name: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!("hasync-status-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let dashboard = Dashboard {
+            yaml: dir.join("main.yaml"),
+            json: dir.join("lovelace.dashboard_main"),
+            base: sync::base_path(&dir.join("base"), "dashboard_main"),
+        };
+        std::fs::write(&dashboard.yaml, YAML).unwrap();
+        sync::sync_yaml_to_json(&dashboard.yaml, &dashboard.json, "dashboard_main", "Main", "main", false).unwrap();
+        dashboard.record_base();
+        dashboard
+    }
+
+    fn record_base(&self) {
+        let base = SyncBase {
+            config: sync::read_yaml_config(&self.yaml).unwrap(),
+            yaml_hash: sync::content_hash(&self.yaml),
+            json_hash: sync::content_hash(&self.json),
+        };
+        sync::save_base(&self.base, &base).unwrap();
+    }
+
+    fn status(&self) -> FileStatus {
+        sync::detect_dashboard_status(&self.yaml, &self.json, &self.base)
+    }
+
+    fn edit(path: &Path, from: &str, to: &str) {
+        let content = std::fs::read_to_string(path).unwrap();
+        assert!(content.contains(from));
+        std::fs::write(path, content.replace(from, to)).unwrap();
+    }
+}
+
+#[test]
+fn freshly_synced_is_in_sync() {
+    assert_eq!(Dashboard::synced("fresh").status(), FileStatus::InSync);
+}
+
+#[test]
+fn touching_a_file_is_not_drift() {
+    let dashboard = Dashboard::synced("touch");
+    let later = std::time::SystemTime::now() + std::time::Duration::from_secs(120);
+    std::fs::File::options().write(true).open(&dashboard.yaml).unwrap().set_modified(later).unwrap();
+    assert_eq!(dashboard.status(), FileStatus::InSync);
+}
+
+#[test]
+fn json_rewritten_with_identical_content_is_in_sync() {
+    let dashboard = Dashboard::synced("rewrite");
+    let storage: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&dashboard.json).unwrap()).unwrap();
+    std::fs::write(&dashboard.json, serde_json::to_string(&storage).unwrap()).unwrap();
+    assert_ne!(sync::load_base(&dashboard.base).unwrap().json_hash, sync::content_hash(&dashboard.json));
+    assert_eq!(dashboard.status(), FileStatus::InSync);
+}
+
+#[test]
+fn each_side_is_told_apart_from_the_base() {
+    let dashboard = Dashboard::synced("sides");
+    Dashboard::edit(&dashboard.yaml, "type: clock", "type: map");
+    assert_eq!(dashboard.status(), FileStatus::YamlChanged);
+
+    let dashboard = Dashboard::synced("sides-json");
+    Dashboard::edit(&dashboard.json, "Living", "Lounge");
+    assert_eq!(dashboard.status(), FileStatus::JsonChanged);
+
+    Dashboard::edit(&dashboard.yaml, "type: clock", "type: map");
+    assert_eq!(dashboard.status(), FileStatus::BothChanged);
+    assert!(sync::yaml_has_local_edits(&dashboard.yaml, &dashboard.json, &dashboard.base));
+}
+
+#[test]
+fn same_edit_on_both_sides_is_in_sync() {
+    let dashboard = Dashboard::synced("same-edit");
+    Dashboard::edit(&dashboard.yaml, "type: clock", "type: map");
+    Dashboard::edit(&dashboard.json, "\"clock\"", "\"map\"");
+    assert_eq!(dashboard.status(), FileStatus::InSync);
+}
+
+#[test]
+fn missing_side_and_broken_yaml() {
+    let dashboard = Dashboard::synced("missing");
+    std::fs::write(&dashboard.yaml, "views: [unclosed\n").unwrap();
+    assert!(matches!(dashboard.status(), FileStatus::Error(_)));
+    assert!(sync::yaml_has_local_edits(&dashboard.yaml, &dashboard.json, &dashboard.base));
+
+    std::fs::remove_file(&dashboard.json).unwrap();
+    assert_eq!(dashboard.status(), FileStatus::Missing);
+}
@@ -0,0 +1,111 @@
+// JSON→YAML reverse sync against temp dashboards
+
+use hasync::sync::{self, SyncError};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+const STORAGE: &str = r#"{
+  "version": 1,
+  "key": "lovelace.dashboard_main",
+  "data": {
+    "config": {
+      "title": "Main",
+      "views": [
+        { "title": "Home", "path": "home", "cards": [ { "type": "entities", "entities": ["light.kitchen"] } ] }
+      ]
+    },
+    "title": "Main",
+    "url_path": "main"
+  }
+}"#;
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("hasync-sync-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn set_mtime(path: &PathBuf, offset_secs: i64) {
+    let base = SystemTime::now() - Duration::from_secs(3600);
+    let time = if offset_secs >= 0 {
+        base + Duration::from_secs(offset_secs as u64)
+    } else {
+        base - Duration::from_secs(offset_secs.unsigned_abs())
+    };
+    std::fs::File::options().write(true).open(path).unwrap().set_modified(time).unwrap();
+}
+
+#[test]
+fn extracts_data_config_in_storage_order() {
+    let dir = temp_dir("extract");
+    let json = dir.join("lovelace.dashboard_main");
+    std::fs::write(&json, STORAGE).unwrap();
+
+    let config = sync::json_to_yaml(&json).unwrap();
+    let yaml = serde_yaml::to_string(&config).unwrap();
+    assert!(yaml.starts_with("title: Main\nviews:\n"), "{}", yaml);
+    assert!(yaml.find("path: home").unwrap() < yaml.find("cards:").unwrap());
+    assert!(!yaml.contains("url_path"));
+}
+
+#[test]
+fn missing_data_config_is_an_error() {
+    let dir = temp_dir("no-config");
+    let json = dir.join("lovelace.broken");
+    std::fs::write(&json, r#"{"version": 1, "data": {}}"#).unwrap();
+    assert!(matches!(sync::json_to_yaml(&json), Err(SyncError::InvalidJson(_))));
+}
+
+#[test]
+fn reverse_sync_backs_up_and_round_trips() {
+    let dir = temp_dir("round-trip");
+    let json = dir.join("lovelace.dashboard_main");
+    let yaml = dir.join("dashboards/main.yaml");
+    std::fs::write(&json, STORAGE).unwrap();
+    std::fs::create_dir_all(yaml.parent().unwrap()).unwrap();
+    std::fs::write(&yaml, "title: Old\n").unwrap();
+
+    let backup = sync::sync_json_to_yaml(&json, &yaml).unwrap();
+    assert_eq!(std::fs::read_to_string(&backup).unwrap(), "title: Old\n");
+
+    let rebuilt = sync::yaml_to_json(&yaml, "lovelace.dashboard_main", "Main", "main").unwrap();
+    let original: serde_json::Value = serde_json::from_str(STORAGE).unwrap();
+    assert_eq!(rebuilt["data"]["config"], original["data"]["config"]);
+}
+
+#[test]
+fn broken_json_leaves_yaml_untouched() {
+    let dir = temp_dir("broken");
+    let json = dir.join("lovelace.dashboard_main");
+    let yaml = dir.join("main.yaml");
+    std::fs::write(&json, "{ not json").unwrap();
+    std::fs::write(&yaml, "title: Mine\n").unwrap();
+
+    assert!(sync::sync_json_to_yaml(&json, &yaml).is_err());
+    assert_eq!(std::fs::read_to_string(&yaml).unwrap(), "title: Mine\n");
+}
+
+#[test]
+fn newer_differing_yaml_counts_as_local_edits() {
+    let dir = temp_dir("local-edits");
+    let json = dir.join("lovelace.dashboard_main");
+    let yaml = dir.join("main.yaml");
+    std::fs::write(&json, STORAGE).unwrap();
+    std::fs::write(&yaml, "title: Edited locally\nviews: []\n").unwrap();
+    // Never synced, so only the mtimes tell which side moved
+    let no_base = dir.join("base/lovelace.dashboard_main.json");
+
+    set_mtime(&json, 0);
+    set_mtime(&yaml, 60);
+    assert!(sync::yaml_has_local_edits(&yaml, &json, &no_base));
+
+    // Older YAML is what the reverse sync is for
+    set_mtime(&yaml, -60);
+    assert!(!sync::yaml_has_local_edits(&yaml, &json, &no_base));
+
+    // Newer but identical YAML loses nothing
+    sync::sync_json_to_yaml(&json, &yaml).unwrap();
+    set_mtime(&yaml, 60);
+    assert!(!sync::yaml_has_local_edits(&yaml, &json, &no_base));
+}
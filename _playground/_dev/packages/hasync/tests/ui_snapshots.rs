@@ -0,0 +1,131 @@
+// Headless UI snapshots - scripted keys through events::handle_event, rendered to
+// TestBackend and compared with tests/snapshots/*.txt
+//
+// Approve changes with: UPDATE_SNAPSHOTS=1 cargo test --test ui_snapshots
+
+use crossterm::event::KeyCode;
+use hasync::app::App;
+use hasync::config::AppConfig;
+use hasync::{events, ui};
+use tui_common::testing::ScratchDir;
+
+const WIDTH: u16 = 120;
+const HEIGHT: u16 = 32;
+
+tui_common::ui_test_harness!(App, handle: events::handle_event, draw: ui::ui, size: (WIDTH, HEIGHT));
+
+fn fixture_app() -> App {
+    let fixture = include_str!("fixtures/ui.yaml");
+    let config: AppConfig = serde_yaml::from_str(fixture).unwrap();
+    App::with_config(config)
+}
+
+#[test]
+fn dashboards_view() {
+    let mut app = fixture_app();
+    assert_snapshot("dashboards_view", &render(&mut app));
+}
+
+#[test]
+fn actions_column_focused() {
+    let mut app = fixture_app();
+    keys(&mut app, &[KeyCode::Right]);
+    assert_snapshot("actions_column_focused", &render(&mut app));
+}
+
+#[test]
+fn scripts_view() {
+    let mut app = fixture_app();
+    keys(&mut app, &[KeyCode::Down]);
+    assert_snapshot("scripts_view", &render(&mut app));
+}
+
+#[test]
+fn quit_key_stops_the_loop() {
+    let mut app = fixture_app();
+    keys(&mut app, &[KeyCode::Char('q')]);
+    assert!(app.should_quit);
+}
+
+#[test]
+fn merge_conflict_screen() {
+    let scratch = ScratchDir::new("hasync-ui", "merge");
+    let dir = scratch.path();
+    let yaml = dir.join("main.yaml");
+    let json = dir.join("lovelace.dashboard_main");
+
+    let mut config: AppConfig = serde_yaml::from_str(include_str!("fixtures/ui.yaml")).unwrap();
+    config.dashboards[0].yaml_source = yaml.clone();
+    config.dashboards[0].json_storage = json.clone();
+    let mut app = App::with_config(config);
+    app.base_dir = dir.join("base");
+    app.selected_dashboard = Some(0);
+
+    std::fs::write(&yaml, "title: Home\nviews:\n  - title: Living\n    cards:\n      - type: light\n        entity: light.sofa\n").unwrap();
+    app.sync_yaml_to_json_selected(false).unwrap();
+
+    // Edited in the HA UI and in the YAML since
+    let storage = std::fs::read_to_string(&json).unwrap().replace("light.sofa", "light.ui");
+    std::fs::write(&json, storage).unwrap();
+    std::fs::write(&yaml, "title: House\nviews:\n  - title: Living\n    cards:\n      - type: light\n        entity: light.yaml\n").unwrap();
+
+    keys(&mut app, &[KeyCode::Right, KeyCode::Down, KeyCode::Down, KeyCode::Down, KeyCode::Enter]);
+    assert_snapshot("merge_conflict_screen", &render(&mut app));
+
+    keys(&mut app, &[KeyCode::Right, KeyCode::Enter]);
+    assert!(app.popup.is_none());
+    let merged = std::fs::read_to_string(&yaml).unwrap();
+    assert!(merged.contains("title: House") && merged.contains("light.ui"), "{}", merged);
+}
+
+#[test]
+fn dashboard_diff_popup() {
+    let scratch = ScratchDir::new("hasync-ui", "diff");
+    let dir = scratch.path();
+    let yaml = dir.join("main.yaml");
+    let json = dir.join("lovelace.dashboard_main");
+
+    let mut config: AppConfig = serde_yaml::from_str(include_str!("fixtures/ui.yaml")).unwrap();
+    config.dashboards[0].yaml_source = yaml.clone();
+    config.dashboards[0].json_storage = json.clone();
+    let mut app = App::with_config(config);
+    app.base_dir = dir.join("base");
+    app.selected_dashboard = Some(0);
+
+    std::fs::write(&yaml, "title: Home\nviews:\n  - title: Living\n    cards:\n      - type: light\n        entity: light.sofa\n").unwrap();
+    app.sync_yaml_to_json_selected(false).unwrap();
+    std::fs::write(&yaml, "title: Home\nviews:\n  - title: Lounge\n    cards:\n      - type: light\n        entity: light.sofa\n      - type: clock\n").unwrap();
+
+    keys(&mut app, &[KeyCode::Right, KeyCode::Down, KeyCode::Down, KeyCode::Down, KeyCode::Down, KeyCode::Down, KeyCode::Enter]);
+    assert_snapshot("dashboard_diff_popup", &render(&mut app));
+
+    keys(&mut app, &[KeyCode::Tab]);
+    assert_snapshot("dashboard_diff_popup_lines", &render(&mut app));
+
+    keys(&mut app, &[KeyCode::Esc]);
+    assert!(app.popup.is_none());
+}
+
+#[test]
+fn invalid_dashboard_asks_before_writing() {
+    let scratch = ScratchDir::new("hasync-ui", "invalid");
+    let dir = scratch.path();
+    let yaml = dir.join("main.yaml");
+    let json = dir.join("lovelace.dashboard_main");
+
+    let mut config: AppConfig = serde_yaml::from_str(include_str!("fixtures/ui.yaml")).unwrap();
+    config.dashboards[0].yaml_source = yaml.clone();
+    config.dashboards[0].json_storage = json.clone();
+    let mut app = App::with_config(config);
+    app.base_dir = dir.join("base");
+    app.selected_dashboard = Some(0);
+    std::fs::write(&yaml, "views:\n  - title: Living\n    cards:\n      - entity: light.sofa\n").unwrap();
+
+    // Sync YAML→JSON, confirm with Yes
+    keys(&mut app, &[KeyCode::Right, KeyCode::Down, KeyCode::Enter, KeyCode::Left, KeyCode::Enter]);
+    assert!(!json.exists());
+    assert_snapshot("invalid_dashboard_confirm", &render(&mut app));
+
+    keys(&mut app, &[KeyCode::Left, KeyCode::Enter]);
+    assert!(json.exists());
+}
@@ -0,0 +1,112 @@
+// Dashboard validation - envelope fields, view/card shape, YAML line numbers
+
+use hasync::diff::NodePath;
+use hasync::sync::{self, SyncError};
+use hasync::validation::{self, ValidationError};
+use serde_json::json;
+
+const DASHBOARD: &str = "\
+title: Home
+views:
+  - title: Living
+    path: living
+    cards:
+      - type: entities
+        entities:
+          - light.sofa
+      - entity: weather.home
+  - title: Printer
+    path: living
+    cards:
+      - type: vertical-stack
+        cards:
+          - type: gauge
+          - name: no type here
+";
+
+fn temp_dir(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("hasync-validation-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn envelope_fields_are_required() {
+    let findings = validation::check_envelope(&json!({ "version": "1", "data": { "config": { "views": [] } } }));
+    let paths: Vec<String> = findings.iter().map(|f| f.path.to_string()).collect();
+    assert_eq!(paths, vec!["version", "key", "data.title", "data.url_path"]);
+
+    let valid = json!({ "version": 1, "key": "lovelace.main", "data": { "config": { "views": [] }, "title": "Main", "url_path": "main" } });
+    assert!(validation::validate_dashboard_structure(&valid).is_ok());
+}
+
+#[test]
+fn cards_need_a_type_and_views_unique_paths() {
+    let config: serde_json::Value = serde_yaml::from_str(DASHBOARD).unwrap();
+    let mut findings = validation::check_config(&config);
+    validation::add_lines(&mut findings, DASHBOARD, &NodePath::root());
+    let found: Vec<String> = findings.iter().map(|f| f.to_string()).collect();
+    assert_eq!(found, vec![
+        "line 9: views[0].cards[1]: card has no type",
+        "line 11: views[1].path: duplicate view path 'living' (also views[0])",
+        "line 16: views[1].cards[0].cards[1]: card has no type",
+    ]);
+}
+
+#[test]
+fn flow_collections_report_the_nearest_block_line() {
+    let text = "views:\n  - title: A\n    cards: [{entity: light.a}]\n";
+    let config: serde_json::Value = serde_yaml::from_str(text).unwrap();
+    let mut findings = validation::check_config(&config);
+    validation::add_lines(&mut findings, text, &NodePath::root());
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].line, Some(3));
+}
+
+#[test]
+fn strategy_dashboards_have_no_views() {
+    assert!(validation::check_config(&json!({ "strategy": { "type": "original-states" } })).is_empty());
+    assert_eq!(validation::check_config(&json!({ "title": "Empty" }))[0].path.to_string(), "views");
+}
+
+#[test]
+fn validate_yaml_and_json_files() {
+    let dir = temp_dir("files");
+    let yaml = dir.join("main.yaml");
+    std::fs::write(&yaml, DASHBOARD).unwrap();
+    match validation::validate_yaml(&yaml) {
+        Err(ValidationError::InvalidDashboardStructure(findings)) => assert_eq!(findings[0].line, Some(9)),
+        other => panic!("expected findings, got {:?}", other),
+    }
+
+    std::fs::write(&yaml, "views: [unclosed\n").unwrap();
+    assert!(matches!(validation::validate_yaml(&yaml), Err(ValidationError::InvalidYaml(_))));
+
+    let json = dir.join("lovelace.main");
+    std::fs::write(&json, "{\"version\": 1,").unwrap();
+    assert!(matches!(validation::validate_json(&json), Err(ValidationError::InvalidJson(_))));
+}
+
+#[test]
+fn sync_refuses_invalid_output_unless_forced() {
+    let dir = temp_dir("sync");
+    let yaml = dir.join("main.yaml");
+    let json = dir.join("lovelace.main");
+    std::fs::write(&yaml, DASHBOARD).unwrap();
+    std::fs::write(&json, "{}").unwrap();
+
+    match sync::sync_yaml_to_json(&yaml, &json, "lovelace.main", "Main", "main", false) {
+        Err(SyncError::Invalid(findings)) => {
+            assert_eq!(findings.len(), 3);
+            assert_eq!(findings[0].path.to_string(), "data.config.views[0].cards[1]");
+            assert_eq!(findings[0].line, Some(9));
+        }
+        other => panic!("expected a refusal, got {:?}", other),
+    }
+    assert_eq!(std::fs::read_to_string(&json).unwrap(), "{}");
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 2, "no backup for a refused sync");
+
+    sync::sync_yaml_to_json(&yaml, &json, "lovelace.main", "Main", "main", true).unwrap();
+    assert!(std::fs::read_to_string(&json).unwrap().contains("vertical-stack"));
+}